@@ -32,6 +32,9 @@ import { AnimationLoop } from './animation/AnimationLoop';
 import { ThemeTransition } from './animation/ThemeTransition';
 import { FPSDisplay } from './animation/FPSDisplay';
 
+/** Mouse modes in WASM `MouseMode` order */
+const MOUSE_MODES: MouseConfig['mode'][] = ['push', 'pull', 'swirl', 'magnetize', 'collide'];
+
 export class PolygonBackground {
   private container: HTMLElement;
   private canvas: HTMLCanvasElement;
@@ -226,7 +229,7 @@ export class PolygonBackground {
       radiusUnit === 'percent'
         ? Math.min(this.cachedWidth, this.cachedHeight) * (radius / 100)
         : radius;
-    const modeNum = mode === 'pull' ? 1 : mode === 'swirl' ? 2 : 0;
+    const modeNum = Math.max(0, MOUSE_MODES.indexOf(mode));
 
     this.wasmSimulation.tick(
       deltaTime,
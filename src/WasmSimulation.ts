@@ -105,7 +105,7 @@ export class WasmSimulation {
     inCanvas: boolean,
     radius: number,
     strength: number,
-    mode: number // 0=push, 1=pull, 2=swirl
+    mode: number // 0=push, 1=pull, 2=swirl, 3=magnetize, 4=collide
   ): void {
     if (this.simulation) {
       this.simulation.set_mouse_state(
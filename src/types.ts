@@ -49,8 +49,8 @@ export interface MouseConfig {
   radiusUnit: 'px' | 'percent';
   /** Displacement strength in pixels (0-150) - how much points are pushed/pulled */
   strength: number;
-  /** Interaction mode: push, pull, or swirl */
-  mode: 'push' | 'pull' | 'swirl';
+  /** Interaction mode: push, pull, swirl, magnetize (hex halo), or collide (solid disc) */
+  mode: 'push' | 'pull' | 'swirl' | 'magnetize' | 'collide';
   /** How fast points spring back (0=never, 1=instant). Default: 0.08 */
   springBack: number;
   /** How much mouse velocity influences push (0-1). Default: 0.5 */
@@ -0,0 +1,90 @@
+//! Anchor points: canvas positions measured by the host (e.g. corners of page cards)
+//! that hold the nearest points' rest positions, so the mesh seems to cling to real
+//! elements while still deforming around them when disturbed
+
+use crate::constants::MAX_ANCHOR_POINTS;
+use crate::point::Point;
+
+/// Anchor positions (canvas pixels) and the points currently held by them
+#[derive(Clone)]
+pub struct Anchors {
+    positions: Vec<(f32, f32)>,
+    /// (anchor, point index) pairs; rebuilt when `dirty`
+    held: Vec<(usize, usize)>,
+    /// The point set changed since the last assignment
+    dirty: bool,
+}
+
+impl Anchors {
+    pub fn new() -> Self {
+        Self {
+            positions: Vec::new(),
+            held: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    /// Replace the anchors from [x, y, ...] pairs; non-finite pairs are skipped and at
+    /// most MAX_ANCHOR_POINTS are kept
+    pub fn set(&mut self, positions: &[f32]) {
+        self.positions = positions
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .take(MAX_ANCHOR_POINTS)
+            .collect();
+        self.invalidate();
+    }
+
+    /// Re-pin to the nearest points on the next `hold` (after points were added,
+    /// removed or replaced)
+    pub fn invalidate(&mut self) {
+        self.held.clear();
+        self.dirty = true;
+    }
+
+    /// Follow a canvas resize, then re-pin
+    pub fn scale(&mut self, scale_x: f32, scale_y: f32) {
+        for (x, y) in &mut self.positions {
+            *x *= scale_x;
+            *y *= scale_y;
+        }
+        self.invalidate();
+    }
+
+    /// Hold each anchored point's rest position at its anchor; displacement is
+    /// untouched, so forces still deflect the point and the spring brings it back
+    pub fn hold(&mut self, points: &mut [Point]) {
+        if self.dirty {
+            self.assign(points);
+        }
+        for &(anchor, index) in &self.held {
+            if let Some(point) = points.get_mut(index).filter(|p| p.pin.is_none()) {
+                (point.base_x, point.base_y) = self.positions[anchor];
+            }
+        }
+    }
+
+    /// Give each anchor the nearest free point not taken by an earlier anchor
+    /// Hard-pinned points are skipped, their pin already wins
+    fn assign(&mut self, points: &mut [Point]) {
+        self.dirty = false;
+        for point in points.iter_mut() {
+            point.anchored = false;
+        }
+        let mut taken = vec![false; points.len()];
+        for (anchor, &(ax, ay)) in self.positions.iter().enumerate() {
+            let nearest = points
+                .iter()
+                .enumerate()
+                .filter(|(i, p)| !taken[*i] && p.pin.is_none())
+                .map(|(i, p)| (i, (p.base_x - ax).powi(2) + (p.base_y - ay).powi(2)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((index, _)) = nearest {
+                taken[index] = true;
+                points[index].anchored = true;
+                self.held.push((anchor, index));
+            }
+        }
+    }
+}
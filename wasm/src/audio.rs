@@ -0,0 +1,88 @@
+//! Audio reactivity: band levels sampled by the host (e.g. from a WebAudio analyser)
+//! mapped to an ambient pulse, drift speed and stroke jitter
+
+use crate::constants::{
+    AUDIO_LEVEL_SMOOTHING, DEFAULT_AUDIO_DRIFT_BOOST, DEFAULT_AUDIO_JITTER, DEFAULT_AUDIO_PULSE,
+};
+use crate::point::Point;
+
+/// Latest band levels and how strongly each drives the simulation
+#[derive(Clone)]
+pub struct AudioReactivity {
+    /// Smoothed levels in [0, 1]
+    bass: f32,
+    mid: f32,
+    treble: f32,
+    /// Outward push from the canvas center at full bass (CSS pixels per frame)
+    pub pulse: f32,
+    /// Extra drift speed at full mids (fraction of the normal speed)
+    pub drift_boost: f32,
+    /// Stroke jitter amplitude at full treble (CSS pixels)
+    pub jitter: f32,
+}
+
+impl AudioReactivity {
+    pub fn new() -> Self {
+        Self {
+            bass: 0.0,
+            mid: 0.0,
+            treble: 0.0,
+            pulse: DEFAULT_AUDIO_PULSE,
+            drift_boost: DEFAULT_AUDIO_DRIFT_BOOST,
+            jitter: DEFAULT_AUDIO_JITTER,
+        }
+    }
+
+    /// Feed new levels; rises are immediate, falls are smoothed to avoid flicker
+    pub fn set_levels(&mut self, bass: f32, mid: f32, treble: f32) {
+        let follow = |current: f32, level: f32| {
+            let level = if level.is_finite() {
+                level.clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            if level >= current {
+                level
+            } else {
+                current + (level - current) * (1.0 - AUDIO_LEVEL_SMOOTHING)
+            }
+        };
+        self.bass = follow(self.bass, bass);
+        self.mid = follow(self.mid, mid);
+        self.treble = follow(self.treble, treble);
+    }
+
+    /// Drift speed multiplier from the mids
+    pub fn speed_scale(&self) -> f32 {
+        1.0 + self.mid * self.drift_boost
+    }
+
+    /// Stroke jitter amplitude (CSS pixels) from the treble
+    pub fn stroke_jitter(&self) -> f32 {
+        self.treble * self.jitter
+    }
+
+    /// Push displacement outward from the canvas center in proportion to the bass
+    pub fn apply_pulse(
+        &self,
+        points: &mut [Point],
+        width: f32,
+        height: f32,
+        pixel_ratio: f32,
+        delta_time: f32,
+    ) {
+        let push = self.bass * self.pulse * pixel_ratio * delta_time;
+        if push <= 0.0 {
+            return;
+        }
+        let (cx, cy) = (width * 0.5, height * 0.5);
+        for point in points.iter_mut() {
+            let (dx, dy) = (point.base_x - cx, point.base_y - cy);
+            let dist = (dx * dx + dy * dy).sqrt();
+            if dist > 1e-3 {
+                point.dx += dx / dist * push;
+                point.dy += dy / dist * push;
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Headless renderer: simulates a preset file and writes one SVG per frame
+//!
+//! Usage: polygon-background render --config config.json --frames 600 --out ./frames/
+//!
+//! Config (all fields optional):
+//! {
+//!   "width": 1280, "height": 720, "pointCount": 200, "seed": 1,
+//!   "pixelRatio": 1, "speed": 1,
+//!   "preset": { ...output of exportPreset... },
+//!   "colors": { "low": "#1b1f3a", "high": "#6c7bd1" }
+//! }
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use polygon_background_wasm::Simulation;
+use serde_json::Value;
+
+const USAGE: &str =
+    "usage: polygon-background render --config <config.json> --frames <count> --out <dir>";
+
+/// Parsed command line
+struct Args {
+    config: PathBuf,
+    frames: u32,
+    out: PathBuf,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    if args.next().as_deref() != Some("render") {
+        return Err(USAGE.to_string());
+    }
+
+    let (mut config, mut frames, mut out) = (None, None, None);
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        match flag.as_str() {
+            "--config" => config = Some(PathBuf::from(value)),
+            "--frames" => {
+                let count = value.parse().map_err(|_| format!("invalid frame count {}", value))?;
+                frames = Some(count);
+            }
+            "--out" => out = Some(PathBuf::from(value)),
+            _ => return Err(format!("unknown option {}\n{}", flag, USAGE)),
+        }
+    }
+
+    Ok(Args {
+        config: config.ok_or(USAGE)?,
+        frames: frames.unwrap_or(1),
+        out: out.ok_or(USAGE)?,
+    })
+}
+
+/// Parse "#rrggbb" into components
+fn parse_color(hex: &str) -> Result<[f32; 3], String> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if digits.len() != 6 {
+        return Err(format!("invalid color {}", hex));
+    }
+    let mut rgb = [0.0; 3];
+    for (i, channel) in rgb.iter_mut().enumerate() {
+        let byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16)
+            .map_err(|_| format!("invalid color {}", hex))?;
+        *channel = byte as f32;
+    }
+    Ok(rgb)
+}
+
+/// Read an optional number field
+fn number(config: &Value, key: &str, default: f64) -> Result<f64, String> {
+    match config.get(key) {
+        None => Ok(default),
+        Some(v) => v.as_f64().ok_or_else(|| format!("{} must be a number", key)),
+    }
+}
+
+/// Read an optional color from the `colors` group
+fn color(config: &Value, key: &str, default: &str) -> Result<[f32; 3], String> {
+    match config.get("colors").and_then(|c| c.get(key)) {
+        None => parse_color(default),
+        Some(Value::String(hex)) => parse_color(hex),
+        Some(_) => Err(format!("colors.{} must be a string", key)),
+    }
+}
+
+/// Render the current triangle buffer as flat-shaded SVG polygons
+fn render_svg(sim: &Simulation, low: [f32; 3], high: [f32; 3]) -> String {
+    let (width, height) = sim.size();
+    let stride = sim.get_triangle_stride();
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" "#,
+            r#"width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#
+        ),
+        w = width,
+        h = height
+    );
+
+    for triangle in sim.triangle_vertex_data().chunks_exact(stride * 3) {
+        // Base layout: [x, y, height, centroidY, centroidX, centroidY]
+        let t = triangle[2].clamp(0.0, 1.0);
+        let [r, g, b] = [0, 1, 2].map(|i| (low[i] + (high[i] - low[i]) * t).round() as u8);
+        let fill = format!("#{:02x}{:02x}{:02x}", r, g, b);
+        let [x0, y0, x1, y1, x2, y2] = [0, 1, stride, stride + 1, stride * 2, stride * 2 + 1]
+            .map(|i| triangle[i]);
+        let _ = writeln!(
+            svg,
+            r#"<polygon points="{:.2},{:.2} {:.2},{:.2} {:.2},{:.2}" fill="{f}" stroke="{f}"/>"#,
+            x0,
+            y0,
+            x1,
+            y1,
+            x2,
+            y2,
+            f = fill
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let text = std::fs::read_to_string(&args.config)
+        .map_err(|e| format!("cannot read {}: {}", args.config.display(), e))?;
+    let config: Value =
+        serde_json::from_str(&text).map_err(|e| format!("invalid config JSON: {}", e))?;
+
+    let mut sim = Simulation::new(
+        number(&config, "width", 1280.0)? as f32,
+        number(&config, "height", 720.0)? as f32,
+        number(&config, "pointCount", 200.0)? as usize,
+        number(&config, "seed", 1.0)? as u32,
+    );
+    sim.set_device_pixel_ratio(number(&config, "pixelRatio", 1.0)? as f32);
+    if let Some(preset) = config.get("preset") {
+        sim.import_preset_json(preset)?;
+    }
+    let speed = number(&config, "speed", 1.0)? as f32;
+    let low = color(&config, "low", "#1b1f3a")?;
+    let high = color(&config, "high", "#6c7bd1")?;
+
+    std::fs::create_dir_all(&args.out)
+        .map_err(|e| format!("cannot create {}: {}", args.out.display(), e))?;
+
+    for frame in 0..args.frames {
+        sim.update_points(1.0, speed);
+        sim.triangulate();
+        let path: PathBuf = Path::new(&args.out).join(format!("frame_{:05}.svg", frame));
+        std::fs::write(&path, render_svg(&sim, low, high))
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = parse_args(std::env::args().skip(1)).and_then(run);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
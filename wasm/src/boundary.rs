@@ -0,0 +1,257 @@
+//! Boundary handling: edge wrapping and wind tunnel (open inflow/outflow edges)
+
+use crate::constants::{
+    DEFAULT_WIND_TUNNEL_FLOW_SPEED, DEFAULT_WIND_TUNNEL_TURBULENCE, GHOST_THRESHOLD,
+    WIND_TUNNEL_NOISE_SCALE, WIND_TUNNEL_NOISE_SPEED,
+};
+use crate::noise::simplex3d;
+use crate::point::Point;
+use crate::rng::Rng;
+
+/// How points behave when they reach the canvas edges
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum BoundaryMode {
+    /// Points wrap around to the opposite edge (torus)
+    #[default]
+    Wrap,
+    /// Points enter at an inflow edge and leave at the opposite outflow edge
+    WindTunnel,
+}
+
+impl BoundaryMode {
+    /// Convert from numeric value (for JS interop), wrapping if unknown
+    pub fn from_u32(value: u32) -> Self {
+        Self::try_from_u32(value).unwrap_or_default()
+    }
+
+    /// Convert from numeric value, None if unknown (for validated imports)
+    pub fn try_from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(BoundaryMode::Wrap),
+            1 => Some(BoundaryMode::WindTunnel),
+            _ => None,
+        }
+    }
+
+    /// Convert to numeric value (for JS interop)
+    pub fn to_u32(self) -> u32 {
+        match self {
+            BoundaryMode::Wrap => 0,
+            BoundaryMode::WindTunnel => 1,
+        }
+    }
+}
+
+/// Direction of the wind tunnel flow
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum FlowDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl FlowDirection {
+    /// Convert from numeric value (for JS interop), left to right if unknown
+    pub fn from_u32(value: u32) -> Self {
+        Self::try_from_u32(value).unwrap_or_default()
+    }
+
+    /// Convert from numeric value, None if unknown (for validated imports)
+    pub fn try_from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(FlowDirection::LeftToRight),
+            1 => Some(FlowDirection::RightToLeft),
+            2 => Some(FlowDirection::TopToBottom),
+            3 => Some(FlowDirection::BottomToTop),
+            _ => None,
+        }
+    }
+
+    /// Convert to numeric value (for JS interop)
+    pub fn to_u32(self) -> u32 {
+        match self {
+            FlowDirection::LeftToRight => 0,
+            FlowDirection::RightToLeft => 1,
+            FlowDirection::TopToBottom => 2,
+            FlowDirection::BottomToTop => 3,
+        }
+    }
+
+    /// Unit vector of the flow
+    fn vector(self) -> (f32, f32) {
+        match self {
+            FlowDirection::LeftToRight => (1.0, 0.0),
+            FlowDirection::RightToLeft => (-1.0, 0.0),
+            FlowDirection::TopToBottom => (0.0, 1.0),
+            FlowDirection::BottomToTop => (0.0, -1.0),
+        }
+    }
+
+    /// Whether the flow runs along the X axis
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, FlowDirection::LeftToRight | FlowDirection::RightToLeft)
+    }
+}
+
+/// Wind tunnel flow parameters
+#[derive(Clone, Copy)]
+pub struct WindTunnel {
+    pub direction: FlowDirection,
+    /// Flow speed in pixels per frame
+    pub flow_speed: f32,
+    /// Cross-flow noise displacement in pixels per frame
+    pub turbulence: f32,
+}
+
+impl WindTunnel {
+    pub fn new() -> Self {
+        Self {
+            direction: FlowDirection::LeftToRight,
+            flow_speed: DEFAULT_WIND_TUNNEL_FLOW_SPEED,
+            turbulence: DEFAULT_WIND_TUNNEL_TURBULENCE,
+        }
+    }
+}
+
+/// Resolved boundary settings used by point updates and triangulation
+#[derive(Clone, Copy)]
+pub struct Boundary {
+    pub mode: BoundaryMode,
+    pub tunnel: WindTunnel,
+}
+
+impl Boundary {
+    pub fn new() -> Self {
+        Self {
+            mode: BoundaryMode::Wrap,
+            tunnel: WindTunnel::new(),
+        }
+    }
+
+    /// Whether points wrap along the X axis
+    pub fn wraps_x(&self) -> bool {
+        self.mode == BoundaryMode::Wrap || !self.tunnel.direction.is_horizontal()
+    }
+
+    /// Whether points wrap along the Y axis
+    pub fn wraps_y(&self) -> bool {
+        self.mode == BoundaryMode::Wrap || self.tunnel.direction.is_horizontal()
+    }
+
+    /// Displayed position folded back into the canvas along the axes that wrap
+    /// Base positions wrap, but spring displacement can carry a point past the seam;
+    /// triangulating it there (outside the corner points) creates one-frame slivers
+    pub fn seam_position(&self, x: f32, y: f32, width: f32, height: f32) -> (f32, f32) {
+        let x = if self.wraps_x() { x.rem_euclid(width) } else { x };
+        let y = if self.wraps_y() { y.rem_euclid(height) } else { y };
+        (x, y)
+    }
+
+    /// Shortest offset between two positions, through the seam on axes that wrap
+    pub fn shortest_offset(&self, dx: f32, dy: f32, width: f32, height: f32) -> (f32, f32) {
+        let fold = |d: f32, size: f32| d - (d / size).round() * size;
+        let dx = if self.wraps_x() { fold(dx, width) } else { dx };
+        let dy = if self.wraps_y() { fold(dy, height) } else { dy };
+        (dx, dy)
+    }
+
+    /// Distance points travel past an open edge before they respawn
+    /// A fixed fraction of the canvas, so the corner points keep the mesh covering it
+    pub fn overhang(&self, width: f32, height: f32) -> (f32, f32) {
+        let x = if self.wraps_x() { 0.0 } else { width * GHOST_THRESHOLD };
+        let y = if self.wraps_y() { 0.0 } else { height * GHOST_THRESHOLD };
+        (x, y)
+    }
+
+    /// Wrap a point's base position along the axes that wrap
+    pub fn wrap(&self, point: &mut Point, width: f32, height: f32) {
+        if self.wraps_x() {
+            point.wrap_base_x(width);
+        }
+        if self.wraps_y() {
+            point.wrap_base_y(height);
+        }
+    }
+
+    /// Shift a point's rest, current and pinned positions along the axes that wrap
+    /// The rest position wraps and the current one moves by the same amount, so the
+    /// displacement (and the ghost points built from it) stays continuous
+    pub fn translate(&self, point: &mut Point, dx: f32, dy: f32, width: f32, height: f32) {
+        let dx = if self.wraps_x() { dx } else { 0.0 };
+        let dy = if self.wraps_y() { dy } else { 0.0 };
+        let (base_x, base_y) = (point.base_x, point.base_y);
+        (point.base_x, point.base_y) =
+            self.seam_position(point.base_x + dx, point.base_y + dy, width, height);
+        point.x += point.base_x - base_x;
+        point.y += point.base_y - base_y;
+        if let Some((x, y)) = point.pin {
+            point.pin = Some(self.seam_position(x + dx, y + dy, width, height));
+        }
+        if let Some(ref mut a) = point.stuck {
+            a.home_x += dx;
+            a.home_y += dy;
+        }
+    }
+
+    /// Advance a point's base position through the wind tunnel
+    /// Flow speed and turbulence are in CSS pixels, scaled by pixel_ratio
+    /// Returns true if the point left through the outflow edge and was respawned
+    #[allow(clippy::too_many_arguments)]
+    pub fn advance_tunnel_point(
+        &self,
+        point: &mut Point,
+        rng: &mut Rng,
+        delta_time: f32,
+        time: f32,
+        width: f32,
+        height: f32,
+        pixel_ratio: f32,
+    ) -> bool {
+        let tunnel = &self.tunnel;
+        let (fx, fy) = tunnel.direction.vector();
+
+        // Turbulence acts across the flow, sampled from slowly evolving noise
+        let noise_scale = WIND_TUNNEL_NOISE_SCALE / pixel_ratio;
+        let n = simplex3d(
+            point.base_x * noise_scale,
+            point.base_y * noise_scale,
+            time * WIND_TUNNEL_NOISE_SPEED,
+        );
+        let cross = n * tunnel.turbulence * pixel_ratio;
+        let flow = tunnel.flow_speed * pixel_ratio;
+
+        point.base_x += (fx * flow - fy * cross) * delta_time;
+        point.base_y += (fy * flow + fx * cross) * delta_time;
+        self.wrap(point, width, height);
+
+        let (over_x, over_y) = self.overhang(width, height);
+        let exited = match tunnel.direction {
+            FlowDirection::LeftToRight => point.base_x > width + over_x,
+            FlowDirection::RightToLeft => point.base_x < -over_x,
+            FlowDirection::TopToBottom => point.base_y > height + over_y,
+            FlowDirection::BottomToTop => point.base_y < -over_y,
+        };
+
+        if exited {
+            let (x, y) = match tunnel.direction {
+                FlowDirection::LeftToRight => (-over_x, rng.next_f32() * height),
+                FlowDirection::RightToLeft => (width + over_x, rng.next_f32() * height),
+                FlowDirection::TopToBottom => (rng.next_f32() * width, -over_y),
+                FlowDirection::BottomToTop => (rng.next_f32() * width, height + over_y),
+            };
+            point.respawn_at(x, y);
+        } else {
+            // Keep points drifting against a slow flow from escaping the inflow edge
+            match tunnel.direction {
+                FlowDirection::LeftToRight => point.base_x = point.base_x.max(-over_x),
+                FlowDirection::RightToLeft => point.base_x = point.base_x.min(width + over_x),
+                FlowDirection::TopToBottom => point.base_y = point.base_y.max(-over_y),
+                FlowDirection::BottomToTop => point.base_y = point.base_y.min(height + over_y),
+            }
+        }
+
+        exited
+    }
+}
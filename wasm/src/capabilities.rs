@@ -0,0 +1,98 @@
+//! Runtime version and capability introspection for host feature detection
+
+use js_sys::Object;
+use wasm_bindgen::prelude::*;
+
+use crate::constants::{
+    MAX_ANCHOR_POINTS, MAX_CATCHUP_FRAMES, MAX_CONSTELLATION_DISTANCE, MAX_DIMENSION,
+    MAX_EMITTER_RATE, MAX_EXCLUSION_RECTS, MAX_FLIP_REGIONS, MAX_HEIGHT_BLUR_ITERATIONS,
+    MAX_MIN_TRIANGLE_ANGLE, MAX_OBSTACLES, MAX_POINTERS, MAX_POINT_COUNT, MAX_QUALITY_TIER,
+    MAX_SHOCKWAVES, MIN_DIMENSION, MIN_POINT_COUNT,
+};
+use crate::interop::{set, string_array};
+use crate::preset::PRESET_VERSION;
+use crate::protocol::Command;
+
+/// Cargo features compiled into this build
+fn compiled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "console_error_panic_hook") {
+        features.push("console_error_panic_hook");
+    }
+    if cfg!(feature = "alloc-audit") {
+        features.push("alloc-audit");
+    }
+    if cfg!(feature = "threads") {
+        features.push("threads");
+    }
+    features
+}
+
+/// Report crate version, compiled features, limits and available modes
+/// Mode arrays are ordered by their numeric value in the setters
+#[wasm_bindgen]
+pub fn get_capabilities() -> JsValue {
+    let root = Object::new();
+    set(&root, "version", env!("CARGO_PKG_VERSION").into());
+    set(&root, "presetVersion", PRESET_VERSION.into());
+    set(&root, "features", string_array(&compiled_features()));
+
+    let limits = Object::new();
+    set(&limits, "minPoints", (MIN_POINT_COUNT as u32).into());
+    set(&limits, "maxPoints", (MAX_POINT_COUNT as u32).into());
+    set(&limits, "maxShockwaves", (MAX_SHOCKWAVES as u32).into());
+    set(&limits, "maxPointers", (MAX_POINTERS as u32).into());
+    set(&limits, "maxObstacles", (MAX_OBSTACLES as u32).into());
+    set(&limits, "maxAnchorPoints", (MAX_ANCHOR_POINTS as u32).into());
+    set(&limits, "maxExclusionRects", (MAX_EXCLUSION_RECTS as u32).into());
+    set(&limits, "maxEmitterRate", MAX_EMITTER_RATE.into());
+    set(&limits, "maxFlipRegions", (MAX_FLIP_REGIONS as u32).into());
+    set(&limits, "maxCatchupFrames", MAX_CATCHUP_FRAMES.into());
+    set(&limits, "maxConstellationDistance", MAX_CONSTELLATION_DISTANCE.into());
+    set(&limits, "maxHeightBlurIterations", MAX_HEIGHT_BLUR_ITERATIONS.into());
+    set(&limits, "maxMinTriangleAngle", MAX_MIN_TRIANGLE_ANGLE.into());
+    set(&limits, "maxQualityTier", MAX_QUALITY_TIER.into());
+    set(&limits, "minDimension", MIN_DIMENSION.into());
+    set(&limits, "maxDimension", MAX_DIMENSION.into());
+    set(&root, "limits", limits.into());
+
+    let modes = Object::new();
+    set(&modes, "mouse", string_array(&["push", "pull", "swirl", "magnetize", "collide"]));
+    set(&modes, "boundary", string_array(&["wrap", "windTunnel"]));
+    set(
+        &modes,
+        "flowDirection",
+        string_array(&["leftToRight", "rightToLeft", "topToBottom", "bottomToTop"]),
+    );
+    set(&modes, "depth", string_array(&["off", "random", "noise"]));
+    set(&modes, "distribution", string_array(&["random", "jitteredGrid", "hexLattice", "spiral"]));
+    set(&modes, "outputSpace", string_array(&["pixels", "normalized", "clip"]));
+    set(&modes, "triangleOutput", string_array(&["expanded", "indexed"]));
+    set(&modes, "cellOutput", string_array(&["delaunay", "voronoi", "constellation"]));
+    set(&modes, "render", string_array(&["pointsOnly", "lines", "mesh", "meshAndLines"]));
+    set(&modes, "workerCommand", string_array(&Command::NAMES));
+    set(&root, "modes", modes.into());
+
+    // Vertex attribute flags by bit order (flag value = 1 << index)
+    set(
+        &root,
+        "vertexAttributes",
+        string_array(&[
+            "depth",
+            "occlusion",
+            "flip",
+            "lightGradient",
+            "heat",
+            "strokeMotion",
+            "exclusion",
+            "shadow",
+            "audioJitter",
+            "weightedCentroid",
+            "facetSeed",
+            "faceNormal",
+            "diffuse",
+        ]),
+    );
+
+    root.into()
+}
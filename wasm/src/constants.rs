@@ -1,10 +1,194 @@
-/// Physics and simulation constants
+//! Physics and simulation constants
 
-/// Ghost point threshold as fraction of canvas dimensions
+/// Open-edge overhang as fraction of canvas dimensions
 pub const GHOST_THRESHOLD: f32 = 0.15;
 
-/// Maximum concurrent shockwaves
-pub const MAX_SHOCKWAVES: usize = 10;
+/// Adaptive ghost margin in mean point spacings (sqrt(area / count)) from a wrapping edge
+pub const GHOST_MARGIN_SPACINGS: f32 = 3.0;
+
+/// Incremental triangulation: largest re-check epsilon (CSS pixels) and the flips per
+/// triangle above which a full pass is cheaper than repairing
+pub const MAX_INCREMENTAL_EPSILON: f32 = 2.0;
+pub const INCREMENTAL_MAX_FLIP_FRACTION: f32 = 0.25;
+
+/// Largest topology hysteresis margin (degrees past co-circular before an edge flips)
+pub const MAX_TOPOLOGY_HYSTERESIS: f32 = 20.0;
+
+/// Concurrent shockwaves by default and at most (see `set_shockwave_limit`)
+pub const DEFAULT_SHOCKWAVE_CAPACITY: usize = 10;
+pub const MAX_SHOCKWAVES: usize = 64;
+
+/// Maximum extra pointers (touches) tracked besides the mouse
+pub const MAX_POINTERS: usize = 10;
+
+/// Maximum registered obstacles
+pub const MAX_OBSTACLES: usize = 16;
+
+/// Highest point layer for effect filter layer masks (one bit each in a u32)
+pub const MAX_POINT_LAYER: u32 = 31;
+
+/// Maximum anchor points (four per card for 64 cards)
+pub const MAX_ANCHOR_POINTS: usize = 256;
+
+/// Maximum registered exclusion rectangles
+pub const MAX_EXCLUSION_RECTS: usize = 16;
+
+/// Fraction of the distance to the nearest edge applied per frame to points inside a
+/// repelling exclusion rectangle
+pub const EXCLUSION_REPEL_RATE: f32 = 0.05;
+
+/// Maximum density map rebalancing rate (points relocated per second)
+pub const MAX_DENSITY_REBALANCE_RATE: f32 = 120.0;
+
+/// Spiral distribution: radius exponent at full falloff (0.5 = even density), the
+/// passes spent growing the spiral until enough sites land inside the canvas, and the
+/// most spiral sites generated per requested point
+pub const SPIRAL_MAX_EXPONENT: f32 = 1.5;
+pub const SPIRAL_FIT_PASSES: usize = 6;
+pub const SPIRAL_MAX_SITES_PER_POINT: usize = 16;
+
+/// Tile flip animation: concurrent regions, per-triangle duration, max stagger (seconds)
+pub const MAX_FLIP_REGIONS: usize = 8;
+pub const FLIP_DURATION_SECONDS: f32 = 0.8;
+pub const MAX_FLIP_STAGGER: f32 = 10.0;
+
+/// Default light angle in canvas coordinates (radians, Y down): light from the top
+pub const DEFAULT_LIGHT_ANGLE: f32 = -std::f32::consts::FRAC_PI_2;
+
+/// Default 3D light for the diffuse attribute: direction toward the light (canvas axes,
+/// z toward the viewer; from the top-front, normalized when used) and ambient floor
+pub const DEFAULT_LIGHT_VECTOR: [f32; 3] = [0.0, -0.5, 1.0];
+pub const DEFAULT_AMBIENT: f32 = 0.25;
+
+/// Frame-gap catch-up: default/max simulated frames per update, substep size,
+/// and per-update decay of the unsimulated backlog
+pub const DEFAULT_MAX_CATCHUP_FRAMES: f32 = 10.0;
+pub const MAX_CATCHUP_FRAMES: f32 = 120.0;
+pub const MAX_SUBSTEP_FRAMES: f32 = 2.0;
+pub const CATCHUP_BACKLOG_DECAY: f32 = 0.25;
+
+/// Fixed-timestep rates (Hz); the slowest keeps a step within MAX_SUBSTEP_FRAMES
+pub const MIN_FIXED_TIMESTEP_HZ: f32 = 30.0;
+pub const MAX_FIXED_TIMESTEP_HZ: f32 = 480.0;
+
+/// Most physics substeps per update requested with `set_substeps`
+pub const MAX_SUBSTEPS: u32 = 8;
+
+/// Most updates a single `warmup` call runs (ten seconds at 60 fps)
+pub const MAX_WARMUP_STEPS: u32 = 600;
+
+/// Simulation time-scale range for `set_time_scale` (1 = real time)
+pub const MIN_TIME_SCALE: f32 = 0.05;
+pub const MAX_TIME_SCALE: f32 = 4.0;
+
+/// Shadow attenuation per unit of height difference (relative to height intensity)
+/// toward the light: a neighbor a third of the intensity higher fully shadows
+pub const SHADOW_STRENGTH: f32 = 3.0;
+
+/// Face normals: relief (CSS pixels) of one unit of height intensity, so heights
+/// (unitless) compare with canvas distances
+pub const FACE_NORMAL_RELIEF: f32 = 40.0;
+
+/// Normal map: largest texture side, frames between rebuilds, and relief (normal tilt
+/// per unit of height intensity across one texel)
+pub const MAX_NORMAL_MAP_SIZE: usize = 256;
+pub const NORMAL_MAP_INTERVAL_FRAMES: u32 = 10;
+pub const NORMAL_MAP_RELIEF: f32 = 4.0;
+
+/// Audio reactivity: default pulse (CSS pixels per frame at full bass), drift boost
+/// (fraction at full mids), stroke jitter (CSS pixels at full treble), and the share
+/// of a falling level kept per update
+pub const DEFAULT_AUDIO_PULSE: f32 = 1.0;
+pub const DEFAULT_AUDIO_DRIFT_BOOST: f32 = 1.5;
+pub const DEFAULT_AUDIO_JITTER: f32 = 2.0;
+pub const AUDIO_LEVEL_SMOOTHING: f32 = 0.8;
+pub const MAX_AUDIO_RESPONSE: f32 = 20.0;
+
+/// Highest quality tier (full detail); tier 0 is the cheapest
+pub const MAX_QUALITY_TIER: u32 = 4;
+
+/// Adaptive quality: smoothing of reported frame times, the fractions of the target
+/// frame time above / below which the tier steps down / up, and how many consecutive
+/// frames must stay there first (upgrades wait longer so a recovered frame rate is real)
+pub const FRAME_TIME_SMOOTHING: f32 = 0.9;
+pub const QUALITY_DOWNGRADE_RATIO: f32 = 1.2;
+pub const QUALITY_UPGRADE_RATIO: f32 = 0.7;
+pub const QUALITY_DOWNGRADE_FRAMES: u32 = 30;
+pub const QUALITY_UPGRADE_FRAMES: u32 = 180;
+
+/// Environment curves: most keys per curve and largest key value
+pub const MAX_ENVIRONMENT_CURVE_KEYS: usize = 48;
+pub const MAX_ENVIRONMENT_CURVE_VALUE: f32 = 10.0;
+
+/// Device tilt: drift bias at full tilt (CSS pixels per frame), height falloff center
+/// shift at full tilt (fraction of the half canvas size), and the shift change that
+/// triggers a height regeneration
+pub const TILT_DRIFT_SPEED: f32 = 0.3;
+pub const TILT_LIGHT_SHIFT: f32 = 0.4;
+pub const TILT_LIGHT_SHIFT_EPSILON: f32 = 0.02;
+
+/// Scroll parallax: fraction of the remaining offset kept per frame while easing
+/// toward the target, and the distance (CSS pixels) at which it snaps
+pub const SCROLL_SMOOTHING: f32 = 0.85;
+pub const SCROLL_SNAP_DISTANCE: f32 = 0.01;
+
+/// Largest global force component (CSS pixels per frame²)
+pub const MAX_GLOBAL_FORCE: f32 = 10.0;
+
+/// Sticky cursor: points whose rest position is within this fraction of the mouse
+/// radius attach to the cursor
+pub const STICKY_CAPTURE_FRACTION: f32 = 0.5;
+
+/// Highest mesh spring stiffness (fraction of edge stretch corrected per frame)
+pub const MAX_MESH_SPRING_STIFFNESS: f32 = 0.5;
+
+/// Queued raw pointer events kept between ticks, and the gap (milliseconds) after
+/// which the previous batch no longer anchors velocity
+pub const MAX_QUEUED_POINTER_EVENTS: usize = 64;
+pub const POINTER_EVENT_STALE_MS: f64 = 100.0;
+
+/// Highest output smoothing (fraction of the previous position kept per frame)
+pub const MAX_OUTPUT_SMOOTHING: f32 = 0.95;
+
+/// Default and longest constellation edge in CSS pixels (the maximum bounds neighbor
+/// queries)
+pub const DEFAULT_CONSTELLATION_DISTANCE: f32 = 120.0;
+pub const MAX_CONSTELLATION_DISTANCE: f32 = 400.0;
+
+/// Most neighbor-averaging passes over point heights per triangulation
+pub const MAX_HEIGHT_BLUR_ITERATIONS: u32 = 8;
+
+/// Largest minimum angle (degrees) for the border sliver filter
+pub const MAX_MIN_TRIANGLE_ANGLE: f32 = 30.0;
+
+/// Bundled edges: segments per stroke edge and midpoint bow (fraction of edge length)
+pub const EDGE_BUNDLE_SEGMENTS: usize = 4;
+pub const EDGE_BUNDLE_CURVATURE: f32 = 0.15;
+
+/// Heat field: cell size (CSS pixels), default diffusion/retention per frame,
+/// deposits per frame (mouse scales with pointer speed), jitter (CSS pixels at full heat)
+pub const HEAT_CELL_SIZE: f32 = 40.0;
+pub const DEFAULT_HEAT_DIFFUSION: f32 = 0.2;
+pub const DEFAULT_HEAT_RETENTION: f32 = 0.97;
+pub const HEAT_MOUSE_DEPOSIT: f32 = 0.02;
+pub const HEAT_SHOCKWAVE_DEPOSIT: f32 = 0.05;
+pub const HEAT_GRAVITY_DEPOSIT: f32 = 0.01;
+pub const HEAT_JITTER: f32 = 1.5;
+
+/// Interaction metrics: cells per side of the normalized binning grid, longest window
+pub const INTERACTION_METRICS_GRID: usize = 16;
+pub const MAX_INTERACTION_WINDOW_MINUTES: f32 = 10.0;
+
+/// Finite-difference step (noise units) for flow field curl, and strength/time_speed caps
+pub const FLOW_FIELD_EPSILON: f32 = 0.01;
+pub const MAX_FLOW_FIELD_STRENGTH: f32 = 10.0;
+pub const MAX_FLOW_FIELD_TIME_SPEED: f32 = 0.1;
+
+/// Turbulence: amount (CSS pixels per frame) and speed caps, and the noise offset
+/// separating the y field from the x field
+pub const MAX_TURBULENCE_AMOUNT: f32 = 5.0;
+pub const MAX_TURBULENCE_SPEED: f32 = 0.1;
+pub const TURBULENCE_AXIS_OFFSET: f32 = 137.0;
 
 /// Physics defaults
 pub const DEFAULT_SPRING_BACK: f32 = 0.06;
@@ -16,12 +200,68 @@ pub const SHOCKWAVE_DECAY: f32 = 0.96;
 pub const SHOCKWAVE_WAVE_WIDTH: f32 = 60.0;
 pub const SHOCKWAVE_SPEED: f32 = 12.0;
 
+/// Implosion strength kept per frame: slower than shockwaves so the ring still pulls
+/// when it reaches the center
+pub const IMPLOSION_DECAY: f32 = 0.99;
+
+/// Maximum shockwave emissions per second for a single emitter
+pub const MAX_EMITTER_RATE: f32 = 60.0;
+
+/// Simulation frames per second that delta_time is normalized to
+pub const FRAMES_PER_SECOND: f32 = 60.0;
+
 /// Gravity well behavior
 pub const GRAVITY_WELL_MIN_DIST: f32 = 20.0;
 pub const GRAVITY_WELL_ATTRACT_STRENGTH: f32 = 3.0;
 pub const GRAVITY_WELL_REPEL_STRENGTH: f32 = -5.0;
 pub const GRAVITY_WELL_MAX_RANGE: f32 = 1000.0;
 
+/// Gravity well throw: velocity smoothing while dragged, release speed needed to throw
+/// and speed at which a thrown well expires (CSS pixels per frame), velocity kept per
+/// edge bounce, and the default friction (velocity kept per frame)
+pub const WELL_VELOCITY_SMOOTHING: f32 = 0.5;
+pub const WELL_THROW_MIN_SPEED: f32 = 2.0;
+pub const WELL_THROW_STOP_SPEED: f32 = 0.3;
+pub const WELL_BOUNCE_RESTITUTION: f32 = 0.7;
+pub const DEFAULT_WELL_THROW_FRICTION: f32 = 0.95;
+
+/// Density equalization behavior
+pub const DEFAULT_DENSITY_EQUALIZATION_STRENGTH: f32 = 0.5;
+
+/// Maximum pending scheduled effects (a scheduled gravity well takes two)
+pub const MAX_SCHEDULED_EFFECTS: usize = 256;
+
+/// Auto maintenance: length of each relaxation window (frames) and its density
+/// equalization strength, a tenth of the default so the correction stays invisible
+pub const MAINTENANCE_WINDOW_FRAMES: f32 = 300.0;
+pub const MAINTENANCE_STRENGTH: f32 = 0.05;
+
+/// Idle behavior: pointer speed (CSS pixels per frame) counting as input, ramp times
+/// (frames), swirl push (CSS pixels per frame at intensity 1) and wander speed
+/// (radians per frame), frames between soft shockwaves and their strength, max intensity
+pub const IDLE_INPUT_SPEED: f32 = 0.1;
+pub const IDLE_RAMP_UP_FRAMES: f32 = 120.0;
+pub const IDLE_RAMP_DOWN_FRAMES: f32 = 30.0;
+pub const IDLE_SWIRL_STRENGTH: f32 = 0.3;
+pub const IDLE_SWIRL_SPEED: f32 = 0.004;
+pub const IDLE_SHOCKWAVE_INTERVAL_FRAMES: f32 = 300.0;
+pub const IDLE_SHOCKWAVE_STRENGTH: f32 = 10.0;
+pub const MAX_IDLE_INTENSITY: f32 = 3.0;
+
+/// Settle-to-static: calm frames before sleeping and the pointer speed (CSS pixels per
+/// frame) that wakes the simulation
+pub const SETTLE_CALM_FRAMES: f32 = 30.0;
+pub const SETTLE_WAKE_SPEED: f32 = 0.1;
+/// Fraction of a cell width a point moves per frame at strength 1 and full overcrowding
+pub const DENSITY_EQUALIZATION_RATE: f32 = 0.004;
+
+/// Wind tunnel behavior
+pub const DEFAULT_WIND_TUNNEL_FLOW_SPEED: f32 = 1.0;
+pub const DEFAULT_WIND_TUNNEL_TURBULENCE: f32 = 0.6;
+pub const WIND_TUNNEL_NOISE_SCALE: f32 = 0.004;
+/// Noise time advance per frame
+pub const WIND_TUNNEL_NOISE_SPEED: f32 = 0.004;
+
 /// Minimum squared distance to avoid division issues
 pub const MIN_DIST_SQ: f32 = 1.0;
 
@@ -31,15 +271,64 @@ pub const DEFAULT_MOUSE_RADIUS: f32 = 150.0;
 /// Default mouse strength
 pub const DEFAULT_MOUSE_STRENGTH: f32 = 80.0;
 
+/// Magnetize mode: hex lattice spacing (CSS pixels) and pull toward the nearest node
+/// per frame at full falloff and default strength
+pub const MAGNETIZE_SPACING: f32 = 28.0;
+pub const MAGNETIZE_PULL: f32 = 0.35;
+
+/// Collide mode: fraction of the cursor's approach speed passed on to points it hits
+/// at default strength
+pub const COLLIDE_RESTITUTION: f32 = 0.5;
+
+/// Default mouse velocity dynamics
+pub const DEFAULT_MOUSE_VELOCITY_SMOOTHING: f32 = 0.6;
+pub const DEFAULT_MOUSE_OFF_CANVAS_DECAY: f32 = 0.9;
+
+/// One-euro filter derivative cutoff in Hz
+pub const INPUT_FILTER_DERIVATIVE_CUTOFF: f32 = 1.0;
+
 /// Base velocity for new points
 pub const BASE_VELOCITY: f32 = 0.5;
 
+/// Depth of points without parallax (scale factor 1)
+pub const NEUTRAL_DEPTH: f32 = 0.5;
+
+/// RNG stream ids, mixed with the master seed (see RngStreams)
+/// Never renumber: that changes the output for existing seeds
+pub const RNG_STREAM_PLACEMENT: u32 = 1;
+pub const RNG_STREAM_DRIFT: u32 = 2;
+pub const RNG_STREAM_DEPTH: u32 = 3;
+pub const RNG_STREAM_HEAT: u32 = 4;
+pub const RNG_STREAM_DENSITY: u32 = 5;
+pub const RNG_STREAM_RAIN: u32 = 6;
+pub const RNG_STREAM_IDLE: u32 = 7;
+
+/// Noise scale for noise-based depth regions
+pub const DEPTH_NOISE_SCALE: f32 = 0.002;
+
 /// Default noise scale
 pub const DEFAULT_NOISE_SCALE: f32 = 0.003;
 
 /// Default height intensity
 pub const DEFAULT_HEIGHT_INTENSITY: f32 = 0.6;
 
+/// Items (points or triangles) a pass needs before the "threads" feature splits it, and
+/// items per parallel chunk
+#[cfg(feature = "threads")]
+pub const PARALLEL_MIN_ITEMS: usize = 2048;
+#[cfg(feature = "threads")]
+pub const PARALLEL_CHUNK_ITEMS: usize = 512;
+
+/// Maximum recorded input events (recording stops growing beyond this)
+pub const MAX_RECORDED_EVENTS: usize = 200_000;
+
+/// Maximum recording snapshots (full state copies); beyond it every other snapshot is
+/// dropped and the snapshot interval doubles
+pub const MAX_RECORDING_SNAPSHOTS: usize = 64;
+
+/// Point count when a config object does not specify one
+pub const DEFAULT_POINT_COUNT: u32 = 80;
+
 /// Validation limits
 pub const MIN_POINT_COUNT: usize = 3;
 pub const MAX_POINT_COUNT: usize = 10000;
@@ -0,0 +1,49 @@
+//! Constellation mode: distance-based edges between nearby points (no triangulation)
+//! Neighbors come from the spatial grid, so the cost stays O(n·k)
+
+use crate::point::Point;
+use crate::spatial_grid::SpatialGrid;
+use crate::triangulation::OutputTransform;
+
+/// Floats per constellation edge: [x1, y1, x2, y2, alpha]
+pub const CONSTELLATION_EDGE_STRIDE: usize = 5;
+
+/// Build edges between points closer than `max_distance` (canvas pixels)
+/// Alpha fades linearly from 1 (coincident) to 0 at `max_distance`; each pair is emitted once
+pub fn build_constellation(
+    points: &[Point],
+    grid: &SpatialGrid,
+    max_distance: f32,
+    transform: &OutputTransform,
+    buffer: &mut Vec<f32>,
+) {
+    buffer.clear();
+    if max_distance <= 0.0 {
+        return;
+    }
+    let max_dist_sq = max_distance * max_distance;
+
+    for (i, a) in points.iter().enumerate() {
+        for j in grid.query_radius(a.x, a.y, max_distance) {
+            if j <= i {
+                continue;
+            }
+            let b = &points[j];
+            let dx = b.x - a.x;
+            let dy = b.y - a.y;
+            let dist_sq = dx * dx + dy * dy;
+            if dist_sq >= max_dist_sq {
+                continue;
+            }
+
+            let alpha = 1.0 - dist_sq.sqrt() / max_distance;
+            buffer.extend_from_slice(&[
+                transform.x(a.x),
+                transform.y(a.y),
+                transform.x(b.x),
+                transform.y(b.y),
+                alpha,
+            ]);
+        }
+    }
+}
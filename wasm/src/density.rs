@@ -0,0 +1,72 @@
+//! Density map: a grayscale grid of relative point density used for placement
+//! Cells are picked in proportion to their weight, positions are uniform within a cell
+
+use crate::constants::MAX_DENSITY_REBALANCE_RATE;
+use crate::rng::Rng;
+
+/// Relative density on a uniform grid stretched over the canvas
+#[derive(Clone)]
+pub struct DensityMap {
+    cols: usize,
+    rows: usize,
+    /// Cumulative cell weights (last entry = total)
+    cdf: Vec<f32>,
+    /// Points relocated per second to converge on the map (0 = spawning only)
+    rebalance_rate: f32,
+    rebalance_accumulator: f32,
+}
+
+impl DensityMap {
+    /// Build from row-major bytes (0 = empty, 255 = densest)
+    /// None if the size does not match or every cell is empty
+    pub fn new(data: &[u8], cols: usize, rows: usize) -> Option<Self> {
+        if cols == 0 || rows == 0 || data.len() != cols * rows {
+            return None;
+        }
+        let mut total = 0.0;
+        let cdf: Vec<f32> = data
+            .iter()
+            .map(|&v| {
+                total += v as f32;
+                total
+            })
+            .collect();
+        if total <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            cols,
+            rows,
+            cdf,
+            rebalance_rate: 0.0,
+            rebalance_accumulator: 0.0,
+        })
+    }
+
+    /// Random position distributed according to the map (canvas pixels)
+    pub fn sample(&self, rng: &mut Rng, width: f32, height: f32) -> (f32, f32) {
+        let total = self.cdf[self.cdf.len() - 1];
+        let target = rng.next_f32() * total;
+        let cell = self.cdf.partition_point(|&c| c <= target).min(self.cdf.len() - 1);
+        let (col, row) = (cell % self.cols, cell / self.cols);
+        let x = (col as f32 + rng.next_f32()) / self.cols as f32 * width;
+        let y = (row as f32 + rng.next_f32()) / self.rows as f32 * height;
+        (x, y)
+    }
+
+    pub fn rebalance_rate(&self) -> f32 {
+        self.rebalance_rate
+    }
+
+    pub fn set_rebalance_rate(&mut self, rate: f32) {
+        self.rebalance_rate = rate.clamp(0.0, MAX_DENSITY_REBALANCE_RATE);
+    }
+
+    /// Advance the rebalance clock and return how many points to relocate
+    pub fn rebalance_due(&mut self, dt_seconds: f32) -> u32 {
+        self.rebalance_accumulator += self.rebalance_rate * dt_seconds;
+        let due = self.rebalance_accumulator.floor();
+        self.rebalance_accumulator -= due;
+        due as u32
+    }
+}
@@ -0,0 +1,68 @@
+//! Per-point depth for parallax inside a single mesh
+//! Depth scales drift speed, mouse response and point size
+
+use crate::constants::{DEPTH_NOISE_SCALE, NEUTRAL_DEPTH};
+use crate::noise::simplex3d;
+use crate::point::Point;
+use crate::rng::Rng;
+
+/// How point depth values are generated
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum DepthMode {
+    /// All points share the neutral depth (no parallax)
+    #[default]
+    Off,
+    /// Independent random depth per point
+    Random,
+    /// Depth sampled from noise at the point's position (coherent depth regions)
+    Noise,
+}
+
+impl DepthMode {
+    /// Convert from numeric value (for JS interop), off if unknown
+    pub fn from_u32(value: u32) -> Self {
+        Self::try_from_u32(value).unwrap_or_default()
+    }
+
+    /// Convert from numeric value, None if unknown (for validated imports)
+    pub fn try_from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(DepthMode::Off),
+            1 => Some(DepthMode::Random),
+            2 => Some(DepthMode::Noise),
+            _ => None,
+        }
+    }
+
+    /// Convert to numeric value (for JS interop)
+    pub fn to_u32(self) -> u32 {
+        match self {
+            DepthMode::Off => 0,
+            DepthMode::Random => 1,
+            DepthMode::Noise => 2,
+        }
+    }
+
+    /// Generate a depth in [0, 1] for a point
+    pub fn sample(self, point: &Point, rng: &mut Rng) -> f32 {
+        match self {
+            DepthMode::Off => NEUTRAL_DEPTH,
+            DepthMode::Random => rng.next_f32(),
+            DepthMode::Noise => {
+                let n = simplex3d(
+                    point.base_x * DEPTH_NOISE_SCALE,
+                    point.base_y * DEPTH_NOISE_SCALE,
+                    // Offset from the height noise slice so depth and height are uncorrelated
+                    31.7,
+                );
+                ((n + 1.0) / 2.0).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// Scale factor for a depth value: (1 - parallax) for far points, (1 + parallax) for near ones
+#[inline]
+pub fn depth_factor(depth: f32, parallax: f32) -> f32 {
+    1.0 + parallax * (depth * 2.0 - 1.0)
+}
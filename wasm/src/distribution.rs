@@ -0,0 +1,163 @@
+//! Point distributions: independent random positions, structured lattices (jittered
+//! grid, hex) that give evenly sized triangles, or a golden-angle spiral whose density
+//! falls off from a focal point
+
+use crate::constants::{SPIRAL_FIT_PASSES, SPIRAL_MAX_EXPONENT, SPIRAL_MAX_SITES_PER_POINT};
+use crate::rng::Rng;
+
+/// Config mode number of the spiral (it needs a center, so `from_u32` does not build it)
+pub const SPIRAL_MODE: u32 = 3;
+
+/// How point sets are laid out
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum Distribution {
+    /// Independent uniform positions (or density map samples), added one at a time
+    #[default]
+    Random,
+    /// Centers of a near-square grid over the canvas, each moved up to `jitter` (0-1)
+    /// of a cell
+    JitteredGrid { jitter: f32 },
+    /// Hexagonal lattice (odd rows shifted half a step), each site moved up to
+    /// `jitter` (0-1) of the spacing
+    HexLattice { jitter: f32 },
+    /// Sunflower (Vogel) spiral around (center_x, center_y) in canvas pixels; `falloff`
+    /// 0 = even density, 1 = density falling off with the distance from the center
+    Spiral { center_x: f32, center_y: f32, falloff: f32 },
+}
+
+impl Distribution {
+    /// Convert from numeric values (for JS interop): 0 = random, 1 = jittered grid,
+    /// 2 = hex lattice; jitter is clamped to 0..1
+    pub fn from_u32(value: u32, jitter: f32) -> Self {
+        let jitter = if jitter.is_finite() { jitter.clamp(0.0, 1.0) } else { 0.0 };
+        match value {
+            1 => Distribution::JitteredGrid { jitter },
+            2 => Distribution::HexLattice { jitter },
+            _ => Distribution::Random,
+        }
+    }
+
+    /// Golden-angle spiral centered at (center_x, center_y) canvas pixels, clamped onto
+    /// the width x height canvas; falloff is clamped to 0..1
+    pub fn spiral(center_x: f32, center_y: f32, falloff: f32, width: f32, height: f32) -> Self {
+        let finite = |v: f32| if v.is_finite() { v } else { 0.0 };
+        Distribution::Spiral {
+            center_x: finite(center_x).clamp(0.0, width),
+            center_y: finite(center_y).clamp(0.0, height),
+            falloff: finite(falloff).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Follow a canvas resize (the spiral center keeps its relative position)
+    pub fn scale(&mut self, scale_x: f32, scale_y: f32) {
+        if let Distribution::Spiral { center_x, center_y, .. } = self {
+            *center_x *= scale_x;
+            *center_y *= scale_y;
+        }
+    }
+
+    /// Whether a point set must be laid out as a whole
+    pub fn is_structured(self) -> bool {
+        self != Distribution::Random
+    }
+
+    /// Replace `out` with `count` lattice positions over width x height; surplus sites
+    /// of the last row are dropped at random
+    /// Random leaves `out` empty (its points are placed one at a time)
+    pub fn place(
+        self,
+        count: usize,
+        width: f32,
+        height: f32,
+        rng: &mut Rng,
+        out: &mut Vec<(f32, f32)>,
+    ) {
+        out.clear();
+        let (jitter, cell_aspect, row_shift) = match self {
+            Distribution::Random => return,
+            Distribution::Spiral { center_x, center_y, falloff } => {
+                spiral(count, (center_x, center_y), falloff, width, height, out);
+                drop_surplus(count, rng, out);
+                return;
+            }
+            Distribution::JitteredGrid { jitter } => (jitter, 1.0, 0.0),
+            // Equilateral rows are sqrt(3)/2 of the spacing apart
+            Distribution::HexLattice { jitter } => (jitter, 3f32.sqrt() / 2.0, 0.5),
+        };
+        if count == 0 {
+            return;
+        }
+
+        // Columns so that cells of the target area have the wanted aspect, then as
+        // many rows as needed; the lattice is stretched to fill the canvas exactly
+        let spacing = (width * height / count as f32 / cell_aspect).sqrt();
+        let cols = ((width / spacing).round() as usize).clamp(1, count);
+        let rows = count.div_ceil(cols);
+        let (step_x, step_y) = (width / cols as f32, height / rows as f32);
+        for row in 0..rows {
+            let shift = if row % 2 == 1 { row_shift } else { 0.0 };
+            let center = 0.5 + shift - row_shift / 2.0;
+            for col in 0..cols {
+                let x = (col as f32 + center + (rng.next_f32() - 0.5) * jitter) * step_x;
+                let y = (row as f32 + 0.5 + (rng.next_f32() - 0.5) * jitter) * step_y;
+                out.push((x, y));
+            }
+        }
+
+        // Drop random surplus sites so the gap is not always at the end of the last row
+        drop_surplus(count, rng, out);
+    }
+}
+
+/// Spiral sites inside the canvas, at least `count` unless the fit gives up after
+/// SPIRAL_FIT_PASSES or SPIRAL_MAX_SITES_PER_POINT sites per point (the caller then
+/// fills the rest at random)
+/// Site k of n sits at radius R * ((k + 0.5) / n)^e, turning by the golden angle each
+/// step; R reaches the farthest corner, e = 0.5 is even density and larger exponents
+/// crowd the center. Sites outside the canvas are skipped, so n grows until enough land
+/// inside
+fn spiral(
+    count: usize,
+    (center_x, center_y): (f32, f32),
+    falloff: f32,
+    width: f32,
+    height: f32,
+    out: &mut Vec<(f32, f32)>,
+) {
+    let golden_angle = std::f32::consts::PI * (3.0 - 5f32.sqrt());
+    let exponent = 0.5 + falloff * (SPIRAL_MAX_EXPONENT - 0.5);
+    let reach_x = center_x.max(width - center_x);
+    let reach_y = center_y.max(height - center_y);
+    let radius = reach_x.hypot(reach_y);
+
+    let max_total = count.saturating_mul(SPIRAL_MAX_SITES_PER_POINT);
+    let mut total = count;
+    for _ in 0..SPIRAL_FIT_PASSES {
+        out.clear();
+        for k in 0..total {
+            let r = radius * ((k as f32 + 0.5) / total as f32).powf(exponent);
+            let (sin, cos) = (k as f32 * golden_angle).sin_cos();
+            let (x, y) = (center_x + r * cos, center_y + r * sin);
+            if (0.0..=width).contains(&x) && (0.0..=height).contains(&y) {
+                out.push((x, y));
+            }
+        }
+        if out.len() >= count {
+            return;
+        }
+        if total >= max_total {
+            return;
+        }
+        // Scale by the inside fraction, rounded up so every pass grows
+        let grown = total.saturating_mul(count) / out.len().max(1);
+        total = grown.saturating_add(1).min(max_total);
+    }
+}
+
+/// Remove random sites until `count` remain
+fn drop_surplus(count: usize, rng: &mut Rng, out: &mut Vec<(f32, f32)>) {
+    while out.len() > count {
+        let i = rng.next() as usize % out.len();
+        out.swap_remove(i);
+    }
+}
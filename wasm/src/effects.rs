@@ -1,66 +1,181 @@
-/// Visual effects: Shockwaves, Gravity Wells, Mouse Modes
+//! Visual effects: Shockwaves, Emitters, Gravity Wells, Mouse Modes
 
 use crate::constants::{
-    GRAVITY_WELL_ATTRACT_STRENGTH, GRAVITY_WELL_REPEL_STRENGTH,
-    MAX_SHOCKWAVES, SHOCKWAVE_DECAY, SHOCKWAVE_SPEED,
+    DEFAULT_MOUSE_OFF_CANVAS_DECAY, DEFAULT_MOUSE_RADIUS, DEFAULT_MOUSE_STRENGTH,
+    DEFAULT_MOUSE_VELOCITY_SMOOTHING, DEFAULT_SHOCKWAVE_CAPACITY, GRAVITY_WELL_ATTRACT_STRENGTH,
+    GRAVITY_WELL_MAX_RANGE, GRAVITY_WELL_REPEL_STRENGTH, IMPLOSION_DECAY, MAX_EMITTER_RATE,
+    MAX_POINTERS, MAX_SHOCKWAVES, SHOCKWAVE_DECAY, SHOCKWAVE_SPEED, SHOCKWAVE_WAVE_WIDTH,
+    WELL_BOUNCE_RESTITUTION, WELL_THROW_MIN_SPEED, WELL_THROW_STOP_SPEED, WELL_VELOCITY_SMOOTHING,
 };
+use crate::rng::Rng;
 
-/// Expanding shockwave effect triggered by clicks
+/// Expanding shockwave effect triggered by clicks, or a contracting implosion
 #[derive(Clone, Copy)]
 pub struct Shockwave {
+    /// Handle for `cancel_effect` (0 = unnamed, e.g. emitter waves)
+    pub id: u32,
     pub x: f32,
     pub y: f32,
     pub radius: f32,
     pub strength: f32,
+    /// Strength at trigger time (for normalizing the decay)
+    pub initial_strength: f32,
+    /// Radius change per frame in CSS pixels (negative = implosion, pulling inward)
     pub speed: f32,
+    /// Fraction of the strength kept per frame
+    pub decay: f32,
 }
 
 impl Shockwave {
     /// Create a new shockwave at position with given strength
     pub fn new(x: f32, y: f32, strength: f32) -> Self {
+        let strength = strength.clamp(0.0, 500.0);
         Self {
+            id: 0,
             x,
             y,
             radius: 0.0,
-            strength: strength.clamp(0.0, 500.0),
+            strength,
+            initial_strength: strength,
             speed: SHOCKWAVE_SPEED,
+            decay: SHOCKWAVE_DECAY,
         }
     }
 
-    /// Update shockwave (expand and decay)
-    pub fn update(&mut self) {
-        self.radius += self.speed;
-        self.strength *= SHOCKWAVE_DECAY;
+    /// Create a ring contracting from `start_radius` (canvas pixels) toward (x, y)
+    pub fn implosion(x: f32, y: f32, strength: f32, start_radius: f32) -> Self {
+        Self {
+            radius: start_radius,
+            speed: -SHOCKWAVE_SPEED,
+            decay: IMPLOSION_DECAY,
+            ..Self::new(x, y, strength)
+        }
+    }
+
+    pub fn is_implosion(&self) -> bool {
+        self.speed < 0.0
     }
 
-    /// Check if shockwave is still active
+    /// Current strength as a fraction of the trigger strength
+    pub fn normalized_strength(&self) -> f32 {
+        if self.initial_strength > 0.0 {
+            self.strength / self.initial_strength
+        } else {
+            0.0
+        }
+    }
+
+    /// Update shockwave (expand or contract, and decay) by `share` of a frame's change
+    pub fn update(&mut self, pixel_ratio: f32, share: f32) {
+        self.radius += self.speed * pixel_ratio * share;
+        self.strength *= if share == 1.0 { self.decay } else { self.decay.powf(share) };
+    }
+
+    /// Check if shockwave is still active (an implosion ends at its center)
     pub fn is_active(&self) -> bool {
-        self.strength > 0.5
+        self.strength > 0.5 && self.radius > 0.0
+    }
+}
+
+/// What happens to a shockwave added at capacity
+#[derive(Clone, Copy, PartialEq)]
+pub enum OverflowPolicy {
+    /// Remove the oldest wave
+    DropOldest,
+    /// Remove the weakest wave (or skip the new one if it is weaker still)
+    DropWeakest,
+    /// Skip the new wave
+    Reject,
+}
+
+impl OverflowPolicy {
+    /// Convert from numeric value (for JS interop)
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => OverflowPolicy::DropWeakest,
+            2 => OverflowPolicy::Reject,
+            _ => OverflowPolicy::DropOldest,
+        }
     }
 }
 
 /// Collection of active shockwaves
+#[derive(Clone)]
 pub struct ShockwaveManager {
     waves: Vec<Shockwave>,
+    capacity: usize,
+    pub overflow: OverflowPolicy,
+    /// Waves removed or skipped at capacity since the last `take_dropped`
+    dropped: u32,
 }
 
 impl ShockwaveManager {
     pub fn new() -> Self {
-        Self { waves: Vec::new() }
+        Self {
+            waves: Vec::new(),
+            capacity: DEFAULT_SHOCKWAVE_CAPACITY,
+            overflow: OverflowPolicy::DropOldest,
+            dropped: 0,
+        }
     }
 
-    /// Add a new shockwave, removing oldest if at capacity
-    pub fn add(&mut self, x: f32, y: f32, strength: f32) {
-        if self.waves.len() >= MAX_SHOCKWAVES {
-            self.waves.remove(0);
+    /// Change the capacity (1..=MAX_SHOCKWAVES), dropping the oldest waves beyond it
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.clamp(1, MAX_SHOCKWAVES);
+        if self.waves.len() > self.capacity {
+            let excess = self.waves.len() - self.capacity;
+            self.waves.drain(..excess);
+            self.dropped += excess as u32;
         }
-        self.waves.push(Shockwave::new(x, y, strength));
+    }
+
+    /// Add a new shockwave, applying the overflow policy at capacity
+    /// Returns false if the new wave was skipped
+    pub fn add(&mut self, wave: Shockwave) -> bool {
+        if self.waves.len() >= self.capacity {
+            self.dropped += 1;
+            let victim = match self.overflow {
+                OverflowPolicy::DropOldest => 0,
+                OverflowPolicy::DropWeakest => {
+                    let (index, weakest) = self
+                        .waves
+                        .iter()
+                        .enumerate()
+                        .min_by(|a, b| a.1.strength.total_cmp(&b.1.strength))
+                        .expect("capacity is at least one");
+                    if weakest.strength >= wave.strength {
+                        return false;
+                    }
+                    index
+                }
+                OverflowPolicy::Reject => return false,
+            };
+            self.waves.remove(victim);
+        }
+        self.waves.push(wave);
+        true
+    }
+
+    /// Remove a named wave; returns whether it was active
+    pub fn remove(&mut self, id: u32) -> bool {
+        let count = self.waves.len();
+        self.waves.retain(|w| w.id != id);
+        self.waves.len() != count
+    }
+
+    pub fn clear(&mut self) {
+        self.waves.clear();
+    }
+
+    /// Number of waves dropped at capacity since the last call
+    pub fn take_dropped(&mut self) -> u32 {
+        std::mem::take(&mut self.dropped)
     }
 
     /// Update all shockwaves and remove dead ones
-    pub fn update(&mut self) {
+    pub fn update(&mut self, pixel_ratio: f32, share: f32) {
         for wave in &mut self.waves {
-            wave.update();
+            wave.update(pixel_ratio, share);
         }
         self.waves.retain(|w| w.is_active());
     }
@@ -70,6 +185,10 @@ impl ShockwaveManager {
         self.waves.iter()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.waves.is_empty()
+    }
+
     /// Get max radius among all active shockwaves (for spatial grid sizing)
     pub fn max_radius(&self, wave_width: f32) -> f32 {
         self.waves
@@ -79,37 +198,173 @@ impl ShockwaveManager {
     }
 }
 
+/// Floats per effects buffer entry: [kind, id, x, y, radius, ring width, strength]
+pub const EFFECT_STRIDE: usize = 7;
+
+/// Effect kinds in the effects buffer
+pub mod effect_kind {
+    pub const SHOCKWAVE: u32 = 0;
+    pub const ATTRACT_WELL: u32 = 1;
+    pub const REPEL_WELL: u32 = 2;
+    pub const IMPLOSION: u32 = 3;
+}
+
+/// Describe active shockwaves and the gravity well for overlay rendering (canvas
+/// pixels); wave strength is normalized to its trigger strength, wells report 1 and
+/// their range as radius
+pub fn build_effects_buffer(
+    shockwaves: &ShockwaveManager,
+    well: Option<&GravityWell>,
+    pixel_ratio: f32,
+    buffer: &mut Vec<f32>,
+) {
+    buffer.clear();
+    let wave_width = SHOCKWAVE_WAVE_WIDTH * pixel_ratio;
+    for wave in shockwaves.iter() {
+        let kind = if wave.is_implosion() {
+            effect_kind::IMPLOSION
+        } else {
+            effect_kind::SHOCKWAVE
+        };
+        buffer.extend_from_slice(&[
+            kind as f32,
+            wave.id as f32,
+            wave.x,
+            wave.y,
+            wave.radius,
+            wave_width,
+            wave.normalized_strength(),
+        ]);
+    }
+    if let Some(well) = well {
+        let kind = if well.strength >= 0.0 {
+            effect_kind::ATTRACT_WELL
+        } else {
+            effect_kind::REPEL_WELL
+        };
+        buffer.extend_from_slice(&[
+            kind as f32,
+            well.id as f32,
+            well.x,
+            well.y,
+            GRAVITY_WELL_MAX_RANGE * pixel_ratio,
+            0.0,
+            1.0,
+        ]);
+    }
+}
+
+/// How a released gravity well coasts
+#[derive(Clone, Copy)]
+pub struct WellThrow {
+    /// Fraction of the velocity kept per frame
+    pub friction: f32,
+    /// Bounce off the canvas edges (otherwise the well expires when it leaves)
+    pub bounce: bool,
+}
+
 /// Gravity well effect (attract or repel points)
 #[derive(Clone, Copy)]
 pub struct GravityWell {
+    /// Handle for `cancel_effect`
+    pub id: u32,
     pub x: f32,
     pub y: f32,
     pub strength: f32,
+    /// Velocity in pixels per frame, measured while held and integrated while thrown
+    pub vx: f32,
+    pub vy: f32,
+    /// Position at the last update (for measuring velocity)
+    last_x: f32,
+    last_y: f32,
+    /// Coasting after release (None = held by the pointer)
+    thrown: Option<WellThrow>,
 }
 
 impl GravityWell {
-    /// Create an attracting gravity well
-    pub fn attract(x: f32, y: f32) -> Self {
+    fn new(x: f32, y: f32, strength: f32) -> Self {
         Self {
+            id: 0,
             x,
             y,
-            strength: GRAVITY_WELL_ATTRACT_STRENGTH,
+            strength,
+            vx: 0.0,
+            vy: 0.0,
+            last_x: x,
+            last_y: y,
+            thrown: None,
         }
     }
 
+    /// Create an attracting gravity well
+    pub fn attract(x: f32, y: f32) -> Self {
+        Self::new(x, y, GRAVITY_WELL_ATTRACT_STRENGTH)
+    }
+
     /// Create a repelling gravity well
     pub fn repel(x: f32, y: f32) -> Self {
-        Self {
-            x,
-            y,
-            strength: GRAVITY_WELL_REPEL_STRENGTH,
-        }
+        Self::new(x, y, GRAVITY_WELL_REPEL_STRENGTH)
     }
 
-    /// Update position
+    /// Update position (ignored once thrown)
     pub fn set_position(&mut self, x: f32, y: f32) {
-        self.x = x;
-        self.y = y;
+        if self.thrown.is_none() {
+            self.x = x;
+            self.y = y;
+        }
+    }
+
+    /// Let go of the well; it keeps its measured velocity if that is fast enough
+    /// Returns false if the well should simply disappear
+    pub fn throw(&mut self, throw: WellThrow, pixel_ratio: f32) -> bool {
+        let min_speed = WELL_THROW_MIN_SPEED * pixel_ratio;
+        if self.vx * self.vx + self.vy * self.vy < min_speed * min_speed {
+            return false;
+        }
+        self.thrown = Some(throw);
+        true
+    }
+
+    /// Measure velocity from the movement since the last update (held) or coast,
+    /// decelerate and bounce (thrown). Returns false once a thrown well has slowed
+    /// down or left the canvas
+    pub fn update(&mut self, delta_time: f32, width: f32, height: f32, pixel_ratio: f32) -> bool {
+        let Some(throw) = self.thrown else {
+            if delta_time > 0.0 {
+                let follow = 1.0 - WELL_VELOCITY_SMOOTHING;
+                self.vx += ((self.x - self.last_x) / delta_time - self.vx) * follow;
+                self.vy += ((self.y - self.last_y) / delta_time - self.vy) * follow;
+            }
+            self.last_x = self.x;
+            self.last_y = self.y;
+            return true;
+        };
+
+        let keep = throw.friction.powf(delta_time);
+        self.vx *= keep;
+        self.vy *= keep;
+        self.x += self.vx * delta_time;
+        self.y += self.vy * delta_time;
+
+        if throw.bounce {
+            let bounce = |p: &mut f32, v: &mut f32, size: f32| {
+                if *p < 0.0 {
+                    *p = -*p;
+                    *v = v.abs() * WELL_BOUNCE_RESTITUTION;
+                } else if *p > size {
+                    *p = 2.0 * size - *p;
+                    *v = -v.abs() * WELL_BOUNCE_RESTITUTION;
+                }
+                *p = p.clamp(0.0, size);
+            };
+            bounce(&mut self.x, &mut self.vx, width);
+            bounce(&mut self.y, &mut self.vy, height);
+        } else if self.x < 0.0 || self.x > width || self.y < 0.0 || self.y > height {
+            return false;
+        }
+
+        let stop_speed = WELL_THROW_STOP_SPEED * pixel_ratio;
+        self.vx * self.vx + self.vy * self.vy >= stop_speed * stop_speed
     }
 }
 
@@ -120,6 +375,10 @@ pub enum MouseMode {
     Push,
     Pull,
     Swirl,
+    /// Points snap softly onto a hex lattice centered on the cursor
+    Magnetize,
+    /// The cursor is a solid disc: points inside are projected onto its rim
+    Collide,
 }
 
 impl MouseMode {
@@ -128,12 +387,15 @@ impl MouseMode {
         match value {
             1 => MouseMode::Pull,
             2 => MouseMode::Swirl,
+            3 => MouseMode::Magnetize,
+            4 => MouseMode::Collide,
             _ => MouseMode::Push,
         }
     }
 }
 
 /// Mouse state for interaction calculations
+#[derive(Clone)]
 pub struct MouseState {
     pub x: f32,
     pub y: f32,
@@ -145,6 +407,12 @@ pub struct MouseState {
     pub radius: f32,
     pub strength: f32,
     pub mode: MouseMode,
+    /// Fraction of previous velocity kept each update (0 = no smoothing)
+    pub velocity_smoothing: f32,
+    /// Velocity multiplier per update while the pointer is outside the canvas
+    pub off_canvas_decay: f32,
+    /// Use the unsmoothed per-update delta as velocity
+    pub raw_velocity: bool,
 }
 
 impl MouseState {
@@ -157,12 +425,22 @@ impl MouseState {
             vx: 0.0,
             vy: 0.0,
             in_canvas: false,
-            radius: 150.0,
-            strength: 80.0,
+            radius: DEFAULT_MOUSE_RADIUS,
+            strength: DEFAULT_MOUSE_STRENGTH,
             mode: MouseMode::Push,
+            velocity_smoothing: DEFAULT_MOUSE_VELOCITY_SMOOTHING,
+            off_canvas_decay: DEFAULT_MOUSE_OFF_CANVAS_DECAY,
+            raw_velocity: false,
         }
     }
 
+    /// Configure how pointer velocity is smoothed and decays
+    pub fn set_dynamics(&mut self, smoothing: f32, off_canvas_decay: f32, raw_velocity: bool) {
+        self.velocity_smoothing = smoothing.clamp(0.0, 0.99);
+        self.off_canvas_decay = off_canvas_decay.clamp(0.0, 1.0);
+        self.raw_velocity = raw_velocity;
+    }
+
     /// Update mouse state with new position
     pub fn update(
         &mut self,
@@ -172,6 +450,23 @@ impl MouseState {
         radius: f32,
         strength: f32,
         mode: u32,
+    ) {
+        let moved = (x - self.x, y - self.y);
+        self.update_measured(x, y, in_canvas, radius, strength, mode, moved);
+    }
+
+    /// Update with a velocity sample (pixels per update) measured by the caller
+    /// instead of the position delta since the last update
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_measured(
+        &mut self,
+        x: f32,
+        y: f32,
+        in_canvas: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+        velocity: (f32, f32),
     ) {
         self.prev_x = self.x;
         self.prev_y = self.y;
@@ -181,15 +476,19 @@ impl MouseState {
         self.radius = radius;
         self.strength = strength;
 
-        // Update velocity (smoothed)
+        // Update velocity (smoothed unless raw)
         if in_canvas {
-            let new_vx = x - self.prev_x;
-            let new_vy = y - self.prev_y;
-            self.vx = new_vx * 0.4 + self.vx * 0.6;
-            self.vy = new_vy * 0.4 + self.vy * 0.6;
+            let (new_vx, new_vy) = velocity;
+            let keep = if self.raw_velocity {
+                0.0
+            } else {
+                self.velocity_smoothing
+            };
+            self.vx = new_vx * (1.0 - keep) + self.vx * keep;
+            self.vy = new_vy * (1.0 - keep) + self.vy * keep;
         } else {
-            self.vx *= 0.9;
-            self.vy *= 0.9;
+            self.vx *= self.off_canvas_decay;
+            self.vy *= self.off_canvas_decay;
         }
 
         self.mode = MouseMode::from_u32(mode);
@@ -200,3 +499,271 @@ impl MouseState {
         (self.vx * self.vx + self.vy * self.vy).sqrt()
     }
 }
+
+/// Extra pointers (e.g. touches) tracked alongside the primary mouse, keyed by host id
+#[derive(Clone)]
+pub struct PointerSet {
+    pointers: Vec<(u32, MouseState)>,
+}
+
+impl PointerSet {
+    pub fn new() -> Self {
+        Self {
+            pointers: Vec::new(),
+        }
+    }
+
+    /// Update a pointer, tracking it if new (ignored when MAX_POINTERS are tracked)
+    /// Velocity dynamics follow the primary mouse
+    #[allow(clippy::too_many_arguments)]
+    pub fn set(
+        &mut self,
+        id: u32,
+        x: f32,
+        y: f32,
+        active: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+        primary: &MouseState,
+    ) {
+        let index = match self.pointers.iter().position(|(pid, _)| *pid == id) {
+            Some(index) => index,
+            None if self.pointers.len() < MAX_POINTERS => {
+                // Start at the first position so the pointer has no initial velocity
+                let mut state = MouseState::new();
+                state.x = x;
+                state.y = y;
+                self.pointers.push((id, state));
+                self.pointers.len() - 1
+            }
+            None => return,
+        };
+        let state = &mut self.pointers[index].1;
+        state.velocity_smoothing = primary.velocity_smoothing;
+        state.off_canvas_decay = primary.off_canvas_decay;
+        state.raw_velocity = primary.raw_velocity;
+        state.update(x, y, active, radius, strength, mode);
+    }
+
+    /// Stop tracking a pointer
+    pub fn remove(&mut self, id: u32) {
+        self.pointers.retain(|(pid, _)| *pid != id);
+    }
+
+    /// Tracked pointers (inactive ones included)
+    pub fn iter(&self) -> impl Iterator<Item = &MouseState> {
+        self.pointers.iter().map(|(_, state)| state)
+    }
+
+    /// Largest radius among active pointers (CSS pixels)
+    pub fn max_radius(&self) -> f32 {
+        self.iter()
+            .filter(|p| p.in_canvas)
+            .map(|p| p.radius)
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Accumulates fractional emissions for a constant-rate source
+#[derive(Clone, Copy)]
+struct EmissionClock {
+    rate: f32,
+    accumulator: f32,
+}
+
+impl EmissionClock {
+    fn new(rate: f32) -> Self {
+        Self {
+            rate: rate.clamp(0.0, MAX_EMITTER_RATE),
+            accumulator: 0.0,
+        }
+    }
+
+    /// Advance the clock and return how many emissions are due
+    fn advance(&mut self, dt_seconds: f32) -> u32 {
+        self.accumulator += self.rate * dt_seconds;
+        let due = self.accumulator.floor();
+        self.accumulator -= due;
+        due as u32
+    }
+}
+
+/// Shockwave source that follows the cursor
+#[derive(Clone, Copy)]
+struct CursorEmitter {
+    clock: EmissionClock,
+    strength: f32,
+}
+
+/// Small shockwaves at random positions across the canvas
+#[derive(Clone, Copy)]
+struct RainEmitter {
+    clock: EmissionClock,
+    min_strength: f32,
+    max_strength: f32,
+}
+
+/// Shockwave source moving along a keyframed path
+#[derive(Clone)]
+struct PathEmitter {
+    id: u32,
+    /// Keyframes (x, y, t_seconds), sorted by time
+    path: Vec<(f32, f32, f32)>,
+    clock: EmissionClock,
+    strength: f32,
+    elapsed: f32,
+}
+
+impl PathEmitter {
+    /// Interpolated position at the current time, None before the first keyframe and
+    /// once the path is finished
+    fn position(&self) -> Option<(f32, f32)> {
+        let (first, last) = (self.path.first()?, self.path.last()?);
+        if self.elapsed < first.2 || self.elapsed > last.2 {
+            return None;
+        }
+
+        let next = self.path.iter().position(|k| k.2 >= self.elapsed)?;
+        if next == 0 {
+            return Some((first.0, first.1));
+        }
+
+        let (x0, y0, t0) = self.path[next - 1];
+        let (x1, y1, t1) = self.path[next];
+        let t = if t1 > t0 {
+            (self.elapsed - t0) / (t1 - t0)
+        } else {
+            1.0
+        };
+        Some((x0 + (x1 - x0) * t, y0 + (y1 - y0) * t))
+    }
+
+    /// Whether the time is past the last keyframe
+    fn finished(&self) -> bool {
+        self.path.last().is_none_or(|k| self.elapsed > k.2)
+    }
+}
+
+/// Continuous shockwave emitters (cursor-attached, rain and scripted paths)
+#[derive(Clone)]
+pub struct EmitterManager {
+    cursor: Option<CursorEmitter>,
+    rain: Option<RainEmitter>,
+    paths: Vec<PathEmitter>,
+    next_id: u32,
+}
+
+impl EmitterManager {
+    pub fn new() -> Self {
+        Self {
+            cursor: None,
+            rain: None,
+            paths: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Attach (or with rate <= 0, detach) the cursor emitter
+    pub fn set_cursor(&mut self, rate: f32, strength: f32) {
+        self.cursor = if rate > 0.0 {
+            Some(CursorEmitter {
+                clock: EmissionClock::new(rate),
+                strength,
+            })
+        } else {
+            None
+        };
+    }
+
+    /// Start (or with rate <= 0, stop) rain with strengths drawn from the range
+    pub fn set_rain(&mut self, rate: f32, min_strength: f32, max_strength: f32) {
+        self.rain = (rate > 0.0).then(|| RainEmitter {
+            clock: EmissionClock::new(rate),
+            min_strength: min_strength.min(max_strength),
+            max_strength: min_strength.max(max_strength),
+        });
+    }
+
+    /// Add an emitter following flat [x, y, t_seconds, ...] keyframes
+    /// Returns its id, or 0 if the path has no complete keyframe
+    pub fn add_path(&mut self, path: &[f32], rate: f32, strength: f32) -> u32 {
+        let mut keyframes: Vec<(f32, f32, f32)> = path
+            .chunks_exact(3)
+            .map(|k| (k[0], k[1], k[2].max(0.0)))
+            .collect();
+        if keyframes.is_empty() {
+            return 0;
+        }
+        keyframes.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        self.paths.push(PathEmitter {
+            id,
+            path: keyframes,
+            clock: EmissionClock::new(rate),
+            strength,
+            elapsed: 0.0,
+        });
+        id
+    }
+
+    /// Remove a path emitter by id
+    pub fn remove(&mut self, id: u32) {
+        self.paths.retain(|e| e.id != id);
+    }
+
+    /// Remove all emitters, including the cursor emitter and rain
+    pub fn clear(&mut self) {
+        self.cursor = None;
+        self.rain = None;
+        self.paths.clear();
+    }
+
+    /// Whether waves keep spawning without pointer input (rain or path emitters)
+    pub fn emits_without_input(&self) -> bool {
+        self.rain.is_some() || !self.paths.is_empty()
+    }
+
+    /// Advance emitters and spawn their shockwaves; rain falls on a `width` x `height`
+    /// canvas (canvas pixels) using its own random stream
+    pub fn update(
+        &mut self,
+        dt_seconds: f32,
+        mouse: &MouseState,
+        rng: &mut Rng,
+        width: f32,
+        height: f32,
+        shockwaves: &mut ShockwaveManager,
+    ) {
+        if let Some(ref mut cursor) = self.cursor {
+            let due = cursor.clock.advance(dt_seconds);
+            if mouse.in_canvas {
+                for _ in 0..due {
+                    shockwaves.add(Shockwave::new(mouse.x, mouse.y, cursor.strength));
+                }
+            }
+        }
+
+        if let Some(ref mut rain) = self.rain {
+            for _ in 0..rain.clock.advance(dt_seconds) {
+                let (x, y) = (rng.next_f32() * width, rng.next_f32() * height);
+                let range = rain.max_strength - rain.min_strength;
+                let strength = rain.min_strength + rng.next_f32() * range;
+                shockwaves.add(Shockwave::new(x, y, strength));
+            }
+        }
+
+        // The clock only runs while on the path, so a late start does not emit a burst
+        for emitter in &mut self.paths {
+            emitter.elapsed += dt_seconds;
+            if let Some((x, y)) = emitter.position() {
+                for _ in 0..emitter.clock.advance(dt_seconds) {
+                    shockwaves.add(Shockwave::new(x, y, emitter.strength));
+                }
+            }
+        }
+        self.paths.retain(|e| !e.finished());
+    }
+}
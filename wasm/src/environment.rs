@@ -0,0 +1,124 @@
+//! Time-of-day and seasonal variation: the host passes the local hour and day of the
+//! year, configurable curves turn them into drift speed, palette warmth and relief
+
+use crate::constants::{MAX_ENVIRONMENT_CURVE_KEYS, MAX_ENVIRONMENT_CURVE_VALUE};
+
+/// Parameter driven by an environment curve
+#[derive(Clone, Copy, PartialEq)]
+pub enum EnvironmentTarget {
+    /// Drift speed multiplier
+    DriftSpeed,
+    /// Palette warmth for the host theme (0 = cool, 1 = warm)
+    PaletteWarmth,
+    /// Height intensity multiplier
+    HeightScale,
+}
+
+impl EnvironmentTarget {
+    /// Convert from numeric value (for JS interop), None if unknown
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(EnvironmentTarget::DriftSpeed),
+            1 => Some(EnvironmentTarget::PaletteWarmth),
+            2 => Some(EnvironmentTarget::HeightScale),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Periodic piecewise-linear curve with evenly spaced keys (the last wraps to the first)
+#[derive(Clone)]
+pub struct Curve {
+    keys: Vec<f32>,
+}
+
+impl Curve {
+    /// Keys are clamped to 0..=MAX_ENVIRONMENT_CURVE_VALUE; None if empty or too long
+    pub fn new(keys: &[f32]) -> Option<Self> {
+        if keys.is_empty() || keys.len() > MAX_ENVIRONMENT_CURVE_KEYS {
+            return None;
+        }
+        let keys = keys
+            .iter()
+            .map(|&k| {
+                if k.is_finite() {
+                    k.clamp(0.0, MAX_ENVIRONMENT_CURVE_VALUE)
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        Some(Self { keys })
+    }
+
+    /// Value at `phase` through the period (any real, wraps)
+    pub fn sample(&self, phase: f32) -> f32 {
+        let n = self.keys.len();
+        let position = phase.rem_euclid(1.0) * n as f32;
+        let i = (position as usize).min(n - 1);
+        let t = position - i as f32;
+        self.keys[i] + (self.keys[(i + 1) % n] - self.keys[i]) * t
+    }
+}
+
+/// Daily and seasonal curves per target plus the values for the last time set
+#[derive(Clone)]
+pub struct Environment {
+    daily: [Curve; 3],
+    seasonal: [Curve; 3],
+    /// (hour, day of year) of the last `set_time`, None before the first
+    time: Option<(f32, f32)>,
+    values: [f32; 3],
+}
+
+impl Environment {
+    /// Calm, cool mornings and warm, active evenings; warmer summers
+    pub fn new() -> Self {
+        let curve = |keys: &[f32]| Curve::new(keys).expect("default curve");
+        Self {
+            // Keys every 3 hours from midnight
+            daily: [
+                curve(&[0.7, 0.6, 0.7, 0.9, 1.0, 1.1, 1.3, 1.0]),
+                curve(&[0.4, 0.3, 0.2, 0.3, 0.5, 0.7, 0.9, 0.6]),
+                curve(&[1.0]),
+            ],
+            // Keys at the start of each quarter from January 1st
+            seasonal: [
+                curve(&[1.0]),
+                curve(&[0.8, 1.0, 1.2, 1.0]),
+                curve(&[1.0]),
+            ],
+            time: None,
+            values: [1.0, 0.5, 1.0],
+        }
+    }
+
+    /// Replace a target's curves and re-evaluate at the current time
+    pub fn set_curves(&mut self, target: EnvironmentTarget, daily: Curve, seasonal: Curve) {
+        self.daily[target.index()] = daily;
+        self.seasonal[target.index()] = seasonal;
+        if let Some((hour, day)) = self.time {
+            self.set_time(hour, day);
+        }
+    }
+
+    /// Evaluate every target at a local hour (0..24) and day of the year (0..365)
+    pub fn set_time(&mut self, hour: f32, day_of_year: f32) {
+        self.time = Some((hour, day_of_year));
+        let (day_phase, year_phase) = (hour / 24.0, day_of_year / 365.25);
+        for i in 0..3 {
+            self.values[i] = self.daily[i].sample(day_phase) * self.seasonal[i].sample(year_phase);
+        }
+        let warmth = EnvironmentTarget::PaletteWarmth.index();
+        self.values[warmth] = self.values[warmth].min(1.0);
+    }
+
+    /// Current value of a target (neutral before the first `set_time`)
+    pub fn value(&self, target: EnvironmentTarget) -> f32 {
+        self.values[target.index()]
+    }
+}
@@ -0,0 +1,142 @@
+//! Rectangular exclusion zones for content areas (e.g. hero text bounding boxes)
+//! Zones repel points toward their nearest edge and/or flag overlapping triangles
+
+use crate::constants::{EXCLUSION_REPEL_RATE, MAX_EXCLUSION_RECTS};
+use crate::point::Point;
+
+/// What an exclusion zone does
+#[derive(Clone, Copy, PartialEq)]
+pub enum ExclusionMode {
+    /// Push points inside toward the nearest edge
+    Repel,
+    /// Flag overlapping triangles (EXCLUSION vertex attribute)
+    Flag,
+    /// Repel and flag
+    Both,
+}
+
+impl ExclusionMode {
+    /// Convert from numeric value (for JS interop)
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => ExclusionMode::Flag,
+            2 => ExclusionMode::Both,
+            _ => ExclusionMode::Repel,
+        }
+    }
+
+    fn repels(self) -> bool {
+        self != ExclusionMode::Flag
+    }
+
+    fn flags(self) -> bool {
+        self != ExclusionMode::Repel
+    }
+}
+
+/// Axis-aligned rectangle in canvas pixels
+#[derive(Clone, Copy)]
+pub struct ExclusionRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub mode: ExclusionMode,
+}
+
+impl ExclusionRect {
+    /// Whether the rectangle overlaps the box [min_x, max_x] x [min_y, max_y]
+    #[inline]
+    pub fn overlaps(&self, min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> bool {
+        max_x > self.x
+            && min_x < self.x + self.width
+            && max_y > self.y
+            && min_y < self.y + self.height
+    }
+
+    /// Offset from (x, y) to the nearest edge, None if the point is outside
+    fn escape(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        let left = x - self.x;
+        let right = self.x + self.width - x;
+        let top = y - self.y;
+        let bottom = self.y + self.height - y;
+        if left <= 0.0 || right <= 0.0 || top <= 0.0 || bottom <= 0.0 {
+            return None;
+        }
+        let nearest = left.min(right).min(top).min(bottom);
+        Some(if nearest == left {
+            (-left, 0.0)
+        } else if nearest == right {
+            (right, 0.0)
+        } else if nearest == top {
+            (0.0, -top)
+        } else {
+            (0.0, bottom)
+        })
+    }
+}
+
+/// Registered exclusion zones
+#[derive(Clone)]
+pub struct ExclusionZones {
+    rects: Vec<(u32, ExclusionRect)>,
+    next_id: u32,
+}
+
+impl ExclusionZones {
+    pub fn new() -> Self {
+        Self {
+            rects: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Register a rectangle; returns its id, or 0 if empty or MAX_EXCLUSION_RECTS are registered
+    pub fn add(&mut self, x: f32, y: f32, width: f32, height: f32, mode: ExclusionMode) -> u32 {
+        let finite = [x, y, width, height].iter().all(|v| v.is_finite());
+        if !finite || width <= 0.0 || height <= 0.0 || self.rects.len() >= MAX_EXCLUSION_RECTS {
+            return 0;
+        }
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        self.rects.push((
+            id,
+            ExclusionRect {
+                x,
+                y,
+                width,
+                height,
+                mode,
+            },
+        ));
+        id
+    }
+
+    /// Remove a rectangle by id
+    pub fn remove(&mut self, id: u32) {
+        self.rects.retain(|(rid, _)| *rid != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    /// Rectangles that flag triangles
+    pub fn flagging(&self) -> impl Iterator<Item = ExclusionRect> + '_ {
+        self.rects.iter().map(|&(_, r)| r).filter(|r| r.mode.flags())
+    }
+
+    /// Nudge points inside repelling rectangles toward the nearest edge
+    /// Moves a fraction of the remaining distance per frame, scaled by delta_time
+    pub fn repel(&self, points: &mut [Point], delta_time: f32) {
+        let rate = (EXCLUSION_REPEL_RATE * delta_time).min(1.0);
+        for (_, rect) in self.rects.iter().filter(|(_, r)| r.mode.repels()) {
+            for point in points.iter_mut() {
+                if let Some((ex, ey)) = rect.escape(point.x, point.y) {
+                    point.dx += ex * rate;
+                    point.dy += ey * rate;
+                }
+            }
+        }
+    }
+}
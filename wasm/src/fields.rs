@@ -0,0 +1,87 @@
+//! Typed field reading shared by every object format presets are parsed from
+//! Readers leave the target untouched when the key is absent, so one merge handles
+//! sparse updates from JS objects and JSON files alike
+
+/// A parsed object value: a JS value, or a JSON value with the `cli` feature
+pub trait FieldSource: Sized {
+    /// Property `key`, None if absent (or undefined)
+    fn field(&self, key: &str) -> Option<Self>;
+    fn is_object(&self) -> bool;
+    fn as_f64(&self) -> Option<f64>;
+    fn as_bool(&self) -> Option<bool>;
+    fn as_string(&self) -> Option<String>;
+}
+
+/// Last segment of a dotted field path (the object key)
+fn key_of(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+/// Get a nested object group, None if absent
+pub fn group<S: FieldSource>(value: &S, key: &str) -> Result<Option<S>, String> {
+    match value.field(key) {
+        None => Ok(None),
+        Some(group) if group.is_object() => Ok(Some(group)),
+        Some(_) => Err(format!("{} must be an object", key)),
+    }
+}
+
+/// Read a finite number into `target`
+pub fn read_f32<S: FieldSource>(value: &S, path: &str, target: &mut f32) -> Result<(), String> {
+    let Some(field) = value.field(key_of(path)) else {
+        return Ok(());
+    };
+    match field.as_f64() {
+        Some(v) if v.is_finite() => {
+            *target = v as f32;
+            Ok(())
+        }
+        _ => Err(format!("{} must be a finite number", path)),
+    }
+}
+
+/// Read a non-negative integer into `target` (integral floats such as 3.0 included)
+pub fn read_u32<S: FieldSource>(value: &S, path: &str, target: &mut u32) -> Result<(), String> {
+    let Some(field) = value.field(key_of(path)) else {
+        return Ok(());
+    };
+    match field.as_f64() {
+        Some(v) if v >= 0.0 && v <= u32::MAX as f64 && v.fract() == 0.0 => {
+            *target = v as u32;
+            Ok(())
+        }
+        _ => Err(format!("{} must be a non-negative integer", path)),
+    }
+}
+
+/// Read a boolean into `target`
+pub fn read_bool<S: FieldSource>(value: &S, path: &str, target: &mut bool) -> Result<(), String> {
+    let Some(field) = value.field(key_of(path)) else {
+        return Ok(());
+    };
+    match field.as_bool() {
+        Some(v) => {
+            *target = v;
+            Ok(())
+        }
+        None => Err(format!("{} must be a boolean", path)),
+    }
+}
+
+/// Read a string into `target`
+pub fn read_string<S: FieldSource>(
+    value: &S,
+    path: &str,
+    target: &mut String,
+) -> Result<(), String> {
+    let Some(field) = value.field(key_of(path)) else {
+        return Ok(());
+    };
+    match field.as_string() {
+        Some(v) => {
+            *target = v;
+            Ok(())
+        }
+        None => Err(format!("{} must be a string", path)),
+    }
+}
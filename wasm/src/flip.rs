@@ -0,0 +1,106 @@
+//! Tile flip animation: triangles in a region rotate like tiles, staggered across it
+//! The simulation schedules flips; shaders read per-triangle phase/axis attributes
+
+use crate::constants::{FLIP_DURATION_SECONDS, MAX_FLIP_REGIONS, MAX_FLIP_STAGGER};
+
+/// A scheduled flip over a rectangle (canvas pixels)
+/// The flip sweeps along the rectangle's longer side
+#[derive(Clone, Copy)]
+struct FlipRegion {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    /// Simulation time the flip was triggered (seconds)
+    start: f32,
+    /// Delay between the first and last triangle starting (seconds)
+    stagger: f32,
+}
+
+impl FlipRegion {
+    #[inline]
+    fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+
+    #[inline]
+    fn sweeps_horizontally(&self) -> bool {
+        self.width >= self.height
+    }
+
+    /// Start delay for a triangle centered at (x, y)
+    #[inline]
+    fn delay(&self, x: f32, y: f32) -> f32 {
+        let along = if self.sweeps_horizontally() {
+            (x - self.x) / self.width
+        } else {
+            (y - self.y) / self.height
+        };
+        along.clamp(0.0, 1.0) * self.stagger
+    }
+
+    fn is_finished(&self, time: f32) -> bool {
+        time - self.start >= self.stagger + FLIP_DURATION_SECONDS
+    }
+}
+
+/// Manages active flip regions
+#[derive(Clone)]
+pub struct FlipManager {
+    regions: Vec<FlipRegion>,
+}
+
+impl FlipManager {
+    pub fn new() -> Self {
+        Self {
+            regions: Vec::with_capacity(MAX_FLIP_REGIONS),
+        }
+    }
+
+    /// Schedule a flip starting at `time` (seconds); replaces the oldest when full
+    pub fn add(&mut self, x: f32, y: f32, width: f32, height: f32, stagger: f32, time: f32) {
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        if self.regions.len() >= MAX_FLIP_REGIONS {
+            self.regions.remove(0);
+        }
+        self.regions.push(FlipRegion {
+            x,
+            y,
+            width,
+            height,
+            start: time,
+            stagger: stagger.clamp(0.0, MAX_FLIP_STAGGER),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Drop finished flips
+    pub fn update(&mut self, time: f32) {
+        self.regions.retain(|r| !r.is_finished(time));
+    }
+
+    /// Flip phase and rotation axis for a triangle centroid (canvas pixels)
+    /// Phase runs 0 -> 1 over the flip (a full turn, so 0 and 1 both read as rest);
+    /// the axis is a unit vector across the sweep, (0, 0) when no flip covers the triangle
+    /// The newest covering region wins where regions overlap
+    pub fn sample(&self, x: f32, y: f32, time: f32) -> (f32, f32, f32) {
+        for region in self.regions.iter().rev() {
+            if !region.contains(x, y) {
+                continue;
+            }
+            let elapsed = time - region.start - region.delay(x, y);
+            let phase = (elapsed / FLIP_DURATION_SECONDS).clamp(0.0, 1.0);
+            return if region.sweeps_horizontally() {
+                (phase, 0.0, 1.0)
+            } else {
+                (phase, 1.0, 0.0)
+            };
+        }
+        (0.0, 0.0, 0.0)
+    }
+}
@@ -0,0 +1,39 @@
+//! Curl-noise flow field: divergence-free large-scale drift of base positions
+
+use crate::constants::FLOW_FIELD_EPSILON;
+use crate::noise::simplex3d;
+use crate::point::Point;
+
+/// Flow field parameters (scale in 1/CSS pixels, strength in CSS pixels per frame)
+#[derive(Clone, Copy)]
+pub struct FlowField {
+    pub scale: f32,
+    pub strength: f32,
+    /// Noise evolution per frame
+    pub time_speed: f32,
+}
+
+impl FlowField {
+    /// Curl of the noise potential at a canvas position: (dψ/dy, -dψ/dx)
+    /// Returned in canvas pixels per frame
+    pub fn velocity(&self, x: f32, y: f32, time: f32, pixel_ratio: f32) -> (f32, f32) {
+        let nx = x / pixel_ratio * self.scale;
+        let ny = y / pixel_ratio * self.scale;
+        let nz = time * self.time_speed;
+        let e = FLOW_FIELD_EPSILON;
+
+        let dpsi_dx = (simplex3d(nx + e, ny, nz) - simplex3d(nx - e, ny, nz)) / (2.0 * e);
+        let dpsi_dy = (simplex3d(nx, ny + e, nz) - simplex3d(nx, ny - e, nz)) / (2.0 * e);
+
+        let strength = self.strength * pixel_ratio;
+        (dpsi_dy * strength, -dpsi_dx * strength)
+    }
+
+    /// Move a point's base position along the field
+    #[inline]
+    pub fn advect(&self, point: &mut Point, delta_time: f32, time: f32, pixel_ratio: f32) {
+        let (vx, vy) = self.velocity(point.base_x, point.base_y, time, pixel_ratio);
+        point.base_x += vx * delta_time;
+        point.base_y += vy * delta_time;
+    }
+}
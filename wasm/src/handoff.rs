@@ -0,0 +1,152 @@
+//! Compact binary handoff of the visual state between two simulations, e.g. when a
+//! single-page app remounts the canvas on navigation
+//!
+//! Little-endian layout: magic, version, point count, requested point count, the look
+//! parameters as f32, then
+//! per point its positions normalized to the canvas (so any receiving size works) and
+//! velocities in CSS pixels per frame.
+//! Heights are not sent; the receiver regenerates them for its own canvas
+
+use crate::constants::{MAX_POINT_COUNT, MIN_POINT_COUNT};
+use crate::point::Point;
+
+/// "PBGH"
+const MAGIC: [u8; 4] = *b"PBGH";
+const VERSION: u32 = 2;
+
+/// Magic, version, count and requested count words, then HEADER_FLOATS f32
+const HEADER_WORDS: usize = 4;
+const HEADER_FLOATS: usize = 5;
+const HEADER_BYTES: usize = (HEADER_WORDS + HEADER_FLOATS) * 4;
+
+/// x, y, base x, base y, drift vx, vy, displacement dx, dy, depth
+const POINT_FLOATS: usize = 9;
+
+/// Parameters that shape the look and travel with the points
+#[derive(Clone, Copy)]
+pub struct HandoffParams {
+    /// Point count the sender's host asked for, before quality tier scaling
+    pub requested_points: usize,
+    pub noise_scale: f32,
+    pub height_intensity: f32,
+    pub spring_back: f32,
+    pub damping: f32,
+    pub velocity_influence: f32,
+}
+
+/// Serialize points on a `width` x `height` canvas (canvas pixels)
+pub fn encode(
+    points: &[Point],
+    width: f32,
+    height: f32,
+    pixel_ratio: f32,
+    params: &HandoffParams,
+) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_BYTES + points.len() * POINT_FLOATS * 4);
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(points.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(params.requested_points as u32).to_le_bytes());
+
+    let mut push = |value: f32| bytes.extend_from_slice(&value.to_le_bytes());
+    for value in [
+        params.noise_scale,
+        params.height_intensity,
+        params.spring_back,
+        params.damping,
+        params.velocity_influence,
+    ] {
+        push(value);
+    }
+
+    for point in points {
+        // Pinned points travel at their pin, free again on the other side
+        let (x, y) = point.pin.unwrap_or((point.x, point.y));
+        for value in [
+            x / width,
+            y / height,
+            point.base_x / width,
+            point.base_y / height,
+            point.vx / pixel_ratio,
+            point.vy / pixel_ratio,
+            point.dx / pixel_ratio,
+            point.dy / pixel_ratio,
+            point.depth,
+        ] {
+            push(value);
+        }
+    }
+    bytes
+}
+
+/// Parse a handoff and remap its points onto a `width` x `height` canvas (canvas
+/// pixels); heights are left at 0 for the caller to regenerate
+pub fn decode(
+    bytes: &[u8],
+    width: f32,
+    height: f32,
+    pixel_ratio: f32,
+) -> Result<(HandoffParams, Vec<Point>), String> {
+    if bytes.len() < HEADER_BYTES || bytes[..4] != MAGIC {
+        return Err("not a handoff buffer".to_string());
+    }
+    let word = |offset: usize| {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("length checked"))
+    };
+    let float = |offset: usize| f32::from_bits(word(offset));
+
+    let version = word(4);
+    if version != VERSION {
+        return Err(format!("unsupported handoff version {} (expected {})", version, VERSION));
+    }
+    let count = word(8) as usize;
+    if !(MIN_POINT_COUNT..=MAX_POINT_COUNT).contains(&count) {
+        return Err(format!("handoff point count {} out of range", count));
+    }
+    let requested_points = word(12) as usize;
+    if !(MIN_POINT_COUNT..=MAX_POINT_COUNT).contains(&requested_points) {
+        return Err(format!("handoff requested point count {} out of range", requested_points));
+    }
+    if bytes.len() != HEADER_BYTES + count * POINT_FLOATS * 4 {
+        return Err("handoff buffer length does not match its point count".to_string());
+    }
+
+    let header: Vec<f32> = (0..HEADER_FLOATS).map(|i| float((HEADER_WORDS + i) * 4)).collect();
+    if header.iter().any(|value| !value.is_finite()) {
+        return Err("handoff header contains non-finite values".to_string());
+    }
+    let params = HandoffParams {
+        requested_points,
+        noise_scale: header[0],
+        height_intensity: header[1],
+        spring_back: header[2],
+        damping: header[3],
+        velocity_influence: header[4],
+    };
+
+    let mut points = Vec::with_capacity(count);
+    for i in 0..count {
+        let start = HEADER_BYTES + i * POINT_FLOATS * 4;
+        let v: Vec<f32> = (0..POINT_FLOATS).map(|j| float(start + j * 4)).collect();
+        if v.iter().any(|value| !value.is_finite()) {
+            return Err(format!("handoff point {} contains non-finite values", i));
+        }
+        points.push(Point {
+            x: v[0] * width,
+            y: v[1] * height,
+            z: 0.0,
+            base_x: v[2] * width,
+            base_y: v[3] * height,
+            vx: v[4] * pixel_ratio,
+            vy: v[5] * pixel_ratio,
+            dx: v[6] * pixel_ratio,
+            dy: v[7] * pixel_ratio,
+            depth: v[8].clamp(0.0, 1.0),
+            pin: None,
+            stuck: None,
+            anchored: false,
+            layer: 0,
+        });
+    }
+    Ok((params, points))
+}
@@ -0,0 +1,99 @@
+//! Coarse scalar heat field: interactions deposit heat, which diffuses and decays
+//! Heat drives local jitter and the HEAT vertex attribute
+
+use crate::constants::{DEFAULT_HEAT_DIFFUSION, DEFAULT_HEAT_RETENTION};
+
+/// Heat values on a uniform grid covering the canvas
+#[derive(Clone)]
+pub struct HeatField {
+    cols: usize,
+    rows: usize,
+    cell_size: f32,
+    values: Vec<f32>,
+    /// Diffusion scratch (reused each update)
+    next: Vec<f32>,
+    /// Fraction of the neighbor difference exchanged per frame (0..1)
+    pub diffusion: f32,
+    /// Fraction of heat kept per frame (0..1)
+    pub retention: f32,
+}
+
+impl HeatField {
+    pub fn new() -> Self {
+        Self {
+            cols: 0,
+            rows: 0,
+            cell_size: 1.0,
+            values: Vec::new(),
+            next: Vec::new(),
+            diffusion: DEFAULT_HEAT_DIFFUSION,
+            retention: DEFAULT_HEAT_RETENTION,
+        }
+    }
+
+    /// Match the grid to the canvas, resetting heat if the layout changed
+    pub fn ensure_size(&mut self, width: f32, height: f32, cell_size: f32) {
+        let cell_size = cell_size.max(1.0);
+        let cols = ((width / cell_size).ceil() as usize).max(1);
+        let rows = ((height / cell_size).ceil() as usize).max(1);
+        if cols != self.cols || rows != self.rows || cell_size != self.cell_size {
+            self.cols = cols;
+            self.rows = rows;
+            self.cell_size = cell_size;
+            self.values.clear();
+            self.values.resize(cols * rows, 0.0);
+        }
+    }
+
+    /// Add heat to the cell containing (x, y)
+    pub fn deposit(&mut self, x: f32, y: f32, amount: f32) {
+        if self.values.is_empty() || !amount.is_finite() {
+            return;
+        }
+        let col = ((x.max(0.0) / self.cell_size) as usize).min(self.cols - 1);
+        let row = ((y.max(0.0) / self.cell_size) as usize).min(self.rows - 1);
+        let cell = &mut self.values[row * self.cols + col];
+        *cell = (*cell + amount).min(1.0);
+    }
+
+    /// Diffuse toward the 4-neighbor average and decay, scaled by delta_time (frames)
+    pub fn update(&mut self, delta_time: f32) {
+        if self.values.is_empty() {
+            return;
+        }
+        let (cols, rows) = (self.cols, self.rows);
+        let exchange = (self.diffusion * delta_time).clamp(0.0, 1.0);
+        let keep = self.retention.powf(delta_time);
+
+        self.next.clear();
+        self.next.extend((0..rows * cols).map(|i| {
+            let (col, row) = (i % cols, i / cols);
+            let value = self.values[i];
+            let left = if col > 0 { self.values[i - 1] } else { value };
+            let right = if col + 1 < cols { self.values[i + 1] } else { value };
+            let up = if row > 0 { self.values[i - cols] } else { value };
+            let down = if row + 1 < rows { self.values[i + cols] } else { value };
+            let average = (left + right + up + down) * 0.25;
+            (value + (average - value) * exchange) * keep
+        }));
+        std::mem::swap(&mut self.values, &mut self.next);
+    }
+
+    /// Bilinearly interpolated heat at (x, y), 0..1
+    pub fn sample(&self, x: f32, y: f32) -> f32 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        let gx = (x / self.cell_size - 0.5).clamp(0.0, (self.cols - 1) as f32);
+        let gy = (y / self.cell_size - 0.5).clamp(0.0, (self.rows - 1) as f32);
+        let (c0, r0) = (gx as usize, gy as usize);
+        let c1 = (c0 + 1).min(self.cols - 1);
+        let r1 = (r0 + 1).min(self.rows - 1);
+        let (fx, fy) = (gx - c0 as f32, gy - r0 as f32);
+
+        let at = |c: usize, r: usize| self.values[r * self.cols + c];
+        let top = at(c0, r0) + (at(c1, r0) - at(c0, r0)) * fx;
+        let bottom = at(c0, r1) + (at(c1, r1) - at(c0, r1)) * fx;
+        top + (bottom - top) * fy
+    }
+}
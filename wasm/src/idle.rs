@@ -0,0 +1,93 @@
+//! Idle behavior: once pointer input stops for a while, a slowly wandering swirl and
+//! occasional soft shockwaves keep the background alive; both ramp off when input
+//! returns
+
+use crate::constants::{
+    IDLE_RAMP_DOWN_FRAMES, IDLE_RAMP_UP_FRAMES, IDLE_SHOCKWAVE_INTERVAL_FRAMES,
+    IDLE_SHOCKWAVE_STRENGTH, IDLE_SWIRL_SPEED, IDLE_SWIRL_STRENGTH,
+};
+use crate::point::Point;
+
+/// Idle clock and the current ambient level
+#[derive(Clone)]
+pub struct IdleBehavior {
+    /// Frames without input before the ambient effects start
+    delay: f32,
+    /// Ambient effect scale (1 = gentle default)
+    intensity: f32,
+    /// Frames since the last pointer input
+    quiet: f32,
+    /// Ramp position in [0, 1]
+    level: f32,
+    /// Frames until the next soft shockwave
+    next_wave: f32,
+}
+
+impl IdleBehavior {
+    pub fn new(delay_frames: f32, intensity: f32) -> Self {
+        Self {
+            delay: delay_frames,
+            intensity,
+            quiet: 0.0,
+            level: 0.0,
+            next_wave: IDLE_SHOCKWAVE_INTERVAL_FRAMES,
+        }
+    }
+
+    /// Advance by `delta_time` frames; `input` resets the idle clock
+    /// Returns true on the step a soft shockwave is due
+    pub fn update(&mut self, input: bool, delta_time: f32) -> bool {
+        self.quiet = if input { 0.0 } else { self.quiet + delta_time };
+        if self.quiet >= self.delay {
+            self.level = (self.level + delta_time / IDLE_RAMP_UP_FRAMES).min(1.0);
+        } else {
+            self.level = (self.level - delta_time / IDLE_RAMP_DOWN_FRAMES).max(0.0);
+        }
+        if self.level <= 0.0 {
+            self.next_wave = IDLE_SHOCKWAVE_INTERVAL_FRAMES;
+            return false;
+        }
+        self.next_wave -= delta_time;
+        if self.next_wave > 0.0 {
+            return false;
+        }
+        self.next_wave += IDLE_SHOCKWAVE_INTERVAL_FRAMES;
+        true
+    }
+
+    /// Strength of the next soft shockwave at the current level
+    pub fn shockwave_strength(&self) -> f32 {
+        IDLE_SHOCKWAVE_STRENGTH * self.intensity * self.level
+    }
+
+    /// Push displacement around a center wandering over the canvas; the push fades
+    /// from the center to the edge of a radius of a third of the shorter side
+    pub fn swirl(
+        &self,
+        points: &mut [Point],
+        width: f32,
+        height: f32,
+        pixel_ratio: f32,
+        time: f32,
+        delta_time: f32,
+    ) {
+        let strength = IDLE_SWIRL_STRENGTH * self.intensity * self.level * pixel_ratio * delta_time;
+        if strength <= 0.0 {
+            return;
+        }
+        let phase = time * IDLE_SWIRL_SPEED;
+        let cx = width * (0.5 + 0.3 * phase.sin());
+        let cy = height * (0.5 + 0.3 * (phase * 0.7).cos());
+        let radius = width.min(height) / 3.0;
+        for point in points.iter_mut() {
+            let (dx, dy) = (point.x - cx, point.y - cy);
+            let dist = (dx * dx + dy * dy).sqrt();
+            if dist < 1e-3 || dist >= radius {
+                continue;
+            }
+            let push = strength * (1.0 - dist / radius) / dist;
+            point.dx += -dy * push;
+            point.dy += dx * push;
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Incremental triangulation: while the triangulation input keeps its layout (same
+//! vertices from the same sources), the previous Delaunay mesh is kept and repaired with
+//! local edge flips around the vertices that moved, instead of triangulating from scratch
+//!
+//! A full pass runs when the layout changed, a moved vertex inverted one of its
+//! triangles or dented the hull, or the repair needs too many flips
+//!
+//! With a hysteresis margin, an edge only flips once its quad is clearly past
+//! co-circular, so nearly co-circular points stop flipping the diagonal every frame
+
+use delaunator::{next_halfedge, prev_halfedge, Point, Triangulation, EMPTY};
+
+use crate::constants::INCREMENTAL_MAX_FLIP_FRACTION;
+use crate::triangulation::Vertex;
+
+/// Vertex positions the previous mesh was last checked against
+#[derive(Clone)]
+pub struct IncrementalMesh {
+    /// Movement (CSS pixels) since the last check below which a vertex is not re-checked
+    epsilon: f32,
+    pixel_ratio: f32,
+    /// Layout of the triangulation input at the last full pass
+    sources: Vec<Option<usize>>,
+    /// Vertex positions when last checked
+    checked: Vec<Point>,
+    /// Orientation sign shared by all triangles of the mesh
+    orientation: f64,
+    /// Full and repaired passes since enabled
+    full_passes: u32,
+    repaired_passes: u32,
+    /// Scratch reused each pass: vertices off their checked position at all, and those
+    /// past epsilon (whose edges are re-checked for the Delaunay condition)
+    changed: Vec<bool>,
+    moved: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl IncrementalMesh {
+    pub fn new(epsilon: f32, pixel_ratio: f32) -> Self {
+        Self {
+            epsilon,
+            pixel_ratio,
+            sources: Vec::new(),
+            checked: Vec::new(),
+            orientation: 0.0,
+            full_passes: 0,
+            repaired_passes: 0,
+            changed: Vec::new(),
+            moved: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn set_pixel_ratio(&mut self, pixel_ratio: f32) {
+        self.pixel_ratio = pixel_ratio;
+    }
+
+    /// [full passes, repaired passes] since enabled
+    pub fn stats(&self) -> [u32; 2] {
+        [self.full_passes, self.repaired_passes]
+    }
+
+    /// Delaunay mesh of `points` (the delaunator input built from `vertices`): the
+    /// `previous` mesh repaired in place when possible (no allocation), else a full
+    /// triangulation
+    /// `hysteresis` (radians, 0 = exact Delaunay) delays flips during repairs
+    pub fn triangulate(
+        &mut self,
+        mut previous: Triangulation,
+        points: &[Point],
+        vertices: &[Vertex],
+        hysteresis: f64,
+    ) -> Triangulation {
+        if self.repair(&mut previous, points, vertices, hysteresis) {
+            self.repaired_passes += 1;
+            return previous;
+        }
+        let result = delaunator::triangulate(points);
+        self.full_passes += 1;
+        self.sources.clear();
+        self.sources.extend(vertices.iter().map(|v| v.source));
+        self.checked.clear();
+        self.checked.extend_from_slice(points);
+        self.orientation = match result.triangles.get(..3) {
+            Some(&[a, b, c]) => orient(&points[a], &points[b], &points[c]).signum(),
+            _ => 0.0,
+        };
+        result
+    }
+
+    /// Bring the previous mesh up to date with local flips; false when a full pass
+    /// is needed (the mesh is then left half-repaired and replaced)
+    fn repair(
+        &mut self,
+        mesh: &mut Triangulation,
+        points: &[Point],
+        vertices: &[Vertex],
+        hysteresis: f64,
+    ) -> bool {
+        let same_layout = self.orientation != 0.0
+            && !mesh.triangles.is_empty()
+            && self.sources.len() == vertices.len()
+            && self.sources.iter().zip(vertices).all(|(&s, v)| s == v.source);
+        if !same_layout {
+            return false;
+        }
+
+        let epsilon = (self.epsilon * self.pixel_ratio) as f64;
+        let offsets = points.iter().zip(&self.checked).map(|(p, c)| (p.x - c.x).hypot(p.y - c.y));
+        self.changed.clear();
+        self.moved.clear();
+        for offset in offsets {
+            self.changed.push(offset > 0.0);
+            self.moved.push(offset > epsilon);
+        }
+
+        // Triangles around any vertex that moved must keep their orientation (slivers
+        // can invert below epsilon); only edges around vertices past epsilon are
+        // re-checked for the Delaunay condition
+        self.stack.clear();
+        for (t, tri) in mesh.triangles.chunks_exact(3).enumerate() {
+            if !tri.iter().any(|&v| self.changed[v]) {
+                continue;
+            }
+            let area = orient(&points[tri[0]], &points[tri[1]], &points[tri[2]]);
+            if area * self.orientation <= 0.0 {
+                return false;
+            }
+            if tri.iter().any(|&v| self.moved[v]) {
+                self.stack.extend([t * 3, t * 3 + 1, t * 3 + 2]);
+            }
+        }
+        if !self.hull_convex(mesh, points) {
+            return false;
+        }
+
+        let triangles = mesh.triangles.len() / 3;
+        let mut budget = (triangles as f32 * INCREMENTAL_MAX_FLIP_FRACTION) as usize;
+        while let Some(a) = self.stack.pop() {
+            let b = mesh.halfedges[a];
+            if b == EMPTY || !self.illegal(mesh, points, a, b, hysteresis) {
+                continue;
+            }
+            if budget == 0 {
+                return false;
+            }
+            budget -= 1;
+            flip(mesh, a, b);
+            self.stack.extend([a, next_halfedge(a), b, next_halfedge(b)]);
+        }
+
+        for (c, (p, &moved)) in self.checked.iter_mut().zip(points.iter().zip(&self.moved)) {
+            if moved {
+                *c = p.clone();
+            }
+        }
+        true
+    }
+
+    /// Whether the hull still turns the same way at every changed hull vertex (ghosts
+    /// can lie outside the corners, so the hull is not fixed)
+    fn hull_convex(&self, mesh: &Triangulation, points: &[Point]) -> bool {
+        let (t, h) = (&mesh.triangles, &mesh.halfedges);
+        (0..t.len()).filter(|&e| h[e] == EMPTY).all(|e| {
+            let (start, end) = (t[e], t[next_halfedge(e)]);
+            // Circle around `end` to the hull halfedge leaving it
+            let mut out = next_halfedge(e);
+            while h[out] != EMPTY {
+                out = next_halfedge(h[out]);
+            }
+            let after = t[next_halfedge(out)];
+            !(self.changed[start] || self.changed[end] || self.changed[after])
+                || orient(&points[start], &points[end], &points[after]) * self.orientation > 0.0
+        })
+    }
+
+    /// Whether the vertex across halfedge `a` (twin `b`) lies inside the circumcircle
+    /// of `a`'s triangle; with a `hysteresis` margin, whether the two angles facing the
+    /// edge sum to more than pi plus the margin
+    fn illegal(
+        &self,
+        mesh: &Triangulation,
+        points: &[Point],
+        a: usize,
+        b: usize,
+        hysteresis: f64,
+    ) -> bool {
+        let t = &mesh.triangles;
+        let (start, end, opposite) = (t[a], t[next_halfedge(a)], t[prev_halfedge(a)]);
+        let across = t[prev_halfedge(b)];
+        let (start, end) = (&points[start], &points[end]);
+        if hysteresis > 0.0 {
+            let facing = angle(&points[opposite], start, end) + angle(&points[across], start, end);
+            return facing > std::f64::consts::PI + hysteresis;
+        }
+        in_circle(start, end, &points[opposite], &points[across]) * self.orientation > 0.0
+    }
+}
+
+/// Replace the edge shared by halfedges `a` and `b` with the other diagonal of their quad
+fn flip(mesh: &mut Triangulation, a: usize, b: usize) {
+    let (ar, bl) = (prev_halfedge(a), prev_halfedge(b));
+    let (opposite_a, opposite_b) = (mesh.triangles[ar], mesh.triangles[bl]);
+    mesh.triangles[a] = opposite_b;
+    mesh.triangles[b] = opposite_a;
+
+    let (outer_bl, outer_ar) = (mesh.halfedges[bl], mesh.halfedges[ar]);
+    link(mesh, a, outer_bl);
+    link(mesh, b, outer_ar);
+    link(mesh, ar, bl);
+}
+
+fn link(mesh: &mut Triangulation, a: usize, b: usize) {
+    mesh.halfedges[a] = b;
+    if b != EMPTY {
+        mesh.halfedges[b] = a;
+    }
+}
+
+/// Twice the signed area of triangle abc
+fn orient(a: &Point, b: &Point, c: &Point) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Angle at `apex` between the directions to `a` and `b`
+fn angle(apex: &Point, a: &Point, b: &Point) -> f64 {
+    let (ux, uy, vx, vy) = (a.x - apex.x, a.y - apex.y, b.x - apex.x, b.y - apex.y);
+    (ux * vy - uy * vx).abs().atan2(ux * vx + uy * vy)
+}
+
+/// Positive when `d` lies inside the circumcircle of abc for positive `orient(a, b, c)`
+/// (sign flips with the orientation)
+fn in_circle(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+    (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+        - (bdx * bdx + bdy * bdy) * (adx * cdy - cdx * ady)
+        + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady)
+}
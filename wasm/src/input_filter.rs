@@ -0,0 +1,94 @@
+//! Pointer input smoothing (one-euro filter) to remove touch jitter
+//! See Casiez et al., "1€ Filter: A Simple Speed-based Low-pass Filter for Noisy Input"
+
+use std::f32::consts::PI;
+
+use crate::constants::{FRAMES_PER_SECOND, INPUT_FILTER_DERIVATIVE_CUTOFF};
+
+/// Exponential low-pass filter stage
+#[derive(Clone, Copy)]
+struct LowPass {
+    value: f32,
+}
+
+impl LowPass {
+    #[inline]
+    fn apply(&mut self, x: f32, alpha: f32) -> f32 {
+        self.value += alpha * (x - self.value);
+        self.value
+    }
+}
+
+/// Smoothing factor for a cutoff frequency (Hz) at a sample interval (seconds)
+#[inline]
+fn alpha(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1.0 / (2.0 * PI * cutoff);
+    1.0 / (1.0 + tau / dt)
+}
+
+/// One-euro filter for a single axis
+#[derive(Clone, Copy)]
+struct OneEuroAxis {
+    position: LowPass,
+    derivative: LowPass,
+}
+
+impl OneEuroAxis {
+    fn new(x: f32) -> Self {
+        Self {
+            position: LowPass { value: x },
+            derivative: LowPass { value: 0.0 },
+        }
+    }
+
+    fn apply(&mut self, x: f32, dt: f32, min_cutoff: f32, beta: f32) -> f32 {
+        let raw_derivative = (x - self.position.value) / dt;
+        let derivative = self
+            .derivative
+            .apply(raw_derivative, alpha(INPUT_FILTER_DERIVATIVE_CUTOFF, dt));
+        // Faster motion raises the cutoff: less lag when moving, less jitter when still
+        let cutoff = min_cutoff + beta * derivative.abs();
+        self.position.apply(x, alpha(cutoff, dt))
+    }
+}
+
+/// Two-axis one-euro filter applied to pointer positions
+#[derive(Clone)]
+pub struct PointerFilter {
+    /// Minimum cutoff frequency in Hz (lower = smoother when still)
+    min_cutoff: f32,
+    /// Speed coefficient (higher = less lag during fast motion)
+    beta: f32,
+    axes: Option<(OneEuroAxis, OneEuroAxis)>,
+}
+
+impl PointerFilter {
+    pub fn new(min_cutoff: f32, beta: f32) -> Self {
+        Self {
+            min_cutoff,
+            beta,
+            axes: None,
+        }
+    }
+
+    /// Filter a pointer sample; `delta_time` is in frames
+    /// The filter restarts whenever the pointer leaves the canvas
+    pub fn apply(&mut self, x: f32, y: f32, in_canvas: bool, delta_time: f32) -> (f32, f32) {
+        if !in_canvas {
+            self.axes = None;
+            return (x, y);
+        }
+
+        let dt = delta_time.max(0.01) / FRAMES_PER_SECOND;
+        match self.axes {
+            Some((ref mut ax, ref mut ay)) => (
+                ax.apply(x, dt, self.min_cutoff, self.beta),
+                ay.apply(y, dt, self.min_cutoff, self.beta),
+            ),
+            None => {
+                self.axes = Some((OneEuroAxis::new(x), OneEuroAxis::new(y)));
+                (x, y)
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Helpers for reading and writing plain JS objects via Reflect
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::fields::FieldSource;
+
+/// Set a property on an object
+pub fn set(obj: &Object, key: &str, value: JsValue) {
+    // Setting a plain data property on a fresh object cannot fail
+    let _ = Reflect::set(obj, &JsValue::from_str(key), &value);
+}
+
+/// Get a property (undefined if missing or not an object)
+pub fn get(obj: &JsValue, key: &str) -> JsValue {
+    Reflect::get(obj, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+impl FieldSource for JsValue {
+    fn field(&self, key: &str) -> Option<Self> {
+        Some(get(self, key)).filter(|value| !value.is_undefined())
+    }
+
+    fn is_object(&self) -> bool {
+        JsValue::is_object(self)
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        JsValue::as_f64(self)
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        JsValue::as_bool(self)
+    }
+
+    fn as_string(&self) -> Option<String> {
+        JsValue::as_string(self)
+    }
+}
+
+/// Build a JS array of strings
+pub fn string_array(items: &[&str]) -> JsValue {
+    items
+        .iter()
+        .map(|s| JsValue::from_str(s))
+        .collect::<js_sys::Array>()
+        .into()
+}
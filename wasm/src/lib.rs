@@ -1,16 +1,60 @@
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "alloc-audit")]
+mod alloc_audit;
+mod anchor;
+mod audio;
+mod boundary;
+mod capabilities;
 mod constants;
+mod constellation;
+mod density;
+mod depth;
+mod distribution;
 mod effects;
+mod environment;
+mod exclusion;
+mod fields;
+mod flip;
+mod flow_field;
+mod handoff;
+mod heat;
+mod idle;
+mod incremental;
+mod input_filter;
+mod interop;
+mod maintenance;
+mod memory;
+mod metrics;
 mod noise;
+mod normal_map;
+mod obstacle;
+mod parallel;
 mod physics;
 mod point;
+mod pointer_events;
+mod preset;
+#[cfg(feature = "cli")]
+mod preset_json;
+mod protocol;
+mod quality;
+mod quiescence;
+mod recording;
 mod rng;
+mod schedule;
+mod settle;
 mod simulation;
 mod spatial_grid;
+mod sticky;
 mod triangulation;
+mod turbulence;
+mod voronoi;
+mod watchdog;
 
+pub use capabilities::get_capabilities;
 pub use simulation::Simulation;
+#[cfg(all(feature = "threads", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
 
 /// Initialize panic hook for better error messages in development
 #[wasm_bindgen(start)]
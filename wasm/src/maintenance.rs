@@ -0,0 +1,43 @@
+//! Long-running maintenance for kiosk-style deployments: at every interval a short,
+//! very gentle pass folds accumulated error out of rest positions and relaxes crowded
+//! cells back toward uniform density
+
+use crate::constants::MAINTENANCE_WINDOW_FRAMES;
+
+/// Maintenance clock
+#[derive(Clone)]
+pub struct Maintenance {
+    /// Frames between passes
+    interval: f32,
+    /// Frames since the last pass started
+    elapsed: f32,
+    /// Frames left in the current relaxation window (0 = idle)
+    remaining: f32,
+}
+
+impl Maintenance {
+    pub fn new(interval_frames: f32) -> Self {
+        Self {
+            interval: interval_frames.max(MAINTENANCE_WINDOW_FRAMES),
+            elapsed: 0.0,
+            remaining: 0.0,
+        }
+    }
+
+    /// Advance by `delta_time` frames; returns true on the step a pass starts
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        self.remaining = (self.remaining - delta_time).max(0.0);
+        self.elapsed += delta_time;
+        if self.elapsed < self.interval {
+            return false;
+        }
+        self.elapsed = 0.0;
+        self.remaining = MAINTENANCE_WINDOW_FRAMES;
+        true
+    }
+
+    /// Whether the relaxation window of a pass is running
+    pub fn relaxing(&self) -> bool {
+        self.remaining > 0.0
+    }
+}
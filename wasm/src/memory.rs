@@ -0,0 +1,48 @@
+//! Memory generation: a counter bumped whenever a buffer handed out as a typed-array view
+//! may have moved (the buffer reallocated, or WASM memory grew and detached every view),
+//! so the renderer knows to re-acquire its views
+
+/// Buffer addresses and memory size at the last check
+#[derive(Clone)]
+pub struct MemoryGeneration {
+    generation: u32,
+    addresses: Vec<usize>,
+    pages: usize,
+}
+
+impl MemoryGeneration {
+    pub fn new() -> Self {
+        Self {
+            generation: 0,
+            addresses: Vec::new(),
+            pages: memory_pages(),
+        }
+    }
+
+    /// Compare `addresses` (start of every view-backed buffer) and the memory size with
+    /// the last check; returns the generation, bumped on any change
+    pub fn observe(&mut self, addresses: &[usize]) -> u32 {
+        let pages = memory_pages();
+        if pages != self.pages || self.addresses != addresses {
+            if !self.addresses.is_empty() {
+                self.generation = self.generation.wrapping_add(1);
+            }
+            self.pages = pages;
+            self.addresses.clear();
+            self.addresses.extend_from_slice(addresses);
+        }
+        self.generation
+    }
+}
+
+/// WASM memory size in 64 KiB pages (0 on other targets, where views do not exist)
+fn memory_pages() -> usize {
+    #[cfg(target_arch = "wasm32")]
+    {
+        core::arch::wasm32::memory_size::<0>()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0
+    }
+}
@@ -0,0 +1,97 @@
+//! Interaction metrics: where on the canvas interactions move points, as impulse per
+//! region per second over a rolling window, so hosts can see where users actually play
+//!
+//! Impulses are binned on a fixed grid normalized to the canvas (so resizes keep the
+//! history) into one slot per second of simulated time; the oldest slot is reused
+
+use crate::constants::{FRAMES_PER_SECOND, INTERACTION_METRICS_GRID};
+use crate::point::Point;
+
+const CELLS: usize = INTERACTION_METRICS_GRID * INTERACTION_METRICS_GRID;
+
+/// Ring buffer of per-second impulse grids
+#[derive(Clone)]
+pub struct InteractionMetrics {
+    /// Summed impulse (CSS pixels per frame of displacement velocity) per cell, per second
+    slots: Vec<[f32; CELLS]>,
+    /// Slot being filled
+    current: usize,
+    /// Slots holding data (the current one included)
+    filled: usize,
+    /// Seconds into the current slot
+    elapsed: f32,
+    /// Displacement velocities before the interaction forces (reused each step)
+    before: Vec<(f32, f32)>,
+}
+
+impl InteractionMetrics {
+    pub fn new(window_seconds: usize) -> Self {
+        Self {
+            slots: vec![[0.0; CELLS]; window_seconds.max(1)],
+            current: 0,
+            filled: 1,
+            elapsed: 0.0,
+            before: Vec::new(),
+        }
+    }
+
+    /// Remember displacement velocities ahead of the interaction forces
+    pub fn begin(&mut self, points: &[Point]) {
+        self.before.clear();
+        self.before.extend(points.iter().map(|p| (p.dx, p.dy)));
+    }
+
+    /// Bin each point's velocity change since `begin` at its position, then advance
+    /// the clock by `delta_time` frames
+    pub fn end(
+        &mut self,
+        points: &[Point],
+        width: f32,
+        height: f32,
+        pixel_ratio: f32,
+        delta_time: f32,
+    ) {
+        let slot = &mut self.slots[self.current];
+        let n = INTERACTION_METRICS_GRID;
+        for (point, &(dx, dy)) in points.iter().zip(&self.before) {
+            let impulse = (point.dx - dx).hypot(point.dy - dy) / pixel_ratio;
+            if impulse <= 0.0 || !impulse.is_finite() {
+                continue;
+            }
+            let col = ((point.x / width * n as f32).max(0.0) as usize).min(n - 1);
+            let row = ((point.y / height * n as f32).max(0.0) as usize).min(n - 1);
+            slot[row * n + col] += impulse;
+        }
+
+        self.elapsed += delta_time / FRAMES_PER_SECOND;
+        while self.elapsed >= 1.0 {
+            self.elapsed -= 1.0;
+            self.current = (self.current + 1) % self.slots.len();
+            self.slots[self.current] = [0.0; CELLS];
+            self.filled = (self.filled + 1).min(self.slots.len());
+        }
+    }
+
+    /// Average impulse per second over the window, summed into `cols` x `rows` regions
+    /// (rows top to bottom; each grid cell counts toward the region holding its center)
+    pub fn heatmap(&self, cols: usize, rows: usize) -> Vec<f32> {
+        let mut regions = vec![0.0; cols * rows];
+        let seconds = (self.filled - 1) as f32 + self.elapsed;
+        if seconds <= 0.0 {
+            return regions;
+        }
+        let n = INTERACTION_METRICS_GRID;
+        for slot in &self.slots {
+            for (cell, &impulse) in slot.iter().enumerate() {
+                let (col, row) = (cell % n, cell / n);
+                let region_col = (col * 2 + 1) * cols / (n * 2);
+                let region_row = (row * 2 + 1) * rows / (n * 2);
+                regions[region_row * cols + region_col] += impulse;
+            }
+        }
+        for value in &mut regions {
+            *value /= seconds;
+        }
+        regions
+    }
+}
@@ -1,5 +1,5 @@
-/// Simplex noise implementation optimized for WASM
-/// Based on Stefan Gustavson's simplex noise algorithm
+//! Simplex noise implementation optimized for WASM
+//! Based on Stefan Gustavson's simplex noise algorithm
 
 const F3: f32 = 1.0 / 3.0;
 const G3: f32 = 1.0 / 6.0;
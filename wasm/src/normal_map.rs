@@ -0,0 +1,108 @@
+//! Normal map of the mesh's height field for lighting other page elements
+//! Heights are interpolated across each triangle, normals come from central differences
+
+use crate::constants::NORMAL_MAP_RELIEF;
+use crate::triangulation::Vertex;
+
+/// Cached RGB normal map ([r, g, b] per texel, rows top to bottom)
+/// R = x right, G = y up, B = out of the screen, each mapped from [-1, 1] to 0..255
+#[derive(Clone)]
+pub struct NormalMap {
+    cols: usize,
+    rows: usize,
+    /// Frame of the last rebuild (None = never built)
+    built_frame: Option<u32>,
+    heights: Vec<f32>,
+    pub pixels: Vec<u8>,
+}
+
+impl NormalMap {
+    pub fn new() -> Self {
+        Self {
+            cols: 0,
+            rows: 0,
+            built_frame: None,
+            heights: Vec::new(),
+            pixels: Vec::new(),
+        }
+    }
+
+    /// Whether the map must be rebuilt for this size at `frame`
+    pub fn stale(&self, cols: usize, rows: usize, frame: u32, interval: u32) -> bool {
+        match self.built_frame {
+            Some(built) => {
+                cols != self.cols || rows != self.rows || frame.wrapping_sub(built) >= interval
+            }
+            None => true,
+        }
+    }
+
+    /// Rasterize the triangulation (canvas pixels) into a cols x rows normal map
+    #[allow(clippy::too_many_arguments)]
+    pub fn rebuild(
+        &mut self,
+        vertices: &[Vertex],
+        triangles: &[usize],
+        cols: usize,
+        rows: usize,
+        width: f32,
+        height: f32,
+        height_intensity: f32,
+        frame: u32,
+    ) {
+        self.cols = cols;
+        self.rows = rows;
+        self.built_frame = Some(frame);
+        self.heights.clear();
+        self.heights.resize(cols * rows, 0.0);
+
+        // Sample heights at texel centers by barycentric interpolation
+        let (cell_w, cell_h) = (width / cols as f32, height / rows as f32);
+        for t in triangles.chunks_exact(3) {
+            let [a, b, c] = [vertices[t[0]], vertices[t[1]], vertices[t[2]]];
+            let area = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+            if area.abs() < 1e-6 {
+                continue;
+            }
+
+            let texel = |v: f32, size: f32, count: usize| {
+                ((v / size - 0.5).max(0.0) as usize).min(count - 1)
+            };
+            let col_range = texel(a.x.min(b.x).min(c.x), cell_w, cols)
+                ..=texel(a.x.max(b.x).max(c.x) + cell_w, cell_w, cols);
+            let row_range = texel(a.y.min(b.y).min(c.y), cell_h, rows)
+                ..=texel(a.y.max(b.y).max(c.y) + cell_h, cell_h, rows);
+
+            for row in row_range {
+                let y = (row as f32 + 0.5) * cell_h;
+                for col in col_range.clone() {
+                    let x = (col as f32 + 0.5) * cell_w;
+                    let wa = ((b.x - x) * (c.y - y) - (c.x - x) * (b.y - y)) / area;
+                    let wb = ((c.x - x) * (a.y - y) - (a.x - x) * (c.y - y)) / area;
+                    let wc = 1.0 - wa - wb;
+                    if wa >= 0.0 && wb >= 0.0 && wc >= 0.0 {
+                        self.heights[row * cols + col] = wa * a.z + wb * b.z + wc * c.z;
+                    }
+                }
+            }
+        }
+
+        // Gradients in units of height intensity per texel
+        let scale = NORMAL_MAP_RELIEF / height_intensity.max(1e-3);
+        let h = |col: usize, row: usize| self.heights[row * cols + col];
+        self.pixels.clear();
+        self.pixels.reserve(cols * rows * 3);
+        for row in 0..rows {
+            let (up, down) = (row.saturating_sub(1), (row + 1).min(rows - 1));
+            for col in 0..cols {
+                let (left, right) = (col.saturating_sub(1), (col + 1).min(cols - 1));
+                let nx = -(h(right, row) - h(left, row)) * 0.5 * scale;
+                let ny = (h(col, down) - h(col, up)) * 0.5 * scale;
+                let length = (nx * nx + ny * ny + 1.0).sqrt();
+                for n in [nx / length, ny / length, 1.0 / length] {
+                    self.pixels.push(((n * 0.5 + 0.5) * 255.0).round() as u8);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Circular obstacles: points are pushed out of them and covered triangles can be culled
+//! Keeps the mesh from crowding behind overlaid content such as a logo or avatar
+
+use crate::constants::MAX_OBSTACLES;
+use crate::point::Point;
+use crate::spatial_grid::SpatialGrid;
+
+/// Circle in canvas pixels
+#[derive(Clone, Copy)]
+pub struct Obstacle {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+}
+
+impl Obstacle {
+    #[inline]
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let dx = x - self.x;
+        let dy = y - self.y;
+        dx * dx + dy * dy < self.radius * self.radius
+    }
+}
+
+/// Registered obstacles; radii are stored in CSS pixels
+#[derive(Clone)]
+pub struct ObstacleSet {
+    obstacles: Vec<(u32, Obstacle)>,
+    next_id: u32,
+    /// Drop triangles whose centroid lies inside an obstacle
+    pub cull: bool,
+}
+
+impl ObstacleSet {
+    pub fn new() -> Self {
+        Self {
+            obstacles: Vec::new(),
+            next_id: 1,
+            cull: false,
+        }
+    }
+
+    /// Register a circle; returns its id, or 0 if invalid or MAX_OBSTACLES are registered
+    pub fn add(&mut self, x: f32, y: f32, radius: f32) -> u32 {
+        let valid = x.is_finite() && y.is_finite() && radius.is_finite() && radius > 0.0;
+        if !valid || self.obstacles.len() >= MAX_OBSTACLES {
+            return 0;
+        }
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        self.obstacles.push((id, Obstacle { x, y, radius }));
+        id
+    }
+
+    /// Remove an obstacle by id
+    pub fn remove(&mut self, id: u32) {
+        self.obstacles.retain(|(oid, _)| *oid != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.obstacles.clear();
+    }
+
+    /// Obstacles with radii in canvas pixels
+    pub fn resolved(&self, pixel_ratio: f32) -> impl Iterator<Item = Obstacle> + '_ {
+        self.obstacles.iter().map(move |&(_, o)| Obstacle {
+            radius: o.radius * pixel_ratio,
+            ..o
+        })
+    }
+
+    /// Move points inside an obstacle onto its rim, keeping displacement consistent
+    /// Points at the exact center are pushed straight up
+    pub fn push_out(&self, points: &mut [Point], pixel_ratio: f32, grid: &SpatialGrid) {
+        for obstacle in self.resolved(pixel_ratio) {
+            for point_idx in grid.query_radius(obstacle.x, obstacle.y, obstacle.radius) {
+                let point = &mut points[point_idx];
+                if !obstacle.contains(point.x, point.y) {
+                    continue;
+                }
+                let dx = point.x - obstacle.x;
+                let dy = point.y - obstacle.y;
+                let dist = (dx * dx + dy * dy).sqrt();
+                let (nx, ny) = if dist > 1e-6 {
+                    (dx / dist, dy / dist)
+                } else {
+                    (0.0, -1.0)
+                };
+                point.x = obstacle.x + nx * obstacle.radius;
+                point.y = obstacle.y + ny * obstacle.radius;
+                point.dx = point.x - point.base_x;
+                point.dy = point.y - point.base_y;
+            }
+        }
+    }
+}
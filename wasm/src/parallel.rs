@@ -0,0 +1,24 @@
+//! Optional data parallelism (feature "threads"): large passes are split into chunks
+//! run on the rayon pool, small ones and builds without the feature run inline
+//! On wasm the pool has to be started with `initThreadPool(n)` before the first tick
+
+#[cfg(feature = "threads")]
+use crate::constants::{PARALLEL_CHUNK_ITEMS, PARALLEL_MIN_ITEMS};
+
+/// Run `f` over consecutive chunks of `items` that hold whole units of `unit` elements,
+/// passing the index of each chunk's first unit; chunks run in parallel when the
+/// feature is enabled and there are at least PARALLEL_MIN_ITEMS units
+pub fn for_each_chunk<T: Send>(items: &mut [T], unit: usize, f: impl Fn(usize, &mut [T]) + Sync) {
+    debug_assert!(items.len().is_multiple_of(unit));
+    #[cfg(feature = "threads")]
+    if items.len() >= PARALLEL_MIN_ITEMS * unit {
+        use rayon::prelude::*;
+        let chunk = PARALLEL_CHUNK_ITEMS * unit;
+        items
+            .par_chunks_mut(chunk)
+            .enumerate()
+            .for_each(|(i, chunk)| f(i * PARALLEL_CHUNK_ITEMS, chunk));
+        return;
+    }
+    f(0, items);
+}
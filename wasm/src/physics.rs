@@ -1,41 +1,207 @@
-/// Physics calculations for point interactions
+//! Physics calculations for point interactions
+
+use std::f32::consts::FRAC_1_SQRT_2;
 
 use crate::constants::{
-    GRAVITY_WELL_MAX_RANGE, GRAVITY_WELL_MIN_DIST, MIN_DIST_SQ, SHOCKWAVE_WAVE_WIDTH,
+    COLLIDE_RESTITUTION, DEFAULT_MOUSE_STRENGTH, DENSITY_EQUALIZATION_RATE, GRAVITY_WELL_MAX_RANGE,
+    GRAVITY_WELL_MIN_DIST, MAGNETIZE_PULL, MAGNETIZE_SPACING, MIN_DIST_SQ, SHOCKWAVE_WAVE_WIDTH,
 };
+use crate::boundary::Boundary;
+use crate::depth::depth_factor;
 use crate::effects::{GravityWell, MouseMode, MouseState, Shockwave};
+use crate::parallel::for_each_chunk;
 use crate::point::Point;
 use crate::spatial_grid::SpatialGrid;
 
-/// Apply mouse influence to nearby points
-pub fn apply_mouse_influence(
+/// Height (z) band of points that react to interactions
+#[derive(Clone, Copy)]
+pub struct HeightMask {
+    pub min_z: f32,
+    pub max_z: f32,
+}
+
+impl HeightMask {
+    /// Mask letting every point react
+    pub const ALL: HeightMask = HeightMask {
+        min_z: f32::NEG_INFINITY,
+        max_z: f32::INFINITY,
+    };
+
+    #[inline]
+    pub fn allows(&self, point: &Point) -> bool {
+        point.z >= self.min_z && point.z <= self.max_z
+    }
+}
+
+/// Effect whose reach an `EffectFilter` narrows
+#[derive(Clone, Copy)]
+pub enum EffectTarget {
+    /// Mouse and touch pointers
+    Pointer,
+    GravityWell,
+    /// Shockwaves and implosions, including emitted and rain waves
+    Shockwave,
+}
+
+impl EffectTarget {
+    /// Convert from numeric value (for JS interop), None if unknown
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(EffectTarget::Pointer),
+            1 => Some(EffectTarget::GravityWell),
+            2 => Some(EffectTarget::Shockwave),
+            _ => None,
+        }
+    }
+}
+
+/// Points an effect moves, on top of the height mask shared by all effects
+#[derive(Clone, Copy)]
+pub struct EffectFilter {
+    /// Leave pinned and anchored points alone
+    pub ignore_pinned: bool,
+    /// Bit per point layer that reacts
+    pub layer_mask: u32,
+    /// Parallax depth band that reacts (inclusive)
+    pub depth_range: (f32, f32),
+}
+
+impl EffectFilter {
+    /// Filter letting every point react
+    pub const ALL: EffectFilter = EffectFilter {
+        ignore_pinned: false,
+        layer_mask: u32::MAX,
+        depth_range: (0.0, 1.0),
+    };
+
+    #[inline]
+    pub fn allows(&self, point: &Point) -> bool {
+        let held = point.pin.is_some() || point.anchored;
+        let (min_depth, max_depth) = self.depth_range;
+        !(self.ignore_pinned && held)
+            && self.layer_mask & (1 << point.layer) != 0
+            && point.depth >= min_depth
+            && point.depth <= max_depth
+    }
+}
+
+/// Height mask and an effect's filter, checked together by the force functions
+#[derive(Clone, Copy)]
+pub struct ForceFilter {
+    pub height: HeightMask,
+    pub effect: EffectFilter,
+}
+
+impl ForceFilter {
+    #[inline]
+    pub fn allows(&self, point: &Point) -> bool {
+        self.height.allows(point) && self.effect.allows(point)
+    }
+}
+
+/// Nearest node of a hex lattice with one node at the origin (relative coordinates)
+fn nearest_hex_node(x: f32, y: f32, spacing: f32) -> (f32, f32) {
+    // Lattice basis: (spacing, 0) and (spacing / 2, spacing * sqrt(3) / 2)
+    let row_height = spacing * 0.866_025_4;
+    let j = y / row_height;
+    let i = x / spacing - j * 0.5;
+
+    let mut best = (0.0, 0.0);
+    let mut best_dist_sq = f32::MAX;
+    for cj in [j.floor(), j.ceil()] {
+        for ci in [i.floor(), i.ceil()] {
+            let nx = (ci + cj * 0.5) * spacing;
+            let ny = cj * row_height;
+            let dist_sq = (nx - x) * (nx - x) + (ny - y) * (ny - y);
+            if dist_sq < best_dist_sq {
+                best_dist_sq = dist_sq;
+                best = (nx, ny);
+            }
+        }
+    }
+    best
+}
+
+/// Spring every free (unpinned) point back toward its rest position, see
+/// `Point::apply_spring`; the "threads" feature splits large sets across the pool
+pub fn apply_springs(points: &mut [Point], spring_back: f32, damping: f32) {
+    for_each_chunk(points, 1, |_, points| {
+        for point in points.iter_mut().filter(|p| p.pin.is_none()) {
+            point.apply_spring(spring_back, damping);
+        }
+    });
+}
+
+/// Apply the influence of every active pointer to nearby points
+/// impulse_scale: share of a frame's impulse applied (1 / substeps per frame)
+#[allow(clippy::too_many_arguments)]
+pub fn apply_mouse_influence<'a>(
     points: &mut [Point],
-    mouse: &MouseState,
+    pointers: impl IntoIterator<Item = &'a MouseState>,
     velocity_influence: f32,
+    depth_parallax: f32,
+    pixel_ratio: f32,
+    impulse_scale: f32,
+    filter: &ForceFilter,
     grid: &SpatialGrid,
 ) {
-    if !mouse.in_canvas {
-        return;
+    for mouse in pointers {
+        if mouse.in_canvas {
+            apply_pointer(
+                points,
+                mouse,
+                velocity_influence,
+                depth_parallax,
+                pixel_ratio,
+                impulse_scale,
+                filter,
+                grid,
+            );
+        }
     }
+}
 
-    let radius = mouse.radius;
+/// Apply one pointer's push/pull/swirl/magnetize force or disc collision
+#[allow(clippy::too_many_arguments)]
+fn apply_pointer(
+    points: &mut [Point],
+    mouse: &MouseState,
+    velocity_influence: f32,
+    depth_parallax: f32,
+    pixel_ratio: f32,
+    impulse_scale: f32,
+    filter: &ForceFilter,
+    grid: &SpatialGrid,
+) {
+    // Radius and strength are in CSS pixels, points in canvas pixels
+    let radius = mouse.radius * pixel_ratio;
     let radius_sq = radius * radius;
-    let strength = mouse.strength;
+    let strength = mouse.strength * pixel_ratio * impulse_scale;
+    let min_dist_sq = MIN_DIST_SQ * pixel_ratio * pixel_ratio;
 
-    // Velocity boost
-    let velocity_boost = 1.0 + mouse.speed() * velocity_influence;
+    // Velocity boost (pointer speed in CSS pixels)
+    let velocity_boost = 1.0 + mouse.speed() / pixel_ratio * velocity_influence;
 
-    // Query only nearby points
-    let nearby: Vec<usize> = grid.query_radius(mouse.x, mouse.y, radius).collect();
+    // Magnetize: lattice spacing and pull scale
+    let spacing = MAGNETIZE_SPACING * pixel_ratio;
+    let magnet_pull = MAGNETIZE_PULL * mouse.strength / DEFAULT_MOUSE_STRENGTH * impulse_scale;
 
-    for point_idx in nearby {
+    // Collide: share of the cursor's approach speed passed on
+    let restitution = COLLIDE_RESTITUTION * mouse.strength / DEFAULT_MOUSE_STRENGTH * impulse_scale;
+
+    // Query only nearby points
+    for point_idx in grid.query_radius(mouse.x, mouse.y, radius) {
         let point = &mut points[point_idx];
+        if !filter.allows(point) {
+            continue;
+        }
         let dx = point.x - mouse.x;
         let dy = point.y - mouse.y;
         let dist_sq = dx * dx + dy * dy;
 
-        // Early exit using squared distance
-        if dist_sq >= radius_sq || dist_sq < MIN_DIST_SQ {
+        // Early exit using squared distance (a solid disc must also clear its center)
+        let solid = mouse.mode == MouseMode::Collide;
+        if dist_sq >= radius_sq || (dist_sq < min_dist_sq && !solid) {
             continue;
         }
 
@@ -46,7 +212,11 @@ pub fn apply_mouse_influence(
         // Smoothstep falloff: t² × (3 - 2t)
         let falloff = t * t * (3.0 - 2.0 * t);
 
-        let push = strength * falloff * velocity_boost * 0.08;
+        let push = strength
+            * falloff
+            * velocity_boost
+            * depth_factor(point.depth, depth_parallax)
+            * 0.08;
         let inv_dist = 1.0 / dist;
 
         match mouse.mode {
@@ -76,22 +246,52 @@ pub fn apply_mouse_influence(
                 point.dx += nx * push * 0.2;
                 point.dy += ny * push * 0.2;
             }
+            MouseMode::Magnetize => {
+                // Ease toward the nearest lattice node (no velocity boost: stays ordered)
+                let (tx, ty) = nearest_hex_node(dx, dy, spacing);
+                let pull = (magnet_pull * falloff).min(1.0);
+                point.dx += (tx - dx) * pull;
+                point.dy += (ty - dy) * pull;
+            }
+            MouseMode::Collide => {
+                // Project onto the rim; points at the exact center leave straight up
+                let (nx, ny) = if dist > 1e-6 {
+                    (dx * inv_dist, dy * inv_dist)
+                } else {
+                    (0.0, -1.0)
+                };
+                // Pass on the cursor's approach speed so points are plowed ahead of it
+                let approach = (mouse.vx * nx + mouse.vy * ny).max(0.0) * restitution;
+                let (rim_x, rim_y) = (mouse.x + nx * radius, mouse.y + ny * radius);
+                point.dx += rim_x - point.x + nx * approach;
+                point.dy += rim_y - point.y + ny * approach;
+                point.x = point.base_x + point.dx;
+                point.y = point.base_y + point.dy;
+            }
         }
     }
 }
 
 /// Apply gravity well force to nearby points
-pub fn apply_gravity_well(points: &mut [Point], well: &GravityWell, grid: &SpatialGrid) {
-    let min_dist_sq = GRAVITY_WELL_MIN_DIST * GRAVITY_WELL_MIN_DIST;
-    let max_range_sq = GRAVITY_WELL_MAX_RANGE * GRAVITY_WELL_MAX_RANGE;
+pub fn apply_gravity_well(
+    points: &mut [Point],
+    well: &GravityWell,
+    pixel_ratio: f32,
+    impulse_scale: f32,
+    filter: &ForceFilter,
+    grid: &SpatialGrid,
+) {
+    let min_dist = GRAVITY_WELL_MIN_DIST * pixel_ratio;
+    let max_range = GRAVITY_WELL_MAX_RANGE * pixel_ratio;
+    let min_dist_sq = min_dist * min_dist;
+    let max_range_sq = max_range * max_range;
 
     // Query nearby points
-    let nearby: Vec<usize> = grid
-        .query_radius(well.x, well.y, GRAVITY_WELL_MAX_RANGE)
-        .collect();
-
-    for point_idx in nearby {
+    for point_idx in grid.query_radius(well.x, well.y, max_range) {
         let point = &mut points[point_idx];
+        if !filter.allows(point) {
+            continue;
+        }
         let dx = well.x - point.x;
         let dy = well.y - point.y;
         let dist_sq = dx * dx + dy * dy;
@@ -103,12 +303,13 @@ pub fn apply_gravity_well(points: &mut [Point], well: &GravityWell, grid: &Spati
 
         // Use squared distance for minimum check
         let dist = if dist_sq < min_dist_sq {
-            GRAVITY_WELL_MIN_DIST
+            min_dist
         } else {
             dist_sq.sqrt()
         };
 
-        let force = well.strength / (dist * 0.1);
+        // Falloff uses CSS-pixel distance, force is scaled back to canvas pixels
+        let force = well.strength / (dist / pixel_ratio * 0.1) * pixel_ratio * impulse_scale;
         let inv_dist = 1.0 / dist;
         let nx = dx * inv_dist;
         let ny = dy * inv_dist;
@@ -119,24 +320,34 @@ pub fn apply_gravity_well(points: &mut [Point], well: &GravityWell, grid: &Spati
 }
 
 /// Apply shockwave force to nearby points
-pub fn apply_shockwave(points: &mut [Point], wave: &Shockwave, grid: &SpatialGrid) {
+pub fn apply_shockwave(
+    points: &mut [Point],
+    wave: &Shockwave,
+    pixel_ratio: f32,
+    impulse_scale: f32,
+    filter: &ForceFilter,
+    grid: &SpatialGrid,
+) {
     // Pre-calculate bounds for early exit
-    let min_radius = (wave.radius - SHOCKWAVE_WAVE_WIDTH).max(0.0);
-    let max_radius = wave.radius + SHOCKWAVE_WAVE_WIDTH;
+    let wave_width = SHOCKWAVE_WAVE_WIDTH * pixel_ratio;
+    let min_radius = (wave.radius - wave_width).max(0.0);
+    let max_radius = wave.radius + wave_width;
     let min_radius_sq = min_radius * min_radius;
     let max_radius_sq = max_radius * max_radius;
+    let min_dist_sq = MIN_DIST_SQ * pixel_ratio * pixel_ratio;
 
     // Query nearby points
-    let nearby: Vec<usize> = grid.query_radius(wave.x, wave.y, max_radius).collect();
-
-    for point_idx in nearby {
+    for point_idx in grid.query_radius(wave.x, wave.y, max_radius) {
         let point = &mut points[point_idx];
+        if !filter.allows(point) {
+            continue;
+        }
         let dx = point.x - wave.x;
         let dy = point.y - wave.y;
         let dist_sq = dx * dx + dy * dy;
 
         // Early exit using squared distance bounds
-        if dist_sq < min_radius_sq || dist_sq > max_radius_sq || dist_sq < MIN_DIST_SQ {
+        if dist_sq < min_radius_sq || dist_sq > max_radius_sq || dist_sq < min_dist_sq {
             continue;
         }
 
@@ -144,11 +355,12 @@ pub fn apply_shockwave(points: &mut [Point], wave: &Shockwave, grid: &SpatialGri
         let dist = dist_sq.sqrt();
         let ring_dist = (dist - wave.radius).abs();
 
-        if ring_dist < SHOCKWAVE_WAVE_WIDTH {
-            let falloff = 1.0 - ring_dist / SHOCKWAVE_WAVE_WIDTH;
-            let push = wave.strength * falloff * 0.15;
+        if ring_dist < wave_width {
+            let falloff = 1.0 - ring_dist / wave_width;
+            let push = wave.strength * falloff * 0.15 * pixel_ratio * impulse_scale;
 
-            // Push outward from wave center
+            // Push outward from wave center (inward for an implosion)
+            let push = if wave.is_implosion() { -push } else { push };
             let inv_dist = 1.0 / dist;
             let nx = dx * inv_dist;
             let ny = dy * inv_dist;
@@ -157,3 +369,92 @@ pub fn apply_shockwave(points: &mut [Point], wave: &Shockwave, grid: &SpatialGri
         }
     }
 }
+
+/// Push rest positions out of overcrowded grid cells toward emptier neighbors
+pub fn apply_density_equalization(
+    points: &mut [Point],
+    grid: &SpatialGrid,
+    boundary: &Boundary,
+    strength: f32,
+    delta_time: f32,
+) {
+    let average = points.len() as f32 / grid.cell_count() as f32;
+    if average <= 0.0 {
+        return;
+    }
+
+    let step = strength * delta_time * grid.cell_size * DENSITY_EQUALIZATION_RATE;
+    let width = grid.width;
+    let height = grid.height;
+
+    for point in points.iter_mut() {
+        let (col, row) = grid.cell_coords(point.x, point.y);
+        let own = grid.cell_len(col, row) as f32;
+
+        // Only overcrowded cells push outward
+        if own <= average {
+            continue;
+        }
+
+        let mut fx = 0.0;
+        let mut fy = 0.0;
+        for dr in -1isize..=1 {
+            for dc in -1isize..=1 {
+                if dc == 0 && dr == 0 {
+                    continue;
+                }
+                let neighbor = grid.cell_len_wrapped(col as isize + dc, row as isize + dr) as f32;
+                let excess = (own - neighbor) / average;
+                if excess > 0.0 {
+                    // Normalize diagonal directions
+                    let weight = if dc != 0 && dr != 0 { FRAC_1_SQRT_2 } else { 1.0 };
+                    fx += dc as f32 * excess * weight;
+                    fy += dr as f32 * excess * weight;
+                }
+            }
+        }
+
+        point.base_x += fx * step;
+        point.base_y += fy * step;
+        boundary.wrap(point, width, height);
+    }
+}
+
+/// Springs along Delaunay edges between real points, so displacement spreads to
+/// neighbors like a membrane; rest length is the base-position edge length when the
+/// edge first appeared, so drift that stretches an edge is resisted too
+/// stiffness: fraction of edge stretch corrected per frame
+pub fn apply_mesh_springs(
+    points: &mut [Point],
+    edges: &mut [(usize, usize, f32)],
+    stiffness: f32,
+    boundary: &Boundary,
+    width: f32,
+    height: f32,
+    delta_time: f32,
+) {
+    let factor = (stiffness * delta_time).min(1.0);
+    for (i, j, rest) in edges {
+        // Edges may predate a point count change
+        let (Some(&a), Some(&b)) = (points.get(*i), points.get(*j)) else {
+            continue;
+        };
+        if rest.is_nan() {
+            let (rx, ry) =
+                boundary.shortest_offset(b.base_x - a.base_x, b.base_y - a.base_y, width, height);
+            *rest = (rx * rx + ry * ry).sqrt();
+        }
+        let (ex, ey) = boundary.shortest_offset(b.x - a.x, b.y - a.y, width, height);
+        let length = (ex * ex + ey * ey).sqrt();
+        if length < 1e-3 {
+            continue;
+        }
+
+        // Each endpoint takes half of the correction
+        let pull = (length - *rest) * factor * 0.5 / length;
+        points[*i].dx += ex * pull;
+        points[*i].dy += ey * pull;
+        points[*j].dx -= ex * pull;
+        points[*j].dy -= ey * pull;
+    }
+}
@@ -1,8 +1,9 @@
-/// Point structure representing a simulation particle
+//! Point structure representing a simulation particle
 
 use crate::noise::fbm3d;
 use crate::rng::Rng;
-use crate::constants::BASE_VELOCITY;
+use crate::constants::{BASE_VELOCITY, NEUTRAL_DEPTH};
+use crate::sticky::Attachment;
 
 /// A point with position, velocity, and displacement
 #[derive(Clone, Copy)]
@@ -25,22 +26,58 @@ pub struct Point {
     pub dx: f32,
     /// Displacement velocity Y (from interactions)
     pub dy: f32,
+    /// Parallax depth in [0, 1] (0 = far, 1 = near)
+    pub depth: f32,
+    /// Fixed position while pinned (None = free)
+    pub pin: Option<(f32, f32)>,
+    /// Attachment to the sticky cursor (None = free)
+    pub stuck: Option<Attachment>,
+    /// Held by an anchor point (see `Anchors`)
+    pub anchored: bool,
+    /// Layer 0..32 matched against effect filter layer masks
+    pub layer: u8,
 }
 
 impl Point {
     /// Create a new point with random position and velocity
+    /// Position and velocity come from separate streams so either can change independently
     pub fn new_random(
-        rng: &mut Rng,
+        placement: &mut Rng,
+        drift: &mut Rng,
         width: f32,
         height: f32,
         noise_scale: f32,
         height_intensity: f32,
+        light_shift: (f32, f32),
     ) -> Self {
-        let x = rng.next_f32() * width;
-        let y = rng.next_f32() * height;
-        let vx = (rng.next_f32() - 0.5) * BASE_VELOCITY * 2.0;
-        let vy = (rng.next_f32() - 0.5) * BASE_VELOCITY * 2.0;
-        let z = Self::calculate_height(x, y, width, height, noise_scale, height_intensity);
+        let x = placement.next_f32() * width;
+        let y = placement.next_f32() * height;
+        Self::new_at(x, y, drift, width, height, noise_scale, height_intensity, light_shift)
+    }
+
+    /// Create a new point at (x, y) with random drift velocity
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_at(
+        x: f32,
+        y: f32,
+        drift: &mut Rng,
+        width: f32,
+        height: f32,
+        noise_scale: f32,
+        height_intensity: f32,
+        light_shift: (f32, f32),
+    ) -> Self {
+        let vx = (drift.next_f32() - 0.5) * BASE_VELOCITY * 2.0;
+        let vy = (drift.next_f32() - 0.5) * BASE_VELOCITY * 2.0;
+        let z = Self::calculate_height(
+            x,
+            y,
+            width,
+            height,
+            noise_scale,
+            height_intensity,
+            light_shift,
+        );
 
         Self {
             x,
@@ -52,10 +89,16 @@ impl Point {
             vy,
             dx: 0.0,
             dy: 0.0,
+            depth: NEUTRAL_DEPTH,
+            pin: None,
+            stuck: None,
+            anchored: false,
+            layer: 0,
         }
     }
 
     /// Calculate static height from noise (called once at creation)
+    /// light_shift moves the falloff center by a fraction of the half canvas size
     pub fn calculate_height(
         x: f32,
         y: f32,
@@ -63,10 +106,11 @@ impl Point {
         height: f32,
         noise_scale: f32,
         intensity: f32,
+        light_shift: (f32, f32),
     ) -> f32 {
-        let cx = width / 2.0;
-        let cy = height / 2.0;
-        let max_dist = (cx * cx + cy * cy).sqrt();
+        let max_dist = (width * width + height * height).sqrt() / 2.0;
+        let cx = width / 2.0 * (1.0 + light_shift.0);
+        let cy = height / 2.0 * (1.0 + light_shift.1);
 
         // Sample noise
         let mut z = fbm3d(
@@ -98,6 +142,7 @@ impl Point {
         height: f32,
         noise_scale: f32,
         height_intensity: f32,
+        light_shift: (f32, f32),
     ) {
         self.z = Self::calculate_height(
             self.base_x,
@@ -106,22 +151,40 @@ impl Point {
             height,
             noise_scale,
             height_intensity,
+            light_shift,
         );
     }
 
     /// Update position with floating velocity and edge wrapping
     pub fn update_position(&mut self, delta_time: f32, speed: f32, width: f32, height: f32) {
-        // Apply floating velocity to base position
+        self.drift(delta_time, speed);
+        self.wrap_base(width, height);
+    }
+
+    /// Apply floating velocity to base position
+    pub fn drift(&mut self, delta_time: f32, speed: f32) {
         self.base_x += self.vx * speed * delta_time;
         self.base_y += self.vy * speed * delta_time;
+    }
+
+    /// Wrap base position around canvas edges
+    pub fn wrap_base(&mut self, width: f32, height: f32) {
+        self.wrap_base_x(width);
+        self.wrap_base_y(height);
+    }
 
-        // Wrap around edges
+    /// Wrap base position around the left/right edges
+    pub fn wrap_base_x(&mut self, width: f32) {
         if self.base_x < 0.0 {
             self.base_x += width;
         }
         if self.base_x > width {
             self.base_x -= width;
         }
+    }
+
+    /// Wrap base position around the top/bottom edges
+    pub fn wrap_base_y(&mut self, height: f32) {
         if self.base_y < 0.0 {
             self.base_y += height;
         }
@@ -130,6 +193,43 @@ impl Point {
         }
     }
 
+    /// Pin the point at its current position
+    pub fn pin_here(&mut self) {
+        self.pin = Some((self.x, self.y));
+        self.hold();
+    }
+
+    /// Snap a pinned point back to its pin, discarding drift and displacement
+    pub fn hold(&mut self) {
+        if let Some((x, y)) = self.pin {
+            self.x = x;
+            self.y = y;
+            self.base_x = x;
+            self.base_y = y;
+            self.dx = 0.0;
+            self.dy = 0.0;
+        }
+    }
+
+    /// Detach from the sticky cursor, the spring then pulls the point back home
+    pub fn release(&mut self) {
+        if let Some(a) = self.stuck.take() {
+            self.base_x = a.home_x;
+            self.base_y = a.home_y;
+        }
+    }
+
+    /// Move the point to a new rest position, discarding any displacement
+    pub fn respawn_at(&mut self, x: f32, y: f32) {
+        self.stuck = None;
+        self.x = x;
+        self.y = y;
+        self.base_x = x;
+        self.base_y = y;
+        self.dx = 0.0;
+        self.dy = 0.0;
+    }
+
     /// Apply spring physics to return to base position
     pub fn apply_spring(&mut self, spring_back: f32, damping: f32) {
         // Pull displacement back to zero
@@ -151,5 +251,11 @@ impl Point {
         self.y *= scale_y;
         self.base_x *= scale_x;
         self.base_y *= scale_y;
+        if let Some((x, y)) = self.pin {
+            self.pin = Some((x * scale_x, y * scale_y));
+        }
+        if let Some(ref mut a) = self.stuck {
+            a.scale(scale_x, scale_y);
+        }
     }
 }
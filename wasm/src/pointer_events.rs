@@ -0,0 +1,82 @@
+//! Raw pointer events queued between ticks (high-rate mice, pens, touch)
+//! A tick sweeps the whole sub-frame path and measures velocity from timestamps
+
+use crate::constants::{FRAMES_PER_SECOND, MAX_QUEUED_POINTER_EVENTS, POINTER_EVENT_STALE_MS};
+
+/// One raw pointer sample as reported by the host
+#[derive(Clone, Copy)]
+pub struct PointerEvent {
+    pub x: f32,
+    pub y: f32,
+    /// Host timestamp in milliseconds (e.g. `PointerEvent.timeStamp`)
+    pub t: f64,
+    pub pressure: f32,
+    pub buttons: u32,
+}
+
+impl PointerEvent {
+    /// Strength multiplier: pressed pens scale with pressure, 0.5 (the default for a
+    /// pressed mouse button) and hovering pointers keep full strength
+    pub fn strength_factor(&self) -> f32 {
+        if self.buttons != 0 && self.pressure > 0.0 {
+            (self.pressure * 2.0).min(2.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Events pushed since the last tick plus the last sample of the previous batch
+#[derive(Clone)]
+pub struct PointerEventQueue {
+    events: Vec<PointerEvent>,
+    last: Option<PointerEvent>,
+}
+
+impl PointerEventQueue {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            last: None,
+        }
+    }
+
+    /// Queue an event, dropping the oldest when full; non-finite samples are ignored
+    pub fn push(&mut self, event: PointerEvent) {
+        if !(event.x.is_finite() && event.y.is_finite() && event.t.is_finite()) {
+            return;
+        }
+        if self.events.len() >= MAX_QUEUED_POINTER_EVENTS {
+            self.events.remove(0);
+        }
+        self.events.push(event);
+    }
+
+    /// Queued events in arrival order
+    pub fn events(&self) -> &[PointerEvent] {
+        &self.events
+    }
+
+    /// Velocity in pixels per frame across the queued events, measured from the
+    /// previous batch's last sample when it is recent; None without a usable time span
+    pub fn velocity(&self) -> Option<(f32, f32)> {
+        let end = self.events.last()?;
+        let start = match self.last {
+            Some(last) if end.t - last.t <= POINTER_EVENT_STALE_MS => last,
+            _ => self.events[0],
+        };
+        let frames = ((end.t - start.t) / 1000.0) as f32 * FRAMES_PER_SECOND;
+        if frames <= 0.0 {
+            return None;
+        }
+        Some(((end.x - start.x) / frames, (end.y - start.y) / frames))
+    }
+
+    /// Forget the queued events, keeping the newest as the next velocity baseline
+    pub fn finish(&mut self) {
+        if let Some(&end) = self.events.last() {
+            self.last = Some(end);
+        }
+        self.events.clear();
+    }
+}
@@ -0,0 +1,415 @@
+//! Savable presets of tunable parameters (no point state)
+//! Presets round-trip through plain JS objects so hosts can store them as JSON
+
+use js_sys::Object;
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::boundary::{BoundaryMode, FlowDirection};
+use crate::constants::{
+    DEFAULT_DENSITY_EQUALIZATION_STRENGTH, DEFAULT_MOUSE_RADIUS, DEFAULT_MOUSE_STRENGTH,
+    DEFAULT_POINT_COUNT, MAX_POINT_COUNT, MIN_POINT_COUNT,
+};
+use crate::depth::DepthMode;
+use crate::fields::{group, read_bool, read_f32, read_string, read_u32, FieldSource};
+use crate::interop::set;
+
+/// Current preset format version
+pub const PRESET_VERSION: u32 = 1;
+
+/// Point count derived from the canvas area, so a preset keeps the same level of
+/// detail on a phone and a 4K monitor
+#[derive(Clone, Copy, PartialEq)]
+pub struct PointBudget {
+    /// Points per CSS megapixel (0 = off, the point count stays as set)
+    pub per_megapixel: f32,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl PointBudget {
+    pub const OFF: PointBudget = PointBudget {
+        per_megapixel: 0.0,
+        min: MIN_POINT_COUNT as u32,
+        max: MAX_POINT_COUNT as u32,
+    };
+
+    pub fn is_enabled(&self) -> bool {
+        self.per_megapixel > 0.0
+    }
+
+    /// Point count for a canvas covering `css_area` square CSS pixels
+    pub fn count(&self, css_area: f32) -> usize {
+        let (min, max) = (self.min.min(self.max), self.min.max(self.max));
+        let count = (self.per_megapixel * css_area / 1_000_000.0).round() as u32;
+        count.clamp(min, max) as usize
+    }
+}
+
+/// Snapshot of all tunable simulation parameters
+#[derive(Clone)]
+pub struct Preset {
+    pub name: String,
+
+    // Physics
+    pub spring_back: f32,
+    pub damping: f32,
+    pub velocity_influence: f32,
+
+    // Noise
+    pub noise_scale: f32,
+    pub height_intensity: f32,
+
+    // Mouse dynamics
+    pub mouse_velocity_smoothing: f32,
+    pub mouse_off_canvas_decay: f32,
+    pub mouse_raw_velocity: bool,
+
+    // Density equalization
+    pub density_equalization_enabled: bool,
+    pub density_equalization_strength: f32,
+
+    // Boundary
+    pub boundary_mode: BoundaryMode,
+    pub flow_direction: FlowDirection,
+    pub flow_speed: f32,
+    pub turbulence: f32,
+
+    // Parallax depth
+    pub depth_mode: DepthMode,
+    pub depth_parallax: f32,
+
+    // Area-based point count
+    pub point_budget: PointBudget,
+}
+
+impl Preset {
+    /// Convert to a versioned JS object grouped like the TypeScript options
+    pub fn to_js(&self) -> JsValue {
+        let root = Object::new();
+        set(&root, "version", PRESET_VERSION.into());
+        set(&root, "name", self.name.as_str().into());
+
+        let physics = Object::new();
+        set(&physics, "springBack", self.spring_back.into());
+        set(&physics, "damping", self.damping.into());
+        set(&physics, "velocityInfluence", self.velocity_influence.into());
+        set(&root, "physics", physics.into());
+
+        let noise = Object::new();
+        set(&noise, "scale", self.noise_scale.into());
+        set(&noise, "heightIntensity", self.height_intensity.into());
+        set(&root, "noise", noise.into());
+
+        let mouse = Object::new();
+        set(&mouse, "velocitySmoothing", self.mouse_velocity_smoothing.into());
+        set(&mouse, "offCanvasDecay", self.mouse_off_canvas_decay.into());
+        set(&mouse, "rawVelocity", self.mouse_raw_velocity.into());
+        set(&root, "mouse", mouse.into());
+
+        let density = Object::new();
+        set(&density, "enabled", self.density_equalization_enabled.into());
+        set(&density, "strength", self.density_equalization_strength.into());
+        set(&root, "densityEqualization", density.into());
+
+        let boundary = Object::new();
+        set(&boundary, "mode", self.boundary_mode.to_u32().into());
+        set(&boundary, "direction", self.flow_direction.to_u32().into());
+        set(&boundary, "flowSpeed", self.flow_speed.into());
+        set(&boundary, "turbulence", self.turbulence.into());
+        set(&root, "boundary", boundary.into());
+
+        let depth = Object::new();
+        set(&depth, "mode", self.depth_mode.to_u32().into());
+        set(&depth, "parallax", self.depth_parallax.into());
+        set(&root, "depth", depth.into());
+
+        let budget = Object::new();
+        set(&budget, "perMegapixel", self.point_budget.per_megapixel.into());
+        set(&budget, "min", self.point_budget.min.into());
+        set(&budget, "max", self.point_budget.max.into());
+        set(&root, "pointBudget", budget.into());
+
+        root.into()
+    }
+
+    /// Parse a versioned preset object on top of `base`
+    /// Missing fields keep the base value; invalid fields fail the whole import
+    pub fn parse<S: FieldSource>(value: &S, base: &Preset) -> Result<Preset, String> {
+        if !value.is_object() {
+            return Err("preset must be an object".to_string());
+        }
+
+        let version = value
+            .field("version")
+            .and_then(|version| version.as_f64())
+            .ok_or("preset.version must be a number")?;
+        if version < 1.0 || version > PRESET_VERSION as f64 || version.fract() != 0.0 {
+            return Err(format!(
+                "unsupported preset version {} (expected 1..={})",
+                version, PRESET_VERSION
+            ));
+        }
+
+        let mut preset = base.clone();
+        preset.merge(value)?;
+        Ok(preset)
+    }
+
+    /// `parse` for a JS object
+    pub fn from_js(value: &JsValue, base: &Preset) -> Result<Preset, JsError> {
+        Self::parse(value, base).map_err(|e| JsError::new(&e))
+    }
+
+    /// `merge` for a JS object
+    pub fn merge_js(&mut self, value: &JsValue) -> Result<(), JsError> {
+        self.merge(value).map_err(|e| JsError::new(&e))
+    }
+
+    /// Override fields present in a (possibly sparse) object
+    /// Uses the same grouping as `to_js`; unknown keys are ignored
+    pub fn merge<S: FieldSource>(&mut self, value: &S) -> Result<(), String> {
+        if !value.is_object() {
+            return Err("parameters must be an object".to_string());
+        }
+        read_string(value, "name", &mut self.name)?;
+
+        if let Some(physics) = group(value, "physics")? {
+            read_f32(&physics, "physics.springBack", &mut self.spring_back)?;
+            read_f32(&physics, "physics.damping", &mut self.damping)?;
+            read_f32(&physics, "physics.velocityInfluence", &mut self.velocity_influence)?;
+        }
+
+        if let Some(noise) = group(value, "noise")? {
+            read_f32(&noise, "noise.scale", &mut self.noise_scale)?;
+            read_f32(&noise, "noise.heightIntensity", &mut self.height_intensity)?;
+        }
+
+        if let Some(mouse) = group(value, "mouse")? {
+            read_f32(&mouse, "mouse.velocitySmoothing", &mut self.mouse_velocity_smoothing)?;
+            read_f32(&mouse, "mouse.offCanvasDecay", &mut self.mouse_off_canvas_decay)?;
+            read_bool(&mouse, "mouse.rawVelocity", &mut self.mouse_raw_velocity)?;
+        }
+
+        if let Some(density) = group(value, "densityEqualization")? {
+            read_bool(
+                &density,
+                "densityEqualization.enabled",
+                &mut self.density_equalization_enabled,
+            )?;
+            read_f32(
+                &density,
+                "densityEqualization.strength",
+                &mut self.density_equalization_strength,
+            )?;
+        }
+
+        if let Some(boundary) = group(value, "boundary")? {
+            let mut mode = self.boundary_mode.to_u32();
+            read_u32(&boundary, "boundary.mode", &mut mode)?;
+            self.boundary_mode =
+                BoundaryMode::try_from_u32(mode).ok_or("boundary.mode must be 0..=1")?;
+
+            let mut direction = self.flow_direction.to_u32();
+            read_u32(&boundary, "boundary.direction", &mut direction)?;
+            self.flow_direction =
+                FlowDirection::try_from_u32(direction).ok_or("boundary.direction must be 0..=3")?;
+
+            read_f32(&boundary, "boundary.flowSpeed", &mut self.flow_speed)?;
+            read_f32(&boundary, "boundary.turbulence", &mut self.turbulence)?;
+        }
+
+        if let Some(depth) = group(value, "depth")? {
+            let mut mode = self.depth_mode.to_u32();
+            read_u32(&depth, "depth.mode", &mut mode)?;
+            self.depth_mode = DepthMode::try_from_u32(mode).ok_or("depth.mode must be 0..=2")?;
+            read_f32(&depth, "depth.parallax", &mut self.depth_parallax)?;
+        }
+
+        if let Some(budget) = group(value, "pointBudget")? {
+            let target = &mut self.point_budget;
+            read_f32(&budget, "pointBudget.perMegapixel", &mut target.per_megapixel)?;
+            read_u32(&budget, "pointBudget.min", &mut target.min)?;
+            read_u32(&budget, "pointBudget.max", &mut target.max)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Curated parameter bundle: physics, noise and density overrides on top of the current
+/// preset plus the ambient effects it switches (mouse, boundary, depth and point budget
+/// are kept)
+pub struct BuiltinPreset {
+    pub name: &'static str,
+    pub spring_back: f32,
+    pub damping: f32,
+    pub velocity_influence: f32,
+    pub noise_scale: f32,
+    pub height_intensity: f32,
+    pub density_equalization: Option<f32>,
+    /// Displacement shimmer (amount, scale, speed), None = off
+    pub shimmer: Option<(f32, f32, f32)>,
+    /// Curl-noise flow field (scale, strength, time speed), None = off
+    pub flow_field: Option<(f32, f32, f32)>,
+    /// Mesh spring stiffness, None = off
+    pub mesh_springs: Option<f32>,
+}
+
+/// Built-in presets in `list_presets` order
+pub const BUILTIN_PRESETS: [BuiltinPreset; 5] = [
+    BuiltinPreset {
+        name: "calm",
+        spring_back: 0.04,
+        damping: 0.95,
+        velocity_influence: 0.2,
+        noise_scale: 0.002,
+        height_intensity: 0.5,
+        density_equalization: Some(0.3),
+        shimmer: None,
+        flow_field: Some((0.001, 0.15, 0.002)),
+        mesh_springs: None,
+    },
+    BuiltinPreset {
+        name: "stormy",
+        spring_back: 0.03,
+        damping: 0.88,
+        velocity_influence: 0.6,
+        noise_scale: 0.005,
+        height_intensity: 0.9,
+        density_equalization: None,
+        shimmer: Some((0.8, 0.01, 0.03)),
+        flow_field: Some((0.003, 1.5, 0.02)),
+        mesh_springs: None,
+    },
+    BuiltinPreset {
+        name: "ripple",
+        spring_back: 0.1,
+        damping: 0.9,
+        velocity_influence: 0.4,
+        noise_scale: 0.003,
+        height_intensity: 0.6,
+        density_equalization: None,
+        shimmer: None,
+        flow_field: None,
+        mesh_springs: Some(0.25),
+    },
+    BuiltinPreset {
+        name: "dense-mesh",
+        spring_back: 0.08,
+        damping: 0.92,
+        velocity_influence: 0.3,
+        noise_scale: 0.006,
+        height_intensity: 0.7,
+        density_equalization: Some(DEFAULT_DENSITY_EQUALIZATION_STRENGTH),
+        shimmer: None,
+        flow_field: None,
+        mesh_springs: Some(0.1),
+    },
+    BuiltinPreset {
+        name: "sparse-drift",
+        spring_back: 0.02,
+        damping: 0.96,
+        velocity_influence: 0.2,
+        noise_scale: 0.0015,
+        height_intensity: 0.4,
+        density_equalization: Some(0.2),
+        shimmer: None,
+        flow_field: Some((0.0008, 0.6, 0.004)),
+        mesh_springs: None,
+    },
+];
+
+impl BuiltinPreset {
+    /// Look up a built-in preset by name
+    pub fn find(name: &str) -> Option<&'static BuiltinPreset> {
+        BUILTIN_PRESETS.iter().find(|preset| preset.name == name)
+    }
+
+    /// Names in `BUILTIN_PRESETS` order
+    pub fn names() -> [&'static str; 5] {
+        BUILTIN_PRESETS.map(|preset| preset.name)
+    }
+
+    /// Override the bundle's fields of `base`
+    pub fn apply_to(&self, base: &mut Preset) {
+        base.name = self.name.to_string();
+        base.spring_back = self.spring_back;
+        base.damping = self.damping;
+        base.velocity_influence = self.velocity_influence;
+        base.noise_scale = self.noise_scale;
+        base.height_intensity = self.height_intensity;
+        base.density_equalization_enabled = self.density_equalization.is_some();
+        if let Some(strength) = self.density_equalization {
+            base.density_equalization_strength = strength;
+        }
+    }
+}
+
+/// Construction options for `Simulation::from_config`, deserialized with
+/// serde-wasm-bindgen; tunables come from the preset groups of the same object, whose
+/// fields these structs leave alone
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationConfig {
+    pub width: f32,
+    pub height: f32,
+    #[serde(default = "default_point_count")]
+    pub point_count: u32,
+    #[serde(default)]
+    pub seed: u32,
+    #[serde(default = "default_pixel_ratio")]
+    pub device_pixel_ratio: f32,
+    #[serde(default)]
+    pub distribution: DistributionConfig,
+    #[serde(default)]
+    pub mouse: MouseConfig,
+}
+
+/// `distribution` group: mode and jitter (see `set_distribution`), or mode 3 with a
+/// center and falloff (see `set_spiral_distribution`)
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DistributionConfig {
+    pub mode: u32,
+    pub jitter: f32,
+    /// Spiral center, None = canvas center
+    pub center_x: Option<f32>,
+    pub center_y: Option<f32>,
+    pub falloff: f32,
+}
+
+/// Pointer settings of the `mouse` group, in effect until `tick` or `set_mouse_state`
+/// pass their own (the spatial grid is sized for the radius from the start)
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct MouseConfig {
+    pub radius: f32,
+    pub strength: f32,
+    pub mode: u32,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            radius: DEFAULT_MOUSE_RADIUS,
+            strength: DEFAULT_MOUSE_STRENGTH,
+            mode: 0,
+        }
+    }
+}
+
+fn default_point_count() -> u32 {
+    DEFAULT_POINT_COUNT
+}
+
+fn default_pixel_ratio() -> f32 {
+    1.0
+}
+
+impl SimulationConfig {
+    /// Parse a config object; width and height are required, the rest has defaults
+    pub fn from_js(value: JsValue) -> Result<Self, JsError> {
+        serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
@@ -0,0 +1,37 @@
+//! Native preset parsing from JSON (`cli` feature)
+//! Shares `Preset::parse`/`merge` with JS objects so preset files saved by hosts load
+//! unchanged
+
+use serde_json::Value;
+
+use crate::fields::FieldSource;
+use crate::preset::Preset;
+
+impl FieldSource for Value {
+    fn field(&self, key: &str) -> Option<Self> {
+        self.get(key).cloned()
+    }
+
+    fn is_object(&self) -> bool {
+        Value::is_object(self)
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        Value::as_f64(self)
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        Value::as_bool(self)
+    }
+
+    fn as_string(&self) -> Option<String> {
+        self.as_str().map(str::to_string)
+    }
+}
+
+impl Preset {
+    /// `parse` for a JSON value
+    pub fn from_json(value: &Value, base: &Preset) -> Result<Preset, String> {
+        Self::parse(value, base)
+    }
+}
@@ -0,0 +1,320 @@
+//! Worker protocol: input commands the main thread writes into a shared-memory ring
+//! buffer for a simulation owned by a Web Worker, and the frame metadata sent back
+//!
+//! Both threads construct a `CommandQueue` over the same SharedArrayBuffer; the main
+//! thread `push`es, the worker calls `Simulation::drain_commands` before posting
+//! `get_frame_info` together with the (transferable) vertex arrays
+
+use js_sys::{Atomics, Float32Array, Int32Array, SharedArrayBuffer};
+use wasm_bindgen::prelude::*;
+
+/// Header words (write and read position, in words) ahead of the command words
+const HEAD: u32 = 0;
+const TAIL: u32 = 1;
+const HEADER_BYTES: u32 = 8;
+
+/// Words in the longest command, opcode included
+const MAX_COMMAND_WORDS: usize = 9;
+
+/// Words in `FrameInfo::words`
+pub const FRAME_INFO_WORDS: usize = 13;
+
+/// Input command: an opcode (the variant's position) followed by its arguments as f32
+/// words; booleans are 0 / 1, ids and modes are whole numbers
+#[derive(Clone, Copy)]
+pub enum Command {
+    Tick {
+        delta_time: f32,
+        speed: f32,
+        mouse_x: f32,
+        mouse_y: f32,
+        mouse_in_canvas: bool,
+        mouse_radius: f32,
+        mouse_strength: f32,
+        mouse_mode: u32,
+    },
+    MouseState {
+        x: f32,
+        y: f32,
+        in_canvas: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+    },
+    Pointer {
+        id: u32,
+        x: f32,
+        y: f32,
+        active: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+    },
+    RemovePointer {
+        id: u32,
+    },
+    Shockwave {
+        x: f32,
+        y: f32,
+        strength: f32,
+    },
+    GravityWell {
+        x: f32,
+        y: f32,
+        active: bool,
+        attract: bool,
+    },
+    GravityWellPosition {
+        x: f32,
+        y: f32,
+    },
+    Resize {
+        width: f32,
+        height: f32,
+    },
+    DevicePixelRatio {
+        dpr: f32,
+    },
+    ScrollOffset {
+        x: f32,
+        y: f32,
+    },
+    Tilt {
+        gx: f32,
+        gy: f32,
+    },
+    AudioLevels {
+        bass: f32,
+        mid: f32,
+        treble: f32,
+    },
+}
+
+impl Command {
+    /// Command names by opcode
+    pub const NAMES: [&'static str; 12] = [
+        "tick",
+        "mouseState",
+        "pointer",
+        "removePointer",
+        "shockwave",
+        "gravityWell",
+        "gravityWellPosition",
+        "resize",
+        "devicePixelRatio",
+        "scrollOffset",
+        "tilt",
+        "audioLevels",
+    ];
+
+    /// Argument count of an opcode, None if unknown
+    pub fn arity(opcode: u32) -> Option<usize> {
+        match opcode {
+            0 => Some(8),
+            1 => Some(6),
+            2 => Some(7),
+            3 | 8 => Some(1),
+            4 | 11 => Some(3),
+            5 => Some(4),
+            6 | 7 | 9 | 10 => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Decode an opcode and its arguments, None if the argument count does not match
+    pub fn decode(opcode: u32, args: &[f32]) -> Option<Self> {
+        if Self::arity(opcode)? != args.len() {
+            return None;
+        }
+        let flag = |i: usize| args[i] != 0.0;
+        let int = |i: usize| args[i] as u32;
+        let command = match opcode {
+            0 => Command::Tick {
+                delta_time: args[0],
+                speed: args[1],
+                mouse_x: args[2],
+                mouse_y: args[3],
+                mouse_in_canvas: flag(4),
+                mouse_radius: args[5],
+                mouse_strength: args[6],
+                mouse_mode: int(7),
+            },
+            1 => Command::MouseState {
+                x: args[0],
+                y: args[1],
+                in_canvas: flag(2),
+                radius: args[3],
+                strength: args[4],
+                mode: int(5),
+            },
+            2 => Command::Pointer {
+                id: int(0),
+                x: args[1],
+                y: args[2],
+                active: flag(3),
+                radius: args[4],
+                strength: args[5],
+                mode: int(6),
+            },
+            3 => Command::RemovePointer { id: int(0) },
+            4 => Command::Shockwave {
+                x: args[0],
+                y: args[1],
+                strength: args[2],
+            },
+            5 => Command::GravityWell {
+                x: args[0],
+                y: args[1],
+                active: flag(2),
+                attract: flag(3),
+            },
+            6 => Command::GravityWellPosition {
+                x: args[0],
+                y: args[1],
+            },
+            7 => Command::Resize {
+                width: args[0],
+                height: args[1],
+            },
+            8 => Command::DevicePixelRatio { dpr: args[0] },
+            9 => Command::ScrollOffset {
+                x: args[0],
+                y: args[1],
+            },
+            10 => Command::Tilt {
+                gx: args[0],
+                gy: args[1],
+            },
+            _ => Command::AudioLevels {
+                bass: args[0],
+                mid: args[1],
+                treble: args[2],
+            },
+        };
+        Some(command)
+    }
+}
+
+/// Metadata posted with each frame so the main thread can size and interpret the
+/// transferred vertex arrays without calling into the worker
+pub struct FrameInfo {
+    pub frame: u32,
+    pub triangle_count: u32,
+    pub stroke_vertex_count: u32,
+    pub point_count: u32,
+    pub triangle_stride: u32,
+    pub stroke_stride: u32,
+    pub point_stride: u32,
+    /// [triangle, stroke, point, indexed vertex, index, stroke index] element counts
+    pub buffer_sizes: [u32; 6],
+}
+
+impl FrameInfo {
+    /// Flattened in field order
+    pub fn words(&self) -> [u32; FRAME_INFO_WORDS] {
+        let [a, b, c, d, e, f] = self.buffer_sizes;
+        [
+            self.frame,
+            self.triangle_count,
+            self.stroke_vertex_count,
+            self.point_count,
+            self.triangle_stride,
+            self.stroke_stride,
+            self.point_stride,
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+        ]
+    }
+}
+
+/// Single-producer single-consumer ring of command words in a SharedArrayBuffer
+/// Positions are published with Atomics, so writes are visible before the new head
+#[wasm_bindgen]
+pub struct CommandQueue {
+    header: Int32Array,
+    words: Float32Array,
+    capacity: u32,
+}
+
+#[wasm_bindgen]
+impl CommandQueue {
+    /// SharedArrayBuffer size in bytes for a queue of `capacity` words
+    #[wasm_bindgen]
+    pub fn byte_length(capacity: u32) -> u32 {
+        HEADER_BYTES + capacity.max(MAX_COMMAND_WORDS as u32 + 1) * 4
+    }
+
+    /// View a shared buffer (sized with `byte_length`, zero-filled when new)
+    #[wasm_bindgen(constructor)]
+    pub fn new(buffer: &SharedArrayBuffer) -> Result<CommandQueue, JsError> {
+        let bytes = buffer.byte_length();
+        if bytes < Self::byte_length(0) {
+            return Err(JsError::new("command queue buffer is too small"));
+        }
+        let capacity = (bytes - HEADER_BYTES) / 4;
+        Ok(Self {
+            header: Int32Array::new_with_byte_offset_and_length(buffer, 0, 2),
+            words: Float32Array::new_with_byte_offset_and_length(buffer, HEADER_BYTES, capacity),
+            capacity,
+        })
+    }
+
+    /// Append a command (main thread), opcodes as in `Command::NAMES`
+    /// Returns false if the opcode or argument count is wrong or the queue is full
+    #[wasm_bindgen]
+    pub fn push(&self, opcode: u32, args: &[f32]) -> bool {
+        if Command::arity(opcode) != Some(args.len()) {
+            return false;
+        }
+        let (head, tail) = (self.load(HEAD), self.load(TAIL));
+        let used = (head + self.capacity - tail) % self.capacity;
+        // One word stays free so a full queue is distinguishable from an empty one
+        if used + 1 + args.len() as u32 >= self.capacity {
+            return false;
+        }
+
+        let mut at = head;
+        for word in std::iter::once(opcode as f32).chain(args.iter().copied()) {
+            self.words.set_index(at, word);
+            at = (at + 1) % self.capacity;
+        }
+        self.store(HEAD, at);
+        true
+    }
+}
+
+impl CommandQueue {
+    /// Take the next command (worker), None when empty
+    /// An unknown opcode means the writer disagrees on the protocol; the rest is dropped
+    pub fn pop(&self) -> Option<Command> {
+        let (head, tail) = (self.load(HEAD), self.load(TAIL));
+        if head == tail {
+            return None;
+        }
+        let read = |offset: u32| self.words.get_index((tail + offset) % self.capacity);
+
+        let opcode = read(0) as u32;
+        let Some(arity) = Command::arity(opcode) else {
+            self.store(TAIL, head);
+            return None;
+        };
+        let mut args = [0.0; MAX_COMMAND_WORDS - 1];
+        for (i, arg) in args[..arity].iter_mut().enumerate() {
+            *arg = read(1 + i as u32);
+        }
+        self.store(TAIL, (tail + 1 + arity as u32) % self.capacity);
+        Command::decode(opcode, &args[..arity])
+    }
+
+    fn load(&self, index: u32) -> u32 {
+        Atomics::load(&self.header, index).unwrap_or(0) as u32 % self.capacity
+    }
+
+    fn store(&self, index: u32, value: u32) {
+        let _ = Atomics::store(&self.header, index, value as i32);
+    }
+}
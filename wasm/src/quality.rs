@@ -0,0 +1,122 @@
+//! Quality tiers: one degradation ladder trading detail for frame time, optionally
+//! stepped by an adaptive controller fed with the host's measured frame times
+
+use crate::constants::{
+    FRAME_TIME_SMOOTHING, MAX_QUALITY_TIER, QUALITY_DOWNGRADE_FRAMES, QUALITY_DOWNGRADE_RATIO,
+    QUALITY_UPGRADE_FRAMES, QUALITY_UPGRADE_RATIO,
+};
+
+/// What one rung of the ladder keeps
+#[derive(Clone, Copy)]
+pub struct QualityTier {
+    /// Fraction of the requested point count that is simulated
+    pub point_scale: f32,
+    /// Physics updates per triangulation (1 = every tick)
+    pub triangulation_interval: u32,
+    /// Ambient effects: flow field, heat field, turbulence, density equalization, mesh springs
+    pub effects: bool,
+    /// Optional buffer work: Voronoi, bundled edges, height blur, shadows
+    pub rich_buffers: bool,
+}
+
+/// Tiers from cheapest (0) to full quality (MAX_QUALITY_TIER)
+const TIERS: [QualityTier; MAX_QUALITY_TIER as usize + 1] = [
+    QualityTier {
+        point_scale: 0.35,
+        triangulation_interval: 3,
+        effects: false,
+        rich_buffers: false,
+    },
+    QualityTier {
+        point_scale: 0.5,
+        triangulation_interval: 2,
+        effects: false,
+        rich_buffers: false,
+    },
+    QualityTier {
+        point_scale: 0.75,
+        triangulation_interval: 1,
+        effects: false,
+        rich_buffers: false,
+    },
+    QualityTier {
+        point_scale: 1.0,
+        triangulation_interval: 1,
+        effects: true,
+        rich_buffers: false,
+    },
+    QualityTier {
+        point_scale: 1.0,
+        triangulation_interval: 1,
+        effects: true,
+        rich_buffers: true,
+    },
+];
+
+impl QualityTier {
+    /// Settings of a tier (clamped to the ladder)
+    pub fn get(tier: u32) -> Self {
+        TIERS[tier.min(MAX_QUALITY_TIER) as usize]
+    }
+
+    /// Simulated point count for a requested one (not yet validated)
+    pub fn point_count(&self, requested: usize) -> usize {
+        (requested as f32 * self.point_scale).round() as usize
+    }
+}
+
+/// Steps the tier down when frames stay slow and back up when they stay fast
+/// The dead band between the two ratios and the longer upgrade dwell keep it
+/// from oscillating around the target
+#[derive(Clone)]
+pub struct AdaptiveQuality {
+    target_ms: f32,
+    /// Smoothed frame time (None before the first report)
+    average_ms: Option<f32>,
+    slow_frames: u32,
+    fast_frames: u32,
+}
+
+impl AdaptiveQuality {
+    pub fn new(target_ms: f32) -> Self {
+        Self {
+            target_ms,
+            average_ms: None,
+            slow_frames: 0,
+            fast_frames: 0,
+        }
+    }
+
+    /// Feed one frame time (milliseconds); returns the tier to switch to, if any
+    pub fn report(&mut self, frame_ms: f32, tier: u32) -> Option<u32> {
+        let average = match self.average_ms {
+            Some(average) => frame_ms + (average - frame_ms) * FRAME_TIME_SMOOTHING,
+            None => frame_ms,
+        };
+        self.average_ms = Some(average);
+
+        if average > self.target_ms * QUALITY_DOWNGRADE_RATIO {
+            self.slow_frames += 1;
+            self.fast_frames = 0;
+        } else if average < self.target_ms * QUALITY_UPGRADE_RATIO {
+            self.fast_frames += 1;
+            self.slow_frames = 0;
+        } else {
+            self.slow_frames = 0;
+            self.fast_frames = 0;
+        }
+
+        let next = if self.slow_frames >= QUALITY_DOWNGRADE_FRAMES && tier > 0 {
+            tier - 1
+        } else if self.fast_frames >= QUALITY_UPGRADE_FRAMES && tier < MAX_QUALITY_TIER {
+            tier + 1
+        } else {
+            return None;
+        };
+        // The new tier has a different cost, measure it afresh
+        self.average_ms = None;
+        self.slow_frames = 0;
+        self.fast_frames = 0;
+        Some(next)
+    }
+}
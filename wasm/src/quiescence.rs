@@ -0,0 +1,67 @@
+//! Quiescence: skip triangulation and buffer rebuilds while no point moved more than an
+//! epsilon since the last build, so static backgrounds reuse their buffers
+
+use crate::point::Point;
+use crate::triangulation::{RenderMode, VertexLayout};
+
+/// Point positions and output settings of the last build
+#[derive(Clone)]
+pub struct Quiescence {
+    /// Largest movement (CSS pixels) that still reuses the last build
+    epsilon: f32,
+    /// Point positions (canvas pixels) at the last build
+    built: Vec<(f32, f32)>,
+    layout: VertexLayout,
+    render_mode: RenderMode,
+    /// An input, effect or output setting call since the last build
+    dirty: bool,
+}
+
+impl Quiescence {
+    pub fn new(epsilon: f32) -> Self {
+        Self {
+            epsilon,
+            built: Vec::new(),
+            layout: VertexLayout::default(),
+            render_mode: RenderMode::default(),
+            dirty: true,
+        }
+    }
+
+    /// Force the next build
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Remember what a build just emitted
+    pub fn built(&mut self, points: &[Point], layout: VertexLayout, render_mode: RenderMode) {
+        self.built.clear();
+        self.built.extend(points.iter().map(|p| (p.x, p.y)));
+        self.layout = layout;
+        self.render_mode = render_mode;
+        self.dirty = false;
+    }
+
+    /// Whether the last build still stands: same output settings and every point within
+    /// epsilon of where it was built
+    pub fn holds(
+        &self,
+        points: &[Point],
+        layout: VertexLayout,
+        render_mode: RenderMode,
+        pixel_ratio: f32,
+    ) -> bool {
+        if self.dirty
+            || layout != self.layout
+            || render_mode != self.render_mode
+            || points.len() != self.built.len()
+        {
+            return false;
+        }
+        let epsilon_sq = (self.epsilon * pixel_ratio).powi(2);
+        points
+            .iter()
+            .zip(&self.built)
+            .all(|(p, &(x, y))| (p.x - x).powi(2) + (p.y - y).powi(2) <= epsilon_sq)
+    }
+}
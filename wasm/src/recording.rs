@@ -0,0 +1,540 @@
+//! Input recording with periodic snapshots, used for deterministic seeking
+//! Snapshots hold the full simulation state; inputs between them are replayed
+
+use crate::constants::{MAX_RECORDED_EVENTS, MAX_RECORDING_SNAPSHOTS};
+use crate::preset::Preset;
+use crate::simulation::Simulation;
+
+/// A recorded call that changes simulation state
+#[derive(Clone)]
+pub enum InputEvent {
+    Tick {
+        delta_time: f32,
+        speed: f32,
+        mouse_x: f32,
+        mouse_y: f32,
+        mouse_in_canvas: bool,
+        mouse_radius: f32,
+        mouse_strength: f32,
+        mouse_mode: u32,
+    },
+    MouseState {
+        x: f32,
+        y: f32,
+        in_canvas: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+    },
+    UpdatePoints {
+        delta_time: f32,
+        speed: f32,
+    },
+    Step {
+        delta_time: f32,
+    },
+    Warmup {
+        steps: u32,
+    },
+    Pause,
+    Resume,
+    Shockwave {
+        x: f32,
+        y: f32,
+        strength: f32,
+    },
+    Implosion {
+        x: f32,
+        y: f32,
+        strength: f32,
+        start_radius: f32,
+    },
+    FlipRegion {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        stagger: f32,
+    },
+    GravityWell {
+        x: f32,
+        y: f32,
+        active: bool,
+        attract: bool,
+    },
+    GravityWellPosition {
+        x: f32,
+        y: f32,
+    },
+    CursorEmitter {
+        rate: f32,
+        strength: f32,
+    },
+    Rain {
+        enabled: bool,
+        rate: f32,
+        min_strength: f32,
+        max_strength: f32,
+    },
+    PathEmitter {
+        path: Vec<f32>,
+        rate: f32,
+        strength: f32,
+    },
+    RemoveEmitter {
+        id: u32,
+    },
+    Pointer {
+        id: u32,
+        x: f32,
+        y: f32,
+        active: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+    },
+    RemovePointer {
+        id: u32,
+    },
+    Environment {
+        hour: f32,
+        day_of_year: f32,
+    },
+    AudioLevels {
+        bass: f32,
+        mid: f32,
+        treble: f32,
+    },
+    Tilt {
+        gx: f32,
+        gy: f32,
+    },
+    ScrollOffset {
+        x: f32,
+        y: f32,
+    },
+    StickyCursor {
+        enabled: bool,
+        break_force: f32,
+    },
+    PointerEvent {
+        x: f32,
+        y: f32,
+        t: f64,
+        pressure: f32,
+        buttons: u32,
+    },
+    AddObstacle {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    RemoveObstacle {
+        id: u32,
+    },
+    ClearObstacles,
+    AnchorPoints {
+        positions: Vec<f32>,
+    },
+    AddExclusion {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        mode: u32,
+    },
+    RemoveExclusion {
+        id: u32,
+    },
+    ClearExclusions,
+    PinPoint {
+        index: usize,
+    },
+    UnpinPoint {
+        index: usize,
+    },
+    PinPointsNear {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    PointLayer {
+        index: usize,
+        layer: u32,
+    },
+    PointsLayerNear {
+        x: f32,
+        y: f32,
+        radius: f32,
+        layer: u32,
+    },
+    DensityMap {
+        data: Vec<u8>,
+        cols: usize,
+        rows: usize,
+    },
+    DensityRebalance {
+        rate: f32,
+    },
+    Positions {
+        data: Vec<f32>,
+    },
+    Velocities {
+        data: Vec<f32>,
+    },
+    AddPoint {
+        x: f32,
+        y: f32,
+    },
+    RemovePoint {
+        index: usize,
+    },
+    RemovePointsInRadius {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    ScheduleShockwave {
+        delay_ms: f32,
+        x: f32,
+        y: f32,
+        strength: f32,
+    },
+    ScheduleGravityWell {
+        start_ms: f32,
+        end_ms: f32,
+        x: f32,
+        y: f32,
+        attract: bool,
+    },
+    ClearSchedule,
+    CancelEffect {
+        id: u32,
+    },
+    ClearEffects,
+    NoiseParams {
+        noise_scale: f32,
+        height_intensity: f32,
+    },
+    DevicePixelRatio {
+        dpr: f32,
+    },
+    InputFilter {
+        min_cutoff: f32,
+        beta: f32,
+    },
+    PhysicsParams {
+        spring_back: f32,
+        damping: f32,
+        velocity_influence: f32,
+    },
+    DensityEqualization {
+        enabled: bool,
+        strength: f32,
+    },
+    QualityTier {
+        tier: u32,
+    },
+    AdaptiveQuality {
+        enabled: bool,
+        target_frame_ms: f32,
+    },
+    FrameTime {
+        frame_ms: f32,
+    },
+    EnvironmentCurve {
+        target: u32,
+        daily: Vec<f32>,
+        seasonal: Vec<f32>,
+    },
+    AudioResponse {
+        pulse: f32,
+        drift_boost: f32,
+        jitter: f32,
+    },
+    GlobalForce {
+        fx: f32,
+        fy: f32,
+    },
+    MeshSprings {
+        enabled: bool,
+        stiffness: f32,
+    },
+    ForceHeightMask {
+        min_z: f32,
+        max_z: f32,
+    },
+    BoundaryMode {
+        mode: u32,
+    },
+    WindTunnelParams {
+        direction: u32,
+        flow_speed: f32,
+        turbulence: f32,
+    },
+    PointDepth {
+        mode: u32,
+        parallax: f32,
+    },
+    FlowField {
+        enabled: bool,
+        scale: f32,
+        strength: f32,
+        time_speed: f32,
+    },
+    Turbulence {
+        amount: f32,
+        scale: f32,
+        speed: f32,
+    },
+    HeatField {
+        enabled: bool,
+        diffusion: f32,
+        retention: f32,
+    },
+    InteractionMetrics {
+        window_minutes: f32,
+    },
+    LightDirection {
+        angle: f32,
+    },
+    Light {
+        dir_x: f32,
+        dir_y: f32,
+        dir_z: f32,
+        ambient: f32,
+    },
+    MaxCatchup {
+        frames: f32,
+    },
+    FixedTimestep {
+        hz: f32,
+    },
+    Substeps {
+        n: u32,
+    },
+    TimeScale {
+        scale: f32,
+    },
+    HeightBlur {
+        iterations: u32,
+    },
+    MinTriangleQuality {
+        min_angle_deg: f32,
+    },
+    IncrementalTriangulation {
+        enabled: bool,
+        epsilon: f32,
+    },
+    GhostMargin {
+        px: f32,
+    },
+    BaseTopology {
+        enabled: bool,
+    },
+    TopologyHysteresis {
+        margin_deg: f32,
+    },
+    TriangleMetadata {
+        enabled: bool,
+    },
+    OutputSmoothing {
+        alpha: f32,
+    },
+    ConstellationMode {
+        max_distance: f32,
+    },
+    RenderMode {
+        mode: u32,
+    },
+    BuiltinPreset {
+        name: String,
+    },
+    ShockwaveLimit {
+        capacity: u32,
+        policy: u32,
+    },
+    ObstacleCulling {
+        enabled: bool,
+    },
+    ClearEmitters,
+    GravityWellThrow {
+        enabled: bool,
+        friction: f32,
+        bounce: bool,
+    },
+    Resize {
+        new_width: f32,
+        new_height: f32,
+    },
+    PointCount {
+        count: usize,
+        seed: u32,
+    },
+    Distribution {
+        mode: u32,
+        jitter: f32,
+    },
+    SpiralDistribution {
+        center_x: f32,
+        center_y: f32,
+        falloff: f32,
+    },
+    Watchdog {
+        interval_seconds: f32,
+    },
+    AutoMaintenance {
+        interval_seconds: f32,
+    },
+    IdleBehavior {
+        enabled: bool,
+        delay_ms: f32,
+        intensity: f32,
+    },
+    StaticAfterSettle {
+        enabled: bool,
+        threshold: f32,
+    },
+    QuiescenceEpsilon {
+        epsilon: f32,
+    },
+    Params {
+        preset: Box<Preset>,
+    },
+    Handoff {
+        bytes: Vec<u8>,
+    },
+}
+
+impl InputEvent {
+    /// Whether this event advances the simulation by one frame
+    pub fn advances_frame(&self) -> bool {
+        matches!(
+            self,
+            InputEvent::Tick { .. } | InputEvent::UpdatePoints { .. } | InputEvent::Step { .. }
+        )
+    }
+}
+
+/// Full state captured at a frame boundary
+#[derive(Clone)]
+struct Snapshot {
+    frame: u32,
+    /// Index of the first event recorded after this snapshot
+    event_index: usize,
+    state: Box<Simulation>,
+}
+
+/// Recorded inputs and snapshots
+#[derive(Clone)]
+pub struct Recorder {
+    /// Frames between snapshots (doubles whenever the snapshots are thinned)
+    snapshot_interval: u32,
+    snapshots: Vec<Snapshot>,
+    /// (frame the event was applied at, event)
+    events: Vec<(u32, InputEvent)>,
+    /// Replay position; new input truncates anything recorded after it
+    cursor: usize,
+}
+
+impl Recorder {
+    /// Start recording from an initial snapshot
+    pub fn new(snapshot_interval: u32, frame: u32, initial: Simulation) -> Self {
+        Self {
+            snapshot_interval: snapshot_interval.max(1),
+            snapshots: vec![Snapshot {
+                frame,
+                event_index: 0,
+                state: Box::new(initial),
+            }],
+            events: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Whether a snapshot should be taken before applying a frame-advancing event
+    pub fn wants_snapshot(&self, frame: u32, event: &InputEvent) -> bool {
+        if !event.advances_frame() || self.is_full() {
+            return false;
+        }
+        let first = self.snapshots[0].frame;
+        let last = self.snapshots.last().map_or(first, |s| s.frame);
+        frame > last && (frame - first).is_multiple_of(self.snapshot_interval)
+    }
+
+    /// Whether the event log reached its size limit (recording stops growing)
+    pub fn is_full(&self) -> bool {
+        self.events.len() >= MAX_RECORDED_EVENTS
+    }
+
+    /// Discard history recorded after the replay position
+    fn truncate_future(&mut self, frame: u32) {
+        if self.cursor < self.events.len() {
+            self.events.truncate(self.cursor);
+            self.snapshots.retain(|s| s.frame <= frame);
+        }
+    }
+
+    /// Store a snapshot for the current frame; past MAX_RECORDING_SNAPSHOTS every other
+    /// snapshot (never the first) is dropped so memory stays bounded on long recordings
+    pub fn push_snapshot(&mut self, frame: u32, state: Simulation) {
+        self.truncate_future(frame);
+        self.snapshots.push(Snapshot {
+            frame,
+            event_index: self.events.len(),
+            state: Box::new(state),
+        });
+        if self.snapshots.len() > MAX_RECORDING_SNAPSHOTS {
+            let mut index = 0;
+            self.snapshots.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.snapshot_interval = self.snapshot_interval.saturating_mul(2);
+        }
+    }
+
+    /// Record an event applied at `frame`
+    pub fn push_event(&mut self, frame: u32, event: InputEvent) {
+        self.truncate_future(frame);
+        if self.is_full() {
+            return;
+        }
+        self.events.push((frame, event));
+        self.cursor = self.events.len();
+    }
+
+    /// Latest snapshot at or before `frame` (falls back to the first one)
+    pub fn snapshot_before(&self, frame: u32) -> (u32, usize, &Simulation) {
+        let snapshot = self
+            .snapshots
+            .iter()
+            .rev()
+            .find(|s| s.frame <= frame)
+            .unwrap_or(&self.snapshots[0]);
+        (snapshot.frame, snapshot.event_index, &snapshot.state)
+    }
+
+    /// Events from `index` on
+    pub fn events_from(&self, index: usize) -> &[(u32, InputEvent)] {
+        &self.events[index.min(self.events.len())..]
+    }
+
+    /// Set the replay position after a seek
+    pub fn set_cursor(&mut self, index: usize) {
+        self.cursor = index.min(self.events.len());
+    }
+
+    /// Frame reached after the last recorded event
+    pub fn end_frame(&self) -> u32 {
+        let first = self.snapshots[0].frame;
+        self.events
+            .last()
+            .map_or(first, |(frame, e)| frame + e.advances_frame() as u32)
+    }
+
+    /// First recorded frame
+    pub fn start_frame(&self) -> u32 {
+        self.snapshots[0].frame
+    }
+}
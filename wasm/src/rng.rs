@@ -1,4 +1,9 @@
-/// Fast xorshift32 random number generator
+//! Fast xorshift32 random number generator
+
+use crate::constants::{
+    RNG_STREAM_DENSITY, RNG_STREAM_DEPTH, RNG_STREAM_DRIFT, RNG_STREAM_HEAT, RNG_STREAM_IDLE,
+    RNG_STREAM_PLACEMENT, RNG_STREAM_RAIN,
+};
 
 #[derive(Clone)]
 pub struct Rng {
@@ -12,6 +17,16 @@ impl Rng {
         }
     }
 
+    /// Seed an independent stream from a master seed and a stream id
+    /// Uses murmur3's finalizer so nearby seeds/ids give unrelated states
+    pub fn derive(seed: u32, stream: u32) -> Self {
+        let mut z = seed ^ stream.wrapping_mul(0x9E37_79B9);
+        z = (z ^ (z >> 16)).wrapping_mul(0x85EB_CA6B);
+        z = (z ^ (z >> 13)).wrapping_mul(0xC2B2_AE35);
+        z ^= z >> 16;
+        Self::new(z)
+    }
+
     #[inline]
     pub fn next(&mut self) -> u32 {
         let mut x = self.state;
@@ -27,3 +42,38 @@ impl Rng {
         (self.next() as f32) / (u32::MAX as f32)
     }
 }
+
+/// Per-subsystem random streams derived from one master seed
+/// Each subsystem draws only from its own stream, so toggling a feature never
+/// shifts another's randomness. New randomized features get their own stream.
+#[derive(Clone)]
+pub struct RngStreams {
+    /// Initial and respawn positions
+    pub placement: Rng,
+    /// Initial drift velocities
+    pub drift: Rng,
+    /// Per-point parallax depth
+    pub depth: Rng,
+    /// Heat-driven jitter
+    pub heat: Rng,
+    /// Density map rebalancing (which points move, and where)
+    pub density: Rng,
+    /// Rain drop positions and strengths
+    pub rain: Rng,
+    /// Idle shockwave positions
+    pub idle: Rng,
+}
+
+impl RngStreams {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            placement: Rng::derive(seed, RNG_STREAM_PLACEMENT),
+            drift: Rng::derive(seed, RNG_STREAM_DRIFT),
+            depth: Rng::derive(seed, RNG_STREAM_DEPTH),
+            heat: Rng::derive(seed, RNG_STREAM_HEAT),
+            density: Rng::derive(seed, RNG_STREAM_DENSITY),
+            rain: Rng::derive(seed, RNG_STREAM_RAIN),
+            idle: Rng::derive(seed, RNG_STREAM_IDLE),
+        }
+    }
+}
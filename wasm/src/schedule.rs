@@ -0,0 +1,69 @@
+//! Effect timeline: effects queued with start times fire inside `tick`, so scripted
+//! sequences (e.g. an intro sweep of shockwaves) need no JS timers
+
+use crate::constants::MAX_SCHEDULED_EFFECTS;
+
+/// Effect queued for a point in simulated time
+#[derive(Clone, Copy)]
+pub enum ScheduledEffect {
+    Shockwave { x: f32, y: f32, strength: f32 },
+    /// Place the gravity well (replacing any current one)
+    GravityWellStart { x: f32, y: f32, attract: bool },
+    /// Remove the gravity well
+    GravityWellEnd,
+}
+
+/// Pending effects ordered by time (frames of simulated time), each with the handle
+/// the effect takes when it fires
+#[derive(Clone)]
+pub struct Schedule {
+    entries: Vec<(f32, u32, ScheduledEffect)>,
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Free slots (each effect takes one, a gravity well two)
+    pub fn remaining(&self) -> usize {
+        MAX_SCHEDULED_EFFECTS - self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Queue an effect; effects due at the same time fire in insertion order
+    pub fn push(&mut self, time: f32, id: u32, effect: ScheduledEffect) {
+        if self.entries.len() >= MAX_SCHEDULED_EFFECTS {
+            return;
+        }
+        let index = self.entries.partition_point(|&(t, _, _)| t <= time);
+        self.entries.insert(index, (time, id, effect));
+    }
+
+    /// Take the earliest effect due at or before `time` with its handle
+    pub fn pop_due(&mut self, time: f32) -> Option<(u32, ScheduledEffect)> {
+        match self.entries.first() {
+            Some(&(t, id, effect)) if t <= time => {
+                self.entries.remove(0);
+                Some((id, effect))
+            }
+            _ => None,
+        }
+    }
+
+    /// Drop the pending parts of a named effect; returns whether any were pending
+    pub fn cancel(&mut self, id: u32) -> bool {
+        let count = self.entries.len();
+        self.entries.retain(|&(_, entry_id, _)| entry_id != id);
+        self.entries.len() != count
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -0,0 +1,61 @@
+//! Settle-to-static: once the points have barely moved for a while, the simulation
+//! sleeps (no physics, no triangulation) until new input or an effect wakes it, so
+//! mostly still backgrounds stop costing CPU
+//!
+//! Motion is the mean distance points moved per frame, in CSS pixels
+
+use crate::constants::SETTLE_CALM_FRAMES;
+use crate::point::Point;
+
+/// Motion tracker and sleep state
+#[derive(Clone)]
+pub struct Settle {
+    /// Mean motion (CSS pixels per frame) below which the points count as calm
+    threshold: f32,
+    /// Consecutive calm frames
+    calm: f32,
+    asleep: bool,
+    /// Point positions after the previous update (reused each update)
+    last: Vec<(f32, f32)>,
+}
+
+impl Settle {
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            calm: 0.0,
+            asleep: false,
+            last: Vec::new(),
+        }
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    /// Leave sleep and restart the calm count
+    pub fn wake(&mut self) {
+        self.asleep = false;
+        self.calm = 0.0;
+    }
+
+    /// Measure motion since the previous update of `delta_time` frames; fall asleep
+    /// after SETTLE_CALM_FRAMES calm frames unless `busy` (something will move the
+    /// points without input, e.g. rain)
+    pub fn observe(&mut self, points: &[Point], pixel_ratio: f32, delta_time: f32, busy: bool) {
+        let comparable = self.last.len() == points.len() && !points.is_empty();
+        let calm = comparable && delta_time > 0.0 && {
+            let moved: f32 = points
+                .iter()
+                .zip(&self.last)
+                .map(|(p, &(x, y))| (p.x - x).hypot(p.y - y))
+                .sum();
+            moved / points.len() as f32 / pixel_ratio / delta_time < self.threshold
+        };
+        self.last.clear();
+        self.last.extend(points.iter().map(|p| (p.x, p.y)));
+
+        self.calm = if calm && !busy { self.calm + delta_time } else { 0.0 };
+        self.asleep = self.calm >= SETTLE_CALM_FRAMES;
+    }
+}
@@ -1,27 +1,120 @@
-/// Main simulation state and public WASM API
+//! Main simulation state and public WASM API
 
 use js_sys::Float32Array;
 use wasm_bindgen::prelude::*;
 
+use crate::anchor::Anchors;
+use crate::audio::AudioReactivity;
+use crate::boundary::{Boundary, BoundaryMode, FlowDirection};
 use crate::constants::{
-    DEFAULT_DAMPING, DEFAULT_HEIGHT_INTENSITY, DEFAULT_MOUSE_RADIUS, DEFAULT_NOISE_SCALE,
-    DEFAULT_SPRING_BACK, DEFAULT_VELOCITY_INFLUENCE, GRAVITY_WELL_MAX_RANGE, MAX_DIMENSION,
-    MAX_POINT_COUNT, MIN_DIMENSION, MIN_POINT_COUNT, SHOCKWAVE_WAVE_WIDTH,
+    CATCHUP_BACKLOG_DECAY, DEFAULT_AMBIENT, DEFAULT_CONSTELLATION_DISTANCE, DEFAULT_DAMPING,
+    DEFAULT_DENSITY_EQUALIZATION_STRENGTH, DEFAULT_HEIGHT_INTENSITY, DEFAULT_LIGHT_ANGLE,
+    DEFAULT_LIGHT_VECTOR, DEFAULT_MAX_CATCHUP_FRAMES, DEFAULT_MOUSE_RADIUS, DEFAULT_NOISE_SCALE,
+    DEFAULT_SPRING_BACK, DEFAULT_VELOCITY_INFLUENCE, DEFAULT_WELL_THROW_FRICTION, FRAMES_PER_SECOND,
+    GRAVITY_WELL_MAX_RANGE, HEAT_CELL_SIZE, HEAT_GRAVITY_DEPOSIT, HEAT_JITTER, HEAT_MOUSE_DEPOSIT,
+    HEAT_SHOCKWAVE_DEPOSIT, IDLE_INPUT_SPEED, INTERACTION_METRICS_GRID, MAINTENANCE_STRENGTH,
+    MAX_AUDIO_RESPONSE, MAX_CATCHUP_FRAMES, MAX_CONSTELLATION_DISTANCE, MAX_DIMENSION,
+    MAX_FIXED_TIMESTEP_HZ, MAX_FLOW_FIELD_STRENGTH, MAX_FLOW_FIELD_TIME_SPEED, MAX_GLOBAL_FORCE,
+    MAX_HEIGHT_BLUR_ITERATIONS, MAX_IDLE_INTENSITY, MAX_INCREMENTAL_EPSILON,
+    MAX_INTERACTION_WINDOW_MINUTES, MAX_MESH_SPRING_STIFFNESS, MAX_MIN_TRIANGLE_ANGLE,
+    MAX_NORMAL_MAP_SIZE, MAX_OUTPUT_SMOOTHING, MAX_POINT_COUNT, MAX_POINT_LAYER, MAX_QUALITY_TIER,
+    MAX_SUBSTEPS, MAX_SUBSTEP_FRAMES, MAX_TIME_SCALE, MAX_TOPOLOGY_HYSTERESIS,
+    MAX_TURBULENCE_AMOUNT, MAX_TURBULENCE_SPEED, MAX_WARMUP_STEPS, MIN_DIMENSION,
+    MIN_FIXED_TIMESTEP_HZ, MIN_POINT_COUNT, MIN_TIME_SCALE, NORMAL_MAP_INTERVAL_FRAMES,
+    SCROLL_SMOOTHING, SCROLL_SNAP_DISTANCE, SETTLE_WAKE_SPEED, SHOCKWAVE_WAVE_WIDTH,
+    TILT_DRIFT_SPEED, TILT_LIGHT_SHIFT, TILT_LIGHT_SHIFT_EPSILON,
+};
+use crate::constellation::build_constellation;
+use crate::density::DensityMap;
+use crate::depth::{depth_factor, DepthMode};
+use crate::distribution::{self, Distribution};
+use crate::environment::{Curve, Environment, EnvironmentTarget};
+use crate::exclusion::{ExclusionMode, ExclusionRect, ExclusionZones};
+use crate::effects::{
+    build_effects_buffer, EmitterManager, GravityWell, MouseMode, MouseState, OverflowPolicy,
+    PointerSet, Shockwave, ShockwaveManager, WellThrow, EFFECT_STRIDE,
+};
+use crate::flip::FlipManager;
+use crate::flow_field::FlowField;
+use crate::handoff::{self, HandoffParams};
+use crate::heat::HeatField;
+use crate::idle::IdleBehavior;
+use crate::incremental::IncrementalMesh;
+use crate::input_filter::PointerFilter;
+use crate::interop::string_array;
+use crate::maintenance::Maintenance;
+use crate::memory::MemoryGeneration;
+use crate::metrics::InteractionMetrics;
+use crate::normal_map::NormalMap;
+use crate::obstacle::{Obstacle, ObstacleSet};
+use crate::parallel::for_each_chunk;
+use crate::physics::{
+    apply_density_equalization, apply_gravity_well, apply_mesh_springs, apply_mouse_influence,
+    apply_shockwave, apply_springs, EffectFilter, EffectTarget, ForceFilter, HeightMask,
 };
-use crate::effects::{GravityWell, MouseState, ShockwaveManager};
-use crate::physics::{apply_gravity_well, apply_mouse_influence, apply_shockwave};
 use crate::point::Point;
-use crate::rng::Rng;
+use crate::pointer_events::{PointerEvent, PointerEventQueue};
+use crate::preset::{BuiltinPreset, PointBudget, Preset, SimulationConfig};
+use crate::protocol::{Command, CommandQueue, FrameInfo};
+use crate::quality::{AdaptiveQuality, QualityTier};
+use crate::quiescence::Quiescence;
+use crate::recording::{InputEvent, Recorder};
+use crate::rng::RngStreams;
+use crate::schedule::{Schedule, ScheduledEffect};
+use crate::settle::Settle;
 use crate::spatial_grid::SpatialGrid;
-use crate::triangulation::{triangulate, TriangulationBuffers};
+use crate::sticky::StickyCursor;
+use crate::triangulation::{
+    build_constellation_output, triangulate, vertex_attr, AttributeParams, EdgeMode, OutputSpace,
+    OutputTransform, RenderMode, TriangulationBuffers,
+};
+use crate::turbulence::Turbulence;
+use crate::watchdog::{points_healthy, Watchdog};
 
 /// Main simulation state
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Simulation {
     points: Vec<Point>,
     width: f32,
     height: f32,
-    rng: Rng,
+    // Independent random streams per subsystem
+    rng: RngStreams,
+
+    // Simulation time in frames (advanced by delta_time)
+    time: f32,
+
+    // Number of physics updates performed
+    frame: u32,
+
+    // Frozen by `pause` (ticks return the cached buffers); speed of the last update,
+    // reused by `step`
+    paused: bool,
+    last_speed: f32,
+
+    // Frame-gap catch-up: frames simulated per update at most, unsimulated remainder
+    max_catchup: f32,
+    catchup_backlog: f32,
+
+    // Fixed-timestep mode: frames per physics step (None = follow delta_time) and the
+    // time not yet simulated
+    fixed_step: Option<f32>,
+    step_accumulator: f32,
+
+    // Physics substeps each update (or fixed step) is split into at least
+    substeps: u32,
+
+    // Simulated time per real time (slow motion below 1); interaction impulses unscaled
+    time_scale: f32,
+
+    // Input recording for seeking (None when not recording)
+    recorder: Option<Box<Recorder>>,
+
+    // Canvas pixels per CSS pixel; pixel-based parameters are given in CSS pixels
+    pixel_ratio: f32,
+
+    // Edge behavior
+    boundary: Boundary,
 
     // Noise parameters
     noise_scale: f32,
@@ -29,21 +122,153 @@ pub struct Simulation {
 
     // Mouse state
     mouse: MouseState,
+    pointers: PointerSet,
+    input_filter: Option<PointerFilter>,
+    /// Raw events pushed since the last tick, and the mouse swept along them this tick
+    pointer_events: PointerEventQueue,
+    mouse_path: Vec<MouseState>,
 
     // Physics settings
     spring_back: f32,
     damping: f32,
     velocity_influence: f32,
 
+    // Density equalization
+    density_equalization_enabled: bool,
+    density_equalization_strength: f32,
+
+    // Height band of points reacting to mouse, gravity well and shockwaves
+    force_mask: HeightMask,
+    // Per-effect filters, indexed by EffectTarget
+    effect_filters: [EffectFilter; 3],
+
+    // Parallax depth
+    depth_mode: DepthMode,
+    depth_parallax: f32,
+
     // Effects
     shockwaves: ShockwaveManager,
+    emitters: EmitterManager,
     gravity_well: Option<GravityWell>,
+    // Released wells coast with their drag velocity (None = they vanish on release)
+    well_throw: Option<WellThrow>,
+    flips: FlipManager,
+    obstacles: ObstacleSet,
+    anchors: Anchors,
+    exclusions: ExclusionZones,
 
     // Spatial partitioning
     spatial_grid: SpatialGrid,
 
     // Output buffers
     buffers: TriangulationBuffers,
+
+    // Outputs built per triangulation, and the constellation edge length (CSS pixels)
+    render_mode: RenderMode,
+    constellation_distance: f32,
+
+    // Light angle for the light gradient attribute (radians, canvas coordinates)
+    light_angle: f32,
+    // Unit direction toward the light and ambient floor for the diffuse attribute
+    light: ([f32; 3], f32),
+
+    // Per-point attribute scratch (reused each frame)
+    occlusion: Vec<f32>,
+    heat_values: Vec<f32>,
+    motion: Vec<f32>,
+    culled_obstacles: Vec<Obstacle>,
+    flagged_exclusions: Vec<ExclusionRect>,
+    // Positions at the last buffer build (for STROKE_MOTION)
+    prev_positions: Vec<(f32, f32)>,
+
+    // Fraction of the previous output position kept per build (0 = off), and the
+    // smoothed copies of the points buffers are built from
+    output_smoothing: f32,
+    smoothed_points: Vec<Point>,
+
+    // Active shockwaves and gravity well for overlays (see `get_effects_buffer`)
+    effects_buffer: Vec<f32>,
+    // Bumped when views into the buffers above may be stale (see `memory_generation`)
+    memory: MemoryGeneration,
+
+    // Curl-noise drift of base positions (None = disabled)
+    flow_field: Option<FlowField>,
+
+    // Noise shimmer on displacement (None = disabled)
+    turbulence: Option<Turbulence>,
+
+    // Heat field (None = disabled)
+    heat: Option<HeatField>,
+
+    // Ambient effects while pointer input is idle (None = disabled)
+    idle: Option<IdleBehavior>,
+
+    // Sleep once motion settles (None = always simulate)
+    settle: Option<Settle>,
+
+    // Reuse the last build while points stay put (None = build at every cadence tick)
+    quiescence: Option<Quiescence>,
+    // Whether the last tick rebuilt the output buffers
+    buffers_changed: bool,
+
+    // Rolling impulse-per-region statistics (None = disabled)
+    interaction_metrics: Option<InteractionMetrics>,
+
+    // Relative density for spawning and rebalancing (None = uniform)
+    density_map: Option<DensityMap>,
+    // Layout of point sets (structured lattices replace the whole set)
+    distribution: Distribution,
+
+    // Last-good snapshot and rollback on invalid state (None = disabled)
+    watchdog: Option<Watchdog>,
+
+    // Effects queued for a simulated time
+    schedule: Schedule,
+
+    // Last handle given to a named effect (shockwave, gravity well, scheduled effect)
+    next_effect_id: u32,
+
+    // Periodic distribution upkeep for long-running pages (None = disabled)
+    maintenance: Option<Maintenance>,
+
+    // Time-of-day / seasonal curves and their current values
+    environment: Environment,
+
+    // Host-sampled audio band levels and their responses
+    audio: AudioReactivity,
+
+    // Height field normals rasterized on request, rebuilt every few frames
+    normal_map: NormalMap,
+
+    // Constant acceleration on displacement (CSS pixels per frame²)
+    global_force: (f32, f32),
+
+    // Device tilt in [-1, 1] (drift bias) and the height falloff center shift it set
+    tilt: (f32, f32),
+    light_shift: (f32, f32),
+
+    // Scroll parallax offset applied so far and the one it eases toward (CSS pixels)
+    scroll_offset: (f32, f32),
+    scroll_target: (f32, f32),
+
+    // Points attached to the cursor until stretched too far (None = disabled)
+    sticky: Option<StickyCursor>,
+
+    // Spring stiffness along Delaunay edges (None = points move independently)
+    mesh_springs: Option<f32>,
+
+    // Point count from canvas area, re-evaluated on resize (from presets)
+    point_budget: PointBudget,
+
+    // Degradation ladder: current tier, the point count it scales, and the controller
+    // stepping it from reported frame times (None = manual)
+    quality_tier: u32,
+    requested_points: usize,
+    adaptive_quality: Option<AdaptiveQuality>,
+
+    // Allocator calls during the last tick
+    #[cfg(feature = "alloc-audit")]
+    tick_allocations: u32,
 }
 
 #[wasm_bindgen]
@@ -55,7 +280,7 @@ impl Simulation {
         let height = Self::validate_dimension(height);
         let point_count = Self::validate_point_count(point_count);
 
-        let mut rng = Rng::new(seed);
+        let mut rng = RngStreams::new(seed);
         let noise_scale = DEFAULT_NOISE_SCALE;
         let height_intensity = DEFAULT_HEIGHT_INTENSITY;
 
@@ -63,43 +288,164 @@ impl Simulation {
         let mut points = Vec::with_capacity(point_count);
         for _ in 0..point_count {
             points.push(Point::new_random(
-                &mut rng,
+                &mut rng.placement,
+                &mut rng.drift,
                 width,
                 height,
                 noise_scale,
                 height_intensity,
+                (0.0, 0.0),
             ));
         }
 
         // Default cell size based on mouse radius
         let default_cell_size = DEFAULT_MOUSE_RADIUS / 2.0;
 
-        Self {
+        let mut simulation = Self {
             points,
             width,
             height,
             rng,
+            time: 0.0,
+            frame: 0,
+            paused: false,
+            last_speed: 1.0,
+            max_catchup: DEFAULT_MAX_CATCHUP_FRAMES,
+            catchup_backlog: 0.0,
+            fixed_step: None,
+            step_accumulator: 0.0,
+            substeps: 1,
+            time_scale: 1.0,
+            recorder: None,
+            pixel_ratio: 1.0,
+            boundary: Boundary::new(),
             noise_scale,
             height_intensity,
             mouse: MouseState::new(),
+            pointers: PointerSet::new(),
+            input_filter: None,
+            pointer_events: PointerEventQueue::new(),
+            mouse_path: Vec::new(),
             spring_back: DEFAULT_SPRING_BACK,
             damping: DEFAULT_DAMPING,
             velocity_influence: DEFAULT_VELOCITY_INFLUENCE,
+            density_equalization_enabled: false,
+            density_equalization_strength: DEFAULT_DENSITY_EQUALIZATION_STRENGTH,
+            force_mask: HeightMask::ALL,
+            effect_filters: [EffectFilter::ALL; 3],
+            depth_mode: DepthMode::Off,
+            depth_parallax: 0.0,
             shockwaves: ShockwaveManager::new(),
+            flips: FlipManager::new(),
+            obstacles: ObstacleSet::new(),
+            anchors: Anchors::new(),
+            exclusions: ExclusionZones::new(),
+            emitters: EmitterManager::new(),
             gravity_well: None,
+            well_throw: None,
             spatial_grid: SpatialGrid::new(width, height, default_cell_size),
             buffers: TriangulationBuffers::new(),
+            render_mode: RenderMode::Mesh,
+            constellation_distance: DEFAULT_CONSTELLATION_DISTANCE,
+            light_angle: DEFAULT_LIGHT_ANGLE,
+            light: (normalized(DEFAULT_LIGHT_VECTOR), DEFAULT_AMBIENT),
+            occlusion: Vec::new(),
+            heat_values: Vec::new(),
+            motion: Vec::new(),
+            culled_obstacles: Vec::new(),
+            flagged_exclusions: Vec::new(),
+            prev_positions: Vec::new(),
+            output_smoothing: 0.0,
+            smoothed_points: Vec::new(),
+            effects_buffer: Vec::new(),
+            memory: MemoryGeneration::new(),
+            heat: None,
+            idle: None,
+            settle: None,
+            quiescence: None,
+            buffers_changed: true,
+            interaction_metrics: None,
+            flow_field: None,
+            turbulence: None,
+            watchdog: None,
+            schedule: Schedule::new(),
+            next_effect_id: 0,
+            maintenance: None,
+            environment: Environment::new(),
+            audio: AudioReactivity::new(),
+            normal_map: NormalMap::new(),
+            global_force: (0.0, 0.0),
+            tilt: (0.0, 0.0),
+            light_shift: (0.0, 0.0),
+            scroll_offset: (0.0, 0.0),
+            scroll_target: (0.0, 0.0),
+            sticky: None,
+            mesh_springs: None,
+            point_budget: PointBudget::OFF,
+            quality_tier: MAX_QUALITY_TIER,
+            requested_points: point_count,
+            adaptive_quality: None,
+            density_map: None,
+            distribution: Distribution::Random,
+            #[cfg(feature = "alloc-audit")]
+            tick_allocations: 0,
+        };
+        // Point queries work before the first update
+        simulation.rebuild_spatial_grid();
+        simulation
+    }
+
+    /// Create a simulation from one options object instead of the constructor + setters:
+    /// { width, height (required), pointCount (default 80), seed (default 0),
+    /// devicePixelRatio (default 1), distribution: { mode, jitter } (see `set_distribution`)
+    /// or { mode: 3, centerX, centerY (default canvas center), falloff } (see
+    /// `set_spiral_distribution`), mouse: { radius, strength, mode } (defaults 150, 80,
+    /// push; `tick` and `set_mouse_state` replace them) } plus the `update_params` groups.
+    /// Throws on the first invalid field
+    #[wasm_bindgen]
+    pub fn from_config(config: JsValue) -> Result<Simulation, JsError> {
+        let options = SimulationConfig::from_js(config.clone())?;
+        let mut simulation = Simulation::new(
+            options.width,
+            options.height,
+            options.point_count as usize,
+            options.seed,
+        );
+        let mut preset = simulation.current_preset("");
+        preset.merge_js(&config)?;
+
+        let distribution = &options.distribution;
+        if distribution.mode == distribution::SPIRAL_MODE {
+            let x = distribution.center_x.unwrap_or(simulation.width / 2.0);
+            let y = distribution.center_y.unwrap_or(simulation.height / 2.0);
+            simulation.set_spiral_distribution(x, y, distribution.falloff);
+        } else {
+            simulation.set_distribution(distribution.mode, distribution.jitter);
+        }
+        if simulation.distribution.is_structured() {
+            simulation.set_point_count(options.point_count as usize, options.seed);
         }
+
+        let mouse = &options.mouse;
+        simulation.mouse.radius = mouse.radius;
+        simulation.mouse.strength = mouse.strength;
+        simulation.mouse.mode = MouseMode::from_u32(mouse.mode);
+
+        simulation.set_device_pixel_ratio(options.device_pixel_ratio);
+        simulation.apply_preset_values(&preset);
+        // Size the grid for the configured radius and pixel ratio
+        simulation.rebuild_spatial_grid();
+        Ok(simulation)
     }
 
     // ========== Validation Helpers ==========
 
     fn validate_point_count(count: usize) -> usize {
-        count.max(MIN_POINT_COUNT).min(MAX_POINT_COUNT)
+        count.clamp(MIN_POINT_COUNT, MAX_POINT_COUNT)
     }
 
     fn validate_dimension(value: f32) -> f32 {
-        value.max(MIN_DIMENSION).min(MAX_DIMENSION)
+        value.clamp(MIN_DIMENSION, MAX_DIMENSION)
     }
 
     // ========== Configuration ==========
@@ -107,16 +453,48 @@ impl Simulation {
     /// Set noise parameters and regenerate heights
     #[wasm_bindgen]
     pub fn set_noise_params(&mut self, noise_scale: f32, height_intensity: f32) {
-        self.noise_scale = noise_scale.max(0.0001).min(1.0);
+        self.record(InputEvent::NoiseParams {
+            noise_scale,
+            height_intensity,
+        });
+        self.noise_scale = noise_scale.clamp(0.0001, 1.0);
         self.height_intensity = height_intensity.clamp(0.0, 2.0);
 
-        // Regenerate static heights
+        self.regenerate_heights();
+    }
+
+    /// Set device pixel ratio (canvas pixels per CSS pixel)
+    /// Mouse radius/strength, effect sizes, drift speed and noise feature size are
+    /// specified in CSS pixels and scaled by this ratio; ghost margins are relative already
+    #[wasm_bindgen]
+    pub fn set_device_pixel_ratio(&mut self, dpr: f32) {
+        self.record(InputEvent::DevicePixelRatio { dpr });
+        let dpr = if dpr.is_finite() { dpr.clamp(0.25, 8.0) } else { 1.0 };
+        if dpr != self.pixel_ratio {
+            self.pixel_ratio = dpr;
+            if let Some(ref mut mesh) = self.buffers.incremental {
+                mesh.set_pixel_ratio(dpr);
+            }
+            self.regenerate_heights();
+            self.apply_point_budget();
+        }
+    }
+
+    /// Noise scale in canvas pixels (terrain features keep their CSS-pixel size)
+    fn effective_noise_scale(&self) -> f32 {
+        self.noise_scale / self.pixel_ratio
+    }
+
+    /// Regenerate static heights from current noise parameters
+    fn regenerate_heights(&mut self) {
+        let noise_scale = self.effective_noise_scale();
         for point in &mut self.points {
             point.regenerate_height(
                 self.width,
                 self.height,
-                self.noise_scale,
+                noise_scale,
                 self.height_intensity,
+                self.light_shift,
             );
         }
     }
@@ -132,197 +510,3531 @@ impl Simulation {
         strength: f32,
         mode: u32,
     ) {
+        self.record(InputEvent::MouseState {
+            x,
+            y,
+            in_canvas,
+            radius,
+            strength,
+            mode,
+        });
+        let (x, y) = self.filter_pointer(x, y, in_canvas, 1.0);
         self.mouse.update(x, y, in_canvas, radius, strength, mode);
     }
 
+    /// Set an extra pointer (e.g. a touch) that interacts alongside the mouse
+    /// New ids start being tracked (up to MAX_POINTERS); inactive pointers exert no force
+    /// Positions are not passed through the input filter
+    #[wasm_bindgen]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_pointer(
+        &mut self,
+        id: u32,
+        x: f32,
+        y: f32,
+        active: bool,
+        radius: f32,
+        strength: f32,
+        mode: u32,
+    ) {
+        self.record(InputEvent::Pointer {
+            id,
+            x,
+            y,
+            active,
+            radius,
+            strength,
+            mode,
+        });
+        self.pointers.set(id, x, y, active, radius, strength, mode, &self.mouse);
+    }
+
+    /// Queue a raw pointer event for the next tick (call from every pointermove, including
+    /// `getCoalescedEvents()` samples); t: timestamp in milliseconds
+    /// The next tick takes the mouse position from the last event, measures velocity
+    /// from the timestamps and sweeps mouse forces along the whole path
+    /// Pressed pens scale strength with pressure (0.5, a pressed mouse, is neutral)
+    #[wasm_bindgen]
+    pub fn push_pointer_event(&mut self, x: f32, y: f32, t: f64, pressure: f32, buttons: u32) {
+        self.record(InputEvent::PointerEvent {
+            x,
+            y,
+            t,
+            pressure,
+            buttons,
+        });
+        self.pointer_events.push(PointerEvent {
+            x,
+            y,
+            t,
+            pressure,
+            buttons,
+        });
+    }
+
+    /// Stop tracking an extra pointer
+    #[wasm_bindgen]
+    pub fn remove_pointer(&mut self, id: u32) {
+        self.record(InputEvent::RemovePointer { id });
+        self.pointers.remove(id);
+    }
+
+    /// Enable one-euro smoothing of pointer positions (min_cutoff <= 0 disables)
+    /// min_cutoff: Hz, lower = steadier at rest (~1.0); beta: higher = less lag when moving (~0.01)
+    #[wasm_bindgen]
+    pub fn set_input_filter(&mut self, min_cutoff: f32, beta: f32) {
+        self.record(InputEvent::InputFilter { min_cutoff, beta });
+        self.input_filter = if min_cutoff > 0.0 {
+            Some(PointerFilter::new(min_cutoff.min(100.0), beta.clamp(0.0, 10.0)))
+        } else {
+            None
+        };
+    }
+
+    /// Run a pointer sample through the input filter (if enabled)
+    fn filter_pointer(&mut self, x: f32, y: f32, in_canvas: bool, delta_time: f32) -> (f32, f32) {
+        match self.input_filter {
+            Some(ref mut filter) => filter.apply(x, y, in_canvas, delta_time),
+            None => (x, y),
+        }
+    }
+
+    /// Configure pointer velocity smoothing
+    /// smoothing: fraction of previous velocity kept per update (default 0.6)
+    /// off_canvas_decay: velocity multiplier while outside the canvas (default 0.9)
+    /// raw_velocity: ignore smoothing and use the latest pointer delta directly
+    #[wasm_bindgen]
+    pub fn set_mouse_dynamics(
+        &mut self,
+        smoothing: f32,
+        off_canvas_decay: f32,
+        raw_velocity: bool,
+    ) {
+        self.mouse.set_dynamics(smoothing, off_canvas_decay, raw_velocity);
+    }
+
     /// Set physics parameters
     #[wasm_bindgen]
     pub fn set_physics_params(&mut self, spring_back: f32, damping: f32, velocity_influence: f32) {
+        self.record(InputEvent::PhysicsParams {
+            spring_back,
+            damping,
+            velocity_influence,
+        });
         self.spring_back = spring_back;
         self.damping = damping;
         self.velocity_influence = velocity_influence;
     }
 
-    // ========== Effects ==========
+    /// Enable or disable the density equalization force
+    /// Points in overcrowded grid cells drift toward emptier neighbors
+    /// A non-finite strength keeps the current one
+    #[wasm_bindgen]
+    pub fn set_density_equalization(&mut self, enabled: bool, strength: f32) {
+        self.record(InputEvent::DensityEqualization { enabled, strength });
+        self.density_equalization_enabled = enabled;
+        if strength.is_finite() {
+            self.density_equalization_strength = strength.clamp(0.0, 10.0);
+        }
+    }
 
-    /// Trigger a shockwave at position
+    /// Set the quality tier, 0 (cheapest) to 4 (full detail, the default)
+    /// Lower tiers simulate fewer points (4: 100%, 3: 100%, 2: 75%, 1: 50%, 0: 35% of the
+    /// requested count), triangulate every 2nd (tier 1) or 3rd (tier 0) tick, drop
+    /// ambient effects (flow field, heat field, turbulence, density equalization, mesh
+    /// springs; tiers 0-2) and optional buffer work (Voronoi, bundled edges, height blur,
+    /// shadows; below 4)
+    /// The adaptive controller, if enabled, continues from this tier
     #[wasm_bindgen]
-    pub fn trigger_shockwave(&mut self, x: f32, y: f32, strength: f32) {
-        self.shockwaves.add(x, y, strength);
+    pub fn set_quality_tier(&mut self, tier: u32) {
+        self.record(InputEvent::QualityTier { tier });
+        let tier = tier.min(MAX_QUALITY_TIER);
+        if tier == self.quality_tier {
+            return;
+        }
+        self.quality_tier = tier;
+        self.buffers.lean = !QualityTier::get(tier).rich_buffers;
+        self.fill_requested_points();
     }
 
-    /// Set or clear gravity well
+    /// Get the current quality tier
     #[wasm_bindgen]
-    pub fn set_gravity_well(&mut self, x: f32, y: f32, active: bool, attract: bool) {
-        if active {
-            self.gravity_well = Some(if attract {
-                GravityWell::attract(x, y)
-            } else {
-                GravityWell::repel(x, y)
-            });
-        } else {
-            self.gravity_well = None;
+    pub fn get_quality_tier(&self) -> u32 {
+        self.quality_tier
+    }
+
+    /// Let frame times reported through `report_frame_time` step the quality tier
+    /// target_frame_ms: frame budget, e.g. 16.7 for 60 fps; the tier steps down after
+    /// sustained frames 20% over it and back up after longer runs 30% under it
+    #[wasm_bindgen]
+    pub fn set_adaptive_quality(&mut self, enabled: bool, target_frame_ms: f32) {
+        self.record(InputEvent::AdaptiveQuality {
+            enabled,
+            target_frame_ms,
+        });
+        let valid = target_frame_ms.is_finite() && target_frame_ms > 0.0;
+        self.adaptive_quality = (enabled && valid).then(|| AdaptiveQuality::new(target_frame_ms));
+    }
+
+    /// Report the host's measured frame time (milliseconds) to the adaptive controller
+    /// Returns the quality tier in effect afterwards
+    #[wasm_bindgen]
+    pub fn report_frame_time(&mut self, frame_ms: f32) -> u32 {
+        self.record(InputEvent::FrameTime { frame_ms });
+        if !(frame_ms.is_finite() && frame_ms >= 0.0) {
+            return self.quality_tier;
         }
+        let tier = self.quality_tier;
+        let next = self.adaptive_quality.as_mut().and_then(|q| q.report(frame_ms, tier));
+        if let Some(next) = next {
+            self.unrecorded(|sim| sim.set_quality_tier(next));
+        }
+        self.quality_tier
     }
 
-    /// Update gravity well position
+    /// Set the local time: hour (0..24, fractional) and day of the year (0 = January 1st)
+    /// Curves map it to drift speed, palette warmth (see `get_palette_warmth`) and
+    /// height intensity; by default mornings are calm and cool, evenings warm and active
     #[wasm_bindgen]
-    pub fn update_gravity_well_position(&mut self, x: f32, y: f32) {
-        if let Some(ref mut well) = self.gravity_well {
-            well.set_position(x, y);
+    pub fn set_environment(&mut self, hour: f32, day_of_year: f32) {
+        self.record(InputEvent::Environment { hour, day_of_year });
+        if hour.is_finite() && day_of_year.is_finite() {
+            self.environment.set_time(hour, day_of_year);
         }
     }
 
-    // ========== Simulation Control ==========
+    /// Replace the curves of one environment target
+    /// target: 0 = drift speed multiplier, 1 = palette warmth (0 = cool, 1 = warm),
+    /// 2 = height intensity multiplier
+    /// daily: keys spread evenly over 24 hours from midnight; seasonal: keys spread evenly
+    /// over the year from January 1st; both wrap around and multiply
+    /// Returns false (changing nothing) for an unknown target or an empty / too long curve
+    #[wasm_bindgen]
+    pub fn set_environment_curve(&mut self, target: u32, daily: &[f32], seasonal: &[f32]) -> bool {
+        self.record(InputEvent::EnvironmentCurve {
+            target,
+            daily: daily.to_vec(),
+            seasonal: seasonal.to_vec(),
+        });
+        let (Some(target), Some(daily), Some(seasonal)) = (
+            EnvironmentTarget::from_u32(target),
+            Curve::new(daily),
+            Curve::new(seasonal),
+        ) else {
+            return false;
+        };
+        self.environment.set_curves(target, daily, seasonal);
+        true
+    }
 
-    /// Resize the simulation
+    /// Palette warmth from the environment for the host theme (0 = cool, 1 = warm;
+    /// 0.5 until `set_environment` is called)
     #[wasm_bindgen]
-    pub fn resize(&mut self, new_width: f32, new_height: f32) {
-        let new_width = Self::validate_dimension(new_width);
-        let new_height = Self::validate_dimension(new_height);
+    pub fn get_palette_warmth(&self) -> f32 {
+        self.environment.value(EnvironmentTarget::PaletteWarmth)
+    }
 
-        if self.width > 0.0 && self.height > 0.0 {
-            let scale_x = new_width / self.width;
-            let scale_y = new_height / self.height;
+    /// Feed audio band levels (0..1, e.g. sampled from a WebAudio analyser each frame)
+    /// Bass pushes points outward from the center, mids speed up drift and treble
+    /// drives the AUDIO_JITTER stroke attribute; falling levels are smoothed
+    #[wasm_bindgen]
+    pub fn set_audio_levels(&mut self, bass: f32, mid: f32, treble: f32) {
+        self.record(InputEvent::AudioLevels { bass, mid, treble });
+        self.audio.set_levels(bass, mid, treble);
+    }
+
+    /// Configure audio responses at full level: pulse (CSS pixels per frame of outward
+    /// push, default 1), drift_boost (extra drift speed fraction, default 1.5) and
+    /// jitter (stroke offset amplitude in CSS pixels, default 2); 0 disables a response
+    #[wasm_bindgen]
+    pub fn set_audio_response(&mut self, pulse: f32, drift_boost: f32, jitter: f32) {
+        self.record(InputEvent::AudioResponse {
+            pulse,
+            drift_boost,
+            jitter,
+        });
+        self.audio.pulse = pulse.clamp(0.0, MAX_AUDIO_RESPONSE);
+        self.audio.drift_boost = drift_boost.clamp(0.0, MAX_AUDIO_RESPONSE);
+        self.audio.jitter = jitter.clamp(0.0, MAX_AUDIO_RESPONSE);
+    }
+
+    /// Apply a constant force to every point's displacement (CSS pixels per frame²),
+    /// e.g. (0, 0.5) to let the mesh sag downward; the spring still pulls points back,
+    /// so they settle offset along the force. Components are clamped to ±10, (0, 0) = off
+    #[wasm_bindgen]
+    pub fn set_global_force(&mut self, fx: f32, fy: f32) {
+        self.record(InputEvent::GlobalForce { fx, fy });
+        let clamp = |f: f32| {
+            if f.is_finite() {
+                f.clamp(-MAX_GLOBAL_FORCE, MAX_GLOBAL_FORCE)
+            } else {
+                0.0
+            }
+        };
+        self.global_force = (clamp(fx), clamp(fy));
+    }
+
+    /// Set the device tilt from orientation sensors, each axis normalized to [-1, 1]
+    /// (e.g. gamma / 90 and beta / 90); the mesh drifts gently downhill and the height
+    /// falloff center shifts with it, regenerating heights when it moved noticeably
+    #[wasm_bindgen]
+    pub fn set_tilt(&mut self, gx: f32, gy: f32) {
+        self.record(InputEvent::Tilt { gx, gy });
+        let clamp = |g: f32| {
+            if g.is_finite() {
+                g.clamp(-1.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+        self.tilt = (clamp(gx), clamp(gy));
+
+        let shift = (self.tilt.0 * TILT_LIGHT_SHIFT, self.tilt.1 * TILT_LIGHT_SHIFT);
+        let moved = (shift.0 - self.light_shift.0).abs().max((shift.1 - self.light_shift.1).abs());
+        let leveled = shift == (0.0, 0.0) && shift != self.light_shift;
+        if moved > TILT_LIGHT_SHIFT_EPSILON || leveled {
+            self.light_shift = shift;
+            self.regenerate_heights();
+        }
+    }
+
+    /// Scroll the field with the page: (x, y) is the target offset in CSS pixels, e.g.
+    /// (0, -window.scrollY * 0.3) for a slow parallax. The mesh eases toward it, moving
+    /// every rest position and wrapping at the edges; in wind tunnel mode only the axis
+    /// across the flow scrolls
+    #[wasm_bindgen]
+    pub fn set_scroll_offset(&mut self, x: f32, y: f32) {
+        self.record(InputEvent::ScrollOffset { x, y });
+        if x.is_finite() && y.is_finite() {
+            self.scroll_target = (x, y);
+        }
+    }
 
+    /// Make the cursor sticky: points whose rest position it touches attach and follow
+    /// it until their tension (spring back × stretch in CSS pixels) exceeds break_force,
+    /// then snap back home. Disabling (or the mouse leaving) releases every point
+    #[wasm_bindgen]
+    pub fn set_sticky_cursor(&mut self, enabled: bool, break_force: f32) {
+        self.record(InputEvent::StickyCursor {
+            enabled,
+            break_force,
+        });
+        self.sticky = enabled.then(|| StickyCursor {
+            break_force: break_force.max(0.0),
+        });
+        if !enabled {
             for point in &mut self.points {
-                point.scale(scale_x, scale_y);
+                point.release();
             }
         }
+    }
 
-        self.width = new_width;
-        self.height = new_height;
+    /// Connect each point to its Delaunay neighbors with springs so the mesh deforms
+    /// like a membrane: a pushed point drags its neighbors along
+    /// stiffness: fraction of edge stretch corrected per frame (0..0.5)
+    /// Rest lengths are taken when an edge first appears; edges come from the last triangulation
+    #[wasm_bindgen]
+    pub fn set_mesh_springs(&mut self, enabled: bool, stiffness: f32) {
+        self.record(InputEvent::MeshSprings { enabled, stiffness });
+        self.mesh_springs = enabled.then(|| stiffness.clamp(0.0, MAX_MESH_SPRING_STIFFNESS));
+        self.buffers.collect_edges = enabled;
     }
 
-    /// Set point count (add or remove points)
+    /// Restrict mouse, gravity well and shockwave forces to points with z in [min_z, max_z]
+    /// z runs 0..height intensity; pass -Infinity/Infinity (or NaN) to remove a bound
     #[wasm_bindgen]
-    pub fn set_point_count(&mut self, count: usize, seed: u32) {
-        let count = Self::validate_point_count(count);
-        self.rng = Rng::new(seed);
+    pub fn set_force_height_mask(&mut self, min_z: f32, max_z: f32) {
+        self.record(InputEvent::ForceHeightMask { min_z, max_z });
+        let min_z = if min_z.is_nan() { f32::NEG_INFINITY } else { min_z };
+        let max_z = if max_z.is_nan() { f32::INFINITY } else { max_z };
+        self.force_mask = HeightMask {
+            min_z: min_z.min(max_z),
+            max_z: min_z.max(max_z),
+        };
+    }
 
-        // Add new points if needed
-        while self.points.len() < count {
-            self.points.push(Point::new_random(
-                &mut self.rng,
-                self.width,
-                self.height,
-                self.noise_scale,
-                self.height_intensity,
-            ));
+    /// Narrow which points an effect moves (0 = mouse and touch pointers, 1 = gravity
+    /// well, 2 = shockwaves including implosions, emitters and rain), e.g. an intro
+    /// shockwave that leaves anchored logo points alone
+    /// ignore_pinned: skip pinned and anchored points; layer_mask: bit per point layer
+    /// (see `set_point_layer`); depth in 0..1, inclusive. Returns false for an unknown effect
+    #[wasm_bindgen]
+    pub fn set_effect_filter(
+        &mut self,
+        effect: u32,
+        ignore_pinned: bool,
+        layer_mask: u32,
+        min_depth: f32,
+        max_depth: f32,
+    ) -> bool {
+        let Some(target) = EffectTarget::from_u32(effect) else {
+            return false;
+        };
+        let min_depth = if min_depth.is_nan() { 0.0 } else { min_depth };
+        let max_depth = if max_depth.is_nan() { 1.0 } else { max_depth };
+        self.effect_filters[target as usize] = EffectFilter {
+            ignore_pinned,
+            layer_mask,
+            depth_range: (min_depth.min(max_depth), min_depth.max(max_depth)),
+        };
+        true
+    }
+
+    /// Height mask combined with an effect's filter
+    fn force_filter(&self, target: EffectTarget) -> ForceFilter {
+        ForceFilter {
+            height: self.force_mask,
+            effect: self.effect_filters[target as usize],
         }
+    }
 
-        // Remove excess points
-        self.points.truncate(count);
+    /// Set boundary mode (0 = wrap, 1 = wind tunnel)
+    #[wasm_bindgen]
+    pub fn set_boundary_mode(&mut self, mode: u32) {
+        self.record(InputEvent::BoundaryMode { mode });
+        self.boundary.mode = BoundaryMode::from_u32(mode);
     }
 
-    /// Update point positions
+    /// Set wind tunnel flow parameters
+    /// direction: 0 = left to right, 1 = right to left, 2 = top to bottom, 3 = bottom to top
+    /// Non-finite speed or turbulence keeps the current value
     #[wasm_bindgen]
-    pub fn update_points(&mut self, delta_time: f32, speed: f32) {
-        let delta_time = delta_time.clamp(0.0, 10.0);
-        let speed = speed.clamp(0.0, 10.0);
+    pub fn set_wind_tunnel_params(&mut self, direction: u32, flow_speed: f32, turbulence: f32) {
+        self.record(InputEvent::WindTunnelParams {
+            direction,
+            flow_speed,
+            turbulence,
+        });
+        let tunnel = &mut self.boundary.tunnel;
+        tunnel.direction = FlowDirection::from_u32(direction);
+        if flow_speed.is_finite() {
+            tunnel.flow_speed = flow_speed.clamp(0.0, 50.0);
+        }
+        if turbulence.is_finite() {
+            tunnel.turbulence = turbulence.clamp(0.0, 50.0);
+        }
+    }
 
-        // Update shockwaves
-        self.shockwaves.update();
+    /// Set per-point parallax depth
+    /// mode: 0 = off, 1 = random, 2 = noise regions
+    /// parallax: 0-1, how strongly depth scales drift, mouse response and point size
+    #[wasm_bindgen]
+    pub fn set_point_depth(&mut self, mode: u32, parallax: f32) {
+        self.record(InputEvent::PointDepth { mode, parallax });
+        self.depth_mode = DepthMode::from_u32(mode);
+        self.depth_parallax = parallax.clamp(0.0, 1.0);
 
-        // Update point positions and physics
         for point in &mut self.points {
-            point.update_position(delta_time, speed, self.width, self.height);
-            point.apply_spring(self.spring_back, self.damping);
+            point.depth = self.depth_mode.sample(point, &mut self.rng.depth);
         }
+    }
 
-        // Rebuild spatial grid
-        self.rebuild_spatial_grid();
+    /// Set optional vertex attributes (bit flags, see `triangulation::vertex_attr`)
+    /// 1 = depth: +1 float per triangle vertex, +2 floats (size scale, depth) per point
+    /// 2 = occlusion: +1 float per triangle vertex
+    /// 4 = flip: +3 floats (phase, axis x, axis y) per triangle vertex
+    /// 8 = light gradient: +1 float per triangle vertex (see `set_light_direction`)
+    /// 16 = heat: +1 float per triangle / indexed vertex (see `set_heat_field`)
+    /// 32 = stroke motion: +1 float per stroke vertex (endpoint movement, CSS pixels)
+    /// 64 = exclusion: +1 float per triangle vertex (see `add_exclusion_rect`)
+    /// 128 = shadow: +1 float per triangle vertex, light attenuation from higher
+    ///       neighbors toward the light (see `set_light_direction`)
+    /// 256 = audio jitter: +1 float per stroke vertex (see `set_audio_levels`)
+    /// 512 = weighted centroid: +3 floats per triangle vertex, depth-weighted centroid
+    ///       (x, y) and mean weight (see `set_point_depth`)
+    /// 1024 = facet seed: +1 float per triangle vertex, per-facet random value in [0, 1)
+    /// 2048 = face normal: +3 floats per triangle vertex, unit normal from the heights
+    /// 4096 = diffuse: +1 float per triangle vertex, Lambert shading (see `set_light`)
+    #[wasm_bindgen]
+    pub fn set_vertex_layout(&mut self, flags: u32) {
+        self.buffers.layout.flags = flags;
+        self.output_changed();
+    }
 
-        // Apply effects using spatial queries
-        apply_mouse_influence(
-            &mut self.points,
-            &self.mouse,
-            self.velocity_influence,
-            &self.spatial_grid,
-        );
+    /// Enable a curl-noise flow field moving base positions (divergence-free, so
+    /// points swirl in large organic currents without bunching up)
+    /// scale: noise frequency per CSS pixel; strength: CSS pixels per frame;
+    /// time_speed: noise evolution per frame
+    #[wasm_bindgen]
+    pub fn set_flow_field(&mut self, enabled: bool, scale: f32, strength: f32, time_speed: f32) {
+        self.record(InputEvent::FlowField {
+            enabled,
+            scale,
+            strength,
+            time_speed,
+        });
+        self.flow_field = enabled.then(|| FlowField {
+            scale: scale.clamp(0.0, 1.0),
+            strength: strength.clamp(0.0, MAX_FLOW_FIELD_STRENGTH),
+            time_speed: time_speed.clamp(0.0, MAX_FLOW_FIELD_TIME_SPEED),
+        });
+    }
 
-        if let Some(ref well) = self.gravity_well {
-            apply_gravity_well(&mut self.points, well, &self.spatial_grid);
-        }
+    /// Shimmer the mesh with time-varying noise pushing each point's displacement
+    /// amount: CSS pixels per frame (0 disables); scale: noise frequency per CSS pixel;
+    /// speed: noise evolution per frame
+    #[wasm_bindgen]
+    pub fn set_turbulence(&mut self, amount: f32, scale: f32, speed: f32) {
+        self.record(InputEvent::Turbulence {
+            amount,
+            scale,
+            speed,
+        });
+        self.turbulence = (amount > 0.0).then(|| Turbulence {
+            amount: amount.min(MAX_TURBULENCE_AMOUNT),
+            scale: scale.clamp(0.0, 1.0),
+            speed: speed.clamp(0.0, MAX_TURBULENCE_SPEED),
+        });
+    }
 
-        for wave in self.shockwaves.iter() {
-            apply_shockwave(&mut self.points, wave, &self.spatial_grid);
+    /// Enable the heat field: interactions deposit heat that diffuses and decays,
+    /// jittering points in hot regions and feeding the HEAT vertex attribute
+    /// diffusion: 0..1 neighbor exchange per frame; retention: 0..1 heat kept per frame
+    #[wasm_bindgen]
+    pub fn set_heat_field(&mut self, enabled: bool, diffusion: f32, retention: f32) {
+        self.record(InputEvent::HeatField {
+            enabled,
+            diffusion,
+            retention,
+        });
+        if !enabled {
+            self.heat = None;
+            return;
         }
+        let heat = self.heat.get_or_insert_with(HeatField::new);
+        heat.diffusion = diffusion.clamp(0.0, 1.0);
+        heat.retention = retention.clamp(0.0, 1.0);
     }
 
-    /// Rebuild spatial grid with optimal cell size
-    fn rebuild_spatial_grid(&mut self) {
-        // Calculate optimal cell size based on effect radii
-        let max_shockwave_radius = self.shockwaves.max_radius(SHOCKWAVE_WAVE_WIDTH);
-        let gravity_range = if self.gravity_well.is_some() {
-            GRAVITY_WELL_MAX_RANGE
+    /// Record where interactions move points (impulse per region per second) over the
+    /// last `window_minutes` of simulated time, up to 10 (0 disables and discards)
+    /// Read it with `get_interaction_heatmap`
+    #[wasm_bindgen]
+    pub fn set_interaction_metrics(&mut self, window_minutes: f32) {
+        self.record(InputEvent::InteractionMetrics { window_minutes });
+        self.interaction_metrics = if window_minutes > 0.0 && window_minutes.is_finite() {
+            let minutes = window_minutes.min(MAX_INTERACTION_WINDOW_MINUTES);
+            Some(InteractionMetrics::new((minutes * 60.0).round() as usize))
         } else {
-            0.0
+            None
         };
-        let max_radius = self.mouse.radius.max(max_shockwave_radius).max(gravity_range);
-        let cell_size = (max_radius / 2.0).max(50.0);
+    }
 
-        // Resize if needed
-        if (self.spatial_grid.cell_size - cell_size).abs() > 1.0
-            || self.spatial_grid.width != self.width
-            || self.spatial_grid.height != self.height
-        {
-            self.spatial_grid
-                .resize(self.width, self.height, cell_size);
-        } else {
-            self.spatial_grid.clear();
-        }
+    /// Average interaction impulse per second (displacement velocity change in CSS
+    /// pixels per frame, summed over points) in `cols` x `rows` canvas regions, rows top
+    /// to bottom; sides are clamped to 1..=16, all zero while metrics are disabled
+    #[wasm_bindgen]
+    pub fn get_interaction_heatmap(&self, cols: usize, rows: usize) -> Float32Array {
+        let cols = cols.clamp(1, INTERACTION_METRICS_GRID);
+        let rows = rows.clamp(1, INTERACTION_METRICS_GRID);
+        let regions = match self.interaction_metrics {
+            Some(ref metrics) => metrics.heatmap(cols, rows),
+            None => vec![0.0; cols * rows],
+        };
+        Float32Array::from(&regions[..])
+    }
 
-        // Insert all points
-        for (i, point) in self.points.iter().enumerate() {
-            self.spatial_grid.insert(i, point.x, point.y);
+    /// Set the light direction for the light gradient attribute
+    /// angle: radians in canvas coordinates (0 = from the right, -PI/2 = from the top)
+    #[wasm_bindgen]
+    pub fn set_light_direction(&mut self, angle: f32) {
+        self.record(InputEvent::LightDirection { angle });
+        if angle.is_finite() {
+            self.light_angle = angle;
         }
     }
 
-    // ========== Triangulation ==========
-
-    /// Perform triangulation and build vertex buffers
+    /// Set the 3D light for the diffuse attribute: direction toward the light in canvas
+    /// axes (x right, y down, z toward the viewer; normalized here) and the ambient floor
+    /// (0-1) unlit facets keep; a zero or non-finite direction is ignored
     #[wasm_bindgen]
-    pub fn triangulate(&mut self) -> usize {
-        triangulate(&self.points, self.width, self.height, &mut self.buffers)
+    pub fn set_light(&mut self, dir_x: f32, dir_y: f32, dir_z: f32, ambient: f32) {
+        self.record(InputEvent::Light {
+            dir_x,
+            dir_y,
+            dir_z,
+            ambient,
+        });
+        let length = (dir_x * dir_x + dir_y * dir_y + dir_z * dir_z).sqrt();
+        if length.is_finite() && length > 0.0 {
+            self.light.0 = normalized([dir_x, dir_y, dir_z]);
+        }
+        if ambient.is_finite() {
+            self.light.1 = ambient.clamp(0.0, 1.0);
+        }
     }
 
-    // ========== Data Access ==========
-
-    /// Get triangle vertices as Float32Array
+    /// Cap on frames simulated per update after a long gap (e.g. returning to a tab)
+    /// The gap is split into substeps; time beyond the cap decays instead of jumping
     #[wasm_bindgen]
-    pub fn get_triangle_vertices(&self) -> Float32Array {
-        unsafe { Float32Array::view(&self.buffers.triangle_vertices) }
+    pub fn set_max_catchup(&mut self, frames: f32) {
+        self.record(InputEvent::MaxCatchup { frames });
+        self.max_catchup = frames.clamp(1.0, MAX_CATCHUP_FRAMES);
+        self.catchup_backlog = 0.0;
     }
 
-    /// Get stroke vertices as Float32Array
+    /// Run physics at a fixed rate (Hz, 30..=480; 0 disables): each update accumulates
+    /// its delta and runs as many whole fixed steps as fit, zero or more, so motion is the
+    /// same on 60 Hz and 144 Hz displays. The remainder carries over to the next update
+    /// Each step applies springs, impulses and shockwave decay for its length in frames,
+    /// so effect timing does not depend on the rate either
     #[wasm_bindgen]
-    pub fn get_stroke_vertices(&self) -> Float32Array {
-        unsafe { Float32Array::view(&self.buffers.stroke_vertices) }
+    pub fn set_fixed_timestep(&mut self, hz: f32) {
+        self.record(InputEvent::FixedTimestep { hz });
+        self.fixed_step = (hz > 0.0 && hz.is_finite()).then(|| {
+            FRAMES_PER_SECOND / hz.clamp(MIN_FIXED_TIMESTEP_HZ, MAX_FIXED_TIMESTEP_HZ)
+        });
+        self.step_accumulator = 0.0;
     }
 
-    /// Get point vertices as Float32Array
+    /// Split each update (each fixed step with a fixed timestep) into `n` physics
+    /// substeps, 1..=8. Every substep applies its share of the frame's impulses, spring
+    /// and shockwave motion, so strong shockwaves and wells at low frame rates move points
+    /// in smaller increments instead of tunneling them past each other
     #[wasm_bindgen]
-    pub fn get_point_vertices(&self) -> Float32Array {
-        unsafe { Float32Array::view(&self.buffers.point_vertices) }
+    pub fn set_substeps(&mut self, n: u32) {
+        self.record(InputEvent::Substeps { n });
+        self.substeps = n.clamp(1, MAX_SUBSTEPS);
     }
 
-    /// Get number of triangles
+    /// Scale how fast simulation time passes, 0.05..=4 (1 = real time)
+    /// Drift, clocks, shockwave expansion and decay, emitters and springs all slow or
+    /// speed uniformly, while each update's pointer, well and shockwave impulses keep
+    /// their strength; unlike `speed`, which only scales drift
     #[wasm_bindgen]
-    pub fn get_triangle_count(&self) -> usize {
-        self.buffers.triangle_count()
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.record(InputEvent::TimeScale { scale });
+        if scale.is_finite() {
+            self.time_scale = scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+        }
     }
 
-    /// Get number of stroke line segments
+    /// Set coordinate space of emitted positions and centroids
+    /// 0 = pixels (default), 1 = normalized [0, 1], 2 = clip space [-1, 1] with Y up
     #[wasm_bindgen]
-    pub fn get_stroke_vertex_count(&self) -> usize {
-        self.buffers.stroke_vertex_count()
+    pub fn set_output_space(&mut self, space: u32) {
+        self.buffers.layout.space = OutputSpace::from_u32(space);
+        self.output_changed();
     }
 
-    /// Get number of points
+    /// Emit triangles as a shared vertex pool + Uint32 index buffer instead of
+    /// expanded per-triangle vertices (cuts upload size ~6x for large point counts)
+    /// Shared vertices are [x, y, z, ...depth, occlusion]; per-triangle data
+    /// (average height, centroid, flip) is not emitted, use flat shading instead
+    /// Straight strokes become line indices into the same pool (`get_stroke_indices`)
     #[wasm_bindgen]
-    pub fn get_point_count(&self) -> usize {
-        self.points.len()
+    pub fn set_indexed_output(&mut self, enabled: bool) {
+        self.buffers.layout.indexed = enabled;
+        self.output_changed();
+    }
+
+    /// Also build Voronoi cells (dual of the triangulation) each triangulate
+    /// Cells are fan-triangulated with the base triangle layout, centroid = cell site
+    #[wasm_bindgen]
+    pub fn set_voronoi_enabled(&mut self, enabled: bool) {
+        self.buffers.layout.voronoi = enabled;
+        self.output_changed();
+    }
+
+    /// Smooth heights before emission: each pass averages every point's z with its
+    /// triangulated neighbors, softening brightness jumps at high height intensity
+    /// 0 = off (default), clamped to 8 passes; the points' own heights are untouched
+    #[wasm_bindgen]
+    pub fn set_height_blur(&mut self, iterations: u32) {
+        self.record(InputEvent::HeightBlur { iterations });
+        self.buffers.height_blur = iterations.min(MAX_HEIGHT_BLUR_ITERATIONS);
+    }
+
+    /// Drop sliver triangles along the borders: triangles with a ghost or corner vertex
+    /// whose smallest angle is under `min_angle_deg` (0 = off, clamped to 30); interior
+    /// triangles are always kept
+    #[wasm_bindgen]
+    pub fn set_min_triangle_quality(&mut self, min_angle_deg: f32) {
+        self.record(InputEvent::MinTriangleQuality { min_angle_deg });
+        let angle = if min_angle_deg.is_finite() {
+            min_angle_deg.clamp(0.0, MAX_MIN_TRIANGLE_ANGLE)
+        } else {
+            0.0
+        };
+        self.buffers.sliver_cos = (angle > 0.0).then(|| angle.to_radians().cos());
+    }
+
+    /// Keep the previous Delaunay mesh between builds and repair it with local edge flips
+    /// around points that moved more than `epsilon` CSS pixels (0..=2, 0 = every moved
+    /// point) since they were last checked. Falls back to a full pass when the vertex
+    /// set changed, a triangle inverted or the repair would flip over a quarter of the
+    /// triangles; larger epsilons skip more checks at the cost of slightly non-Delaunay
+    /// triangles. Pays off for slow or still meshes: drifting points keep changing the
+    /// ghost set near wrapping edges, which forces full passes
+    #[wasm_bindgen]
+    pub fn set_incremental_triangulation(&mut self, enabled: bool, epsilon: f32) {
+        self.record(InputEvent::IncrementalTriangulation { enabled, epsilon });
+        let epsilon = if epsilon.is_finite() {
+            epsilon.clamp(0.0, MAX_INCREMENTAL_EPSILON)
+        } else {
+            0.0
+        };
+        self.buffers.incremental = enabled.then(|| IncrementalMesh::new(epsilon, self.pixel_ratio));
+    }
+
+    /// Fix how far from a wrapping edge (CSS pixels) points get ghost copies across it;
+    /// 0 (default) adapts to density: three mean point spacings, so dense meshes feed
+    /// far fewer ghosts to the triangulation and sparse ones leave no border gaps
+    #[wasm_bindgen]
+    pub fn set_ghost_margin_px(&mut self, px: f32) {
+        self.record(InputEvent::GhostMargin { px });
+        self.buffers.ghost_margin = if px.is_finite() { px.max(0.0) } else { 0.0 };
+    }
+
+    /// Triangulate the rest (base) positions while emitting the displaced ones, so the
+    /// mesh stretches under interaction instead of re-connecting; topology then only
+    /// changes as points drift. Displaced triangles may overlap or fold while pushed
+    #[wasm_bindgen]
+    pub fn set_base_topology(&mut self, enabled: bool) {
+        self.record(InputEvent::BaseTopology { enabled });
+        self.buffers.base_topology = enabled;
+    }
+
+    /// Keep the previous topology until an edge is clearly past co-circular: the two
+    /// angles facing it must exceed 180 degrees by `margin_deg` (0 = off, clamped to 20)
+    /// before it flips, so nearly co-circular points stop flickering. Works on the mesh
+    /// incremental triangulation keeps, so a positive margin enables it (epsilon 0) if
+    /// off; full passes (vertex set changes) still start from the exact Delaunay mesh
+    #[wasm_bindgen]
+    pub fn set_topology_hysteresis(&mut self, margin_deg: f32) {
+        self.record(InputEvent::TopologyHysteresis { margin_deg });
+        let margin = if margin_deg.is_finite() {
+            margin_deg.clamp(0.0, MAX_TOPOLOGY_HYSTERESIS)
+        } else {
+            0.0
+        };
+        self.buffers.hysteresis = margin.to_radians() as f64;
+        if margin > 0.0 && self.buffers.incremental.is_none() {
+            self.unrecorded(|sim| sim.set_incremental_triangulation(true, 0.0));
+        }
+    }
+
+    /// [full passes, repaired passes] since incremental triangulation was enabled
+    #[wasm_bindgen]
+    pub fn get_triangulation_stats(&self) -> js_sys::Uint32Array {
+        let stats = self.buffers.incremental.as_ref().map_or([0; 2], IncrementalMesh::stats);
+        js_sys::Uint32Array::from(&stats[..])
+    }
+
+    /// Set how stroke edges are emitted
+    /// 0 = straight segments (default), 1 = bundled: each edge becomes a short polyline
+    /// bowed toward the local drift direction (still line-list pairs, so more vertices)
+    #[wasm_bindgen]
+    pub fn set_edge_mode(&mut self, mode: u32) {
+        self.buffers.layout.edge_mode = EdgeMode::from_u32(mode);
+        self.output_changed();
+    }
+
+    /// Include the outer hull edges in the stroke buffer (default true)
+    /// Every other edge is emitted once whether or not this is set
+    #[wasm_bindgen]
+    pub fn set_stroke_hull_edges(&mut self, enabled: bool) {
+        self.buffers.layout.skip_hull_edges = !enabled;
+        self.output_changed();
+    }
+
+    /// Also build the per-triangle metadata buffer each triangulate
+    /// (see `get_triangle_metadata`)
+    #[wasm_bindgen]
+    pub fn set_triangle_metadata(&mut self, enabled: bool) {
+        self.record(InputEvent::TriangleMetadata { enabled });
+        self.buffers.collect_metadata = enabled;
+    }
+
+    /// Also build a Float64 copy of the triangle vertices (base layout, stride 6), for
+    /// consumers that want f64 data like SVG export; independent of indexed output
+    /// Positions carry the simulation's f32 precision, only the centroids and the output
+    /// transform are evaluated in f64 so they add no rounding of their own
+    #[wasm_bindgen]
+    pub fn set_f64_output(&mut self, enabled: bool) {
+        self.buffers.layout.f64_output = enabled;
+        self.output_changed();
+    }
+
+    /// Exponentially smooth emitted positions toward the simulated ones, hiding
+    /// sub-pixel jitter on high-DPI screens at low speeds; physics is unaffected
+    /// alpha: fraction of the previous output position kept per frame (0 = off, max 0.95)
+    #[wasm_bindgen]
+    pub fn set_output_smoothing(&mut self, alpha: f32) {
+        self.record(InputEvent::OutputSmoothing { alpha });
+        self.output_smoothing = if alpha.is_finite() {
+            alpha.clamp(0.0, MAX_OUTPUT_SMOOTHING)
+        } else {
+            0.0
+        };
+    }
+
+    /// Connect points closer than `max_distance` (CSS pixels) with faded line
+    /// segments instead of triangulating; 0 returns to triangulation
+    /// Triangle/stroke buffers stay empty while enabled; points are still emitted
+    /// Same as `set_render_mode(1)` with a new edge length
+    #[wasm_bindgen]
+    pub fn set_constellation_mode(&mut self, max_distance: f32) {
+        self.record(InputEvent::ConstellationMode { max_distance });
+        if max_distance > 0.0 {
+            self.constellation_distance = max_distance.min(MAX_CONSTELLATION_DISTANCE);
+            self.render_mode = RenderMode::Lines;
+        } else {
+            self.render_mode = RenderMode::Mesh;
+        }
+    }
+
+    /// Choose which outputs `tick` builds
+    /// mode: 0 = points only, 1 = points + constellation lines, 2 = mesh (default),
+    /// 3 = mesh + constellation lines. Points only and lines skip the Delaunay
+    /// triangulation, leaving triangle/stroke buffers empty; lines use the edge length
+    /// of the last `set_constellation_mode` (default 120 CSS pixels)
+    #[wasm_bindgen]
+    pub fn set_render_mode(&mut self, mode: u32) {
+        self.record(InputEvent::RenderMode { mode });
+        self.render_mode = RenderMode::from_u32(mode);
+    }
+
+    /// Floats per shared vertex in indexed mode
+    #[wasm_bindgen]
+    pub fn get_indexed_stride(&self) -> usize {
+        self.buffers.layout.indexed_stride()
+    }
+
+    /// Floats per triangle vertex for the current layout
+    #[wasm_bindgen]
+    pub fn get_triangle_stride(&self) -> usize {
+        self.buffers.layout.triangle_stride()
+    }
+
+    /// Floats per stroke vertex for the current layout
+    #[wasm_bindgen]
+    pub fn get_stroke_stride(&self) -> usize {
+        self.buffers.layout.stroke_stride()
+    }
+
+    /// Floats per point vertex for the current layout
+    #[wasm_bindgen]
+    pub fn get_point_stride(&self) -> usize {
+        self.buffers.layout.point_stride()
+    }
+
+    // ========== Presets ==========
+
+    /// Export all tunable parameters (not point state) as a versioned preset object
+    #[wasm_bindgen]
+    pub fn export_preset(&self, name: &str) -> JsValue {
+        self.current_preset(name).to_js()
+    }
+
+    /// Import a preset object produced by `export_preset`
+    /// Missing fields keep their current values; throws without changing anything if invalid
+    #[wasm_bindgen]
+    pub fn import_preset(&mut self, preset: JsValue) -> Result<(), JsError> {
+        let preset = Preset::from_js(&preset, &self.current_preset(""))?;
+        self.record_params(&preset);
+        Ok(())
+    }
+
+    /// Export the visual state for another simulation (see `import_handoff`), e.g. before
+    /// a single-page app unmounts the canvas: points plus noise and physics parameters,
+    /// no history or effects, so it is fast enough to call during navigation
+    #[wasm_bindgen]
+    pub fn export_handoff(&self) -> js_sys::Uint8Array {
+        let params = HandoffParams {
+            requested_points: self.requested_points,
+            noise_scale: self.noise_scale,
+            height_intensity: self.height_intensity,
+            spring_back: self.spring_back,
+            damping: self.damping,
+            velocity_influence: self.velocity_influence,
+        };
+        let (width, height) = (self.width, self.height);
+        let bytes = handoff::encode(&self.points, width, height, self.pixel_ratio, &params);
+        js_sys::Uint8Array::from(&bytes[..])
+    }
+
+    /// Continue from another simulation's `export_handoff`, remapping its points onto
+    /// this canvas (any size or pixel ratio); the point count and the requested count
+    /// that quality tiers scale become the sender's
+    /// Throws without changing anything if invalid
+    #[wasm_bindgen]
+    pub fn import_handoff(&mut self, bytes: &[u8]) -> Result<(), JsError> {
+        let (params, points) = handoff::decode(bytes, self.width, self.height, self.pixel_ratio)
+            .map_err(|e| JsError::new(&e))?;
+        self.record(InputEvent::Handoff {
+            bytes: bytes.to_vec(),
+        });
+        self.unrecorded(|sim| {
+            sim.set_physics_params(params.spring_back, params.damping, params.velocity_influence);
+            sim.points = points;
+            sim.requested_points = params.requested_points;
+            sim.reindex_points();
+            sim.set_noise_params(params.noise_scale, params.height_intensity);
+        });
+        Ok(())
+    }
+
+    /// Apply a built-in preset by name (see `list_presets`): physics, noise, density
+    /// equalization, turbulence, flow field and mesh springs change together, mouse,
+    /// boundary, depth and point budget settings are kept. Returns false for an unknown name
+    #[wasm_bindgen]
+    pub fn apply_preset(&mut self, name: &str) -> bool {
+        self.record(InputEvent::BuiltinPreset {
+            name: name.to_string(),
+        });
+        let Some(builtin) = BuiltinPreset::find(name) else {
+            return false;
+        };
+        let mut preset = self.current_preset(name);
+        builtin.apply_to(&mut preset);
+        self.unrecorded(|sim| {
+            sim.apply_preset_values(&preset);
+
+            match builtin.flow_field {
+                Some((scale, strength, time_speed)) => {
+                    sim.set_flow_field(true, scale, strength, time_speed)
+                }
+                None => sim.set_flow_field(false, 0.0, 0.0, 0.0),
+            }
+            let stiffness = builtin.mesh_springs;
+            sim.set_mesh_springs(stiffness.is_some(), stiffness.unwrap_or(0.0));
+            let (amount, scale, speed) = builtin.shimmer.unwrap_or_default();
+            sim.set_turbulence(amount, scale, speed);
+        });
+        true
+    }
+
+    /// Names of the built-in presets for `apply_preset` (e.g. for a dropdown)
+    #[wasm_bindgen]
+    pub fn list_presets() -> JsValue {
+        string_array(&BuiltinPreset::names())
+    }
+
+    /// Apply a sparse object of tunable parameters in one call
+    /// Same grouping as presets (`physics`, `noise`, `mouse`, `densityEqualization`,
+    /// `boundary`, `depth`) without a version; theme colors live on the TypeScript side.
+    /// All fields are validated first, so an invalid field leaves everything unchanged.
+    #[wasm_bindgen]
+    pub fn update_params(&mut self, params: JsValue) -> Result<(), JsError> {
+        let mut preset = self.current_preset("");
+        preset.merge_js(&params)?;
+        self.record_params(&preset);
+        Ok(())
+    }
+
+    /// Capture current tunable parameters
+    fn current_preset(&self, name: &str) -> Preset {
+        Preset {
+            name: name.to_string(),
+            spring_back: self.spring_back,
+            damping: self.damping,
+            velocity_influence: self.velocity_influence,
+            noise_scale: self.noise_scale,
+            height_intensity: self.height_intensity,
+            mouse_velocity_smoothing: self.mouse.velocity_smoothing,
+            mouse_off_canvas_decay: self.mouse.off_canvas_decay,
+            mouse_raw_velocity: self.mouse.raw_velocity,
+            density_equalization_enabled: self.density_equalization_enabled,
+            density_equalization_strength: self.density_equalization_strength,
+            boundary_mode: self.boundary.mode,
+            flow_direction: self.boundary.tunnel.direction,
+            flow_speed: self.boundary.tunnel.flow_speed,
+            turbulence: self.boundary.tunnel.turbulence,
+            depth_mode: self.depth_mode,
+            depth_parallax: self.depth_parallax,
+            point_budget: self.point_budget,
+        }
+    }
+
+    /// Record a validated preset as one event, then apply it
+    fn record_params(&mut self, preset: &Preset) {
+        self.record(InputEvent::Params {
+            preset: Box::new(preset.clone()),
+        });
+        self.unrecorded(|sim| sim.apply_preset_values(preset));
+    }
+
+    /// Apply preset values through the regular setters (so validation is shared)
+    fn apply_preset_values(&mut self, preset: &Preset) {
+        self.set_physics_params(preset.spring_back, preset.damping, preset.velocity_influence);
+
+        // Regenerating heights is not free, skip it when noise is unchanged
+        if preset.noise_scale != self.noise_scale
+            || preset.height_intensity != self.height_intensity
+        {
+            self.set_noise_params(preset.noise_scale, preset.height_intensity);
+        }
+
+        self.mouse.set_dynamics(
+            preset.mouse_velocity_smoothing,
+            preset.mouse_off_canvas_decay,
+            preset.mouse_raw_velocity,
+        );
+        self.set_density_equalization(
+            preset.density_equalization_enabled,
+            preset.density_equalization_strength,
+        );
+
+        self.boundary.mode = preset.boundary_mode;
+        self.set_wind_tunnel_params(
+            preset.flow_direction.to_u32(),
+            preset.flow_speed,
+            preset.turbulence,
+        );
+
+        // Resampling depth reshuffles parallax, only do it when the mode changed
+        if preset.depth_mode != self.depth_mode {
+            self.set_point_depth(preset.depth_mode.to_u32(), preset.depth_parallax);
+        } else {
+            self.depth_parallax = preset.depth_parallax.clamp(0.0, 1.0);
+        }
+
+        self.point_budget = PointBudget {
+            per_megapixel: preset.point_budget.per_megapixel.max(0.0),
+            ..preset.point_budget
+        };
+        self.apply_point_budget();
+        self.output_changed();
+    }
+
+    /// Match the point count to the point budget for the current canvas (if enabled)
+    /// Points are added or removed without reseeding, the rest keep their state
+    fn apply_point_budget(&mut self) {
+        if self.point_budget.is_enabled() {
+            let css_area = self.width * self.height / (self.pixel_ratio * self.pixel_ratio);
+            self.requested_points = Self::validate_point_count(self.point_budget.count(css_area));
+            self.fill_requested_points();
+        }
+    }
+
+    // ========== Effects ==========
+
+    /// Trigger a shockwave at position
+    /// Returns its handle for `cancel_effect` (0 if rejected at capacity)
+    #[wasm_bindgen]
+    pub fn trigger_shockwave(&mut self, x: f32, y: f32, strength: f32) -> u32 {
+        self.record(InputEvent::Shockwave { x, y, strength });
+        let id = self.allocate_effect_id();
+        let wave = Shockwave::new(x, y, strength);
+        if self.shockwaves.add(Shockwave { id, ..wave }) {
+            id
+        } else {
+            0
+        }
+    }
+
+    /// Trigger an implosion: a ring contracting from `start_radius` (CSS pixels) toward
+    /// (x, y), pulling points inward as it passes (e.g. to focus a modal)
+    /// Returns its handle for `cancel_effect` (0 if rejected at capacity)
+    #[wasm_bindgen]
+    pub fn trigger_implosion(&mut self, x: f32, y: f32, strength: f32, start_radius: f32) -> u32 {
+        self.record(InputEvent::Implosion {
+            x,
+            y,
+            strength,
+            start_radius,
+        });
+        let start_radius = start_radius.clamp(0.0, MAX_DIMENSION) * self.pixel_ratio;
+        let id = self.allocate_effect_id();
+        let wave = Shockwave::implosion(x, y, strength, start_radius);
+        if self.shockwaves.add(Shockwave { id, ..wave }) {
+            id
+        } else {
+            0
+        }
+    }
+
+    /// Remove a shockwave or gravity well by the handle `trigger_shockwave`,
+    /// `set_gravity_well` or a `schedule_*` call returned, before or after it fired
+    /// Returns whether anything was removed (false once the effect has ended)
+    #[wasm_bindgen]
+    pub fn cancel_effect(&mut self, id: u32) -> bool {
+        self.record(InputEvent::CancelEffect { id });
+        if id == 0 {
+            return false;
+        }
+        let scheduled = self.schedule.cancel(id);
+        let wave = self.shockwaves.remove(id);
+        let well = self.gravity_well.is_some_and(|well| well.id == id);
+        if well {
+            self.gravity_well = None;
+        }
+        scheduled || wave || well
+    }
+
+    /// Remove all shockwaves, the gravity well, emitters and scheduled effects
+    #[wasm_bindgen]
+    pub fn clear_effects(&mut self) {
+        self.record(InputEvent::ClearEffects);
+        self.shockwaves.clear();
+        self.gravity_well = None;
+        self.emitters.clear();
+        self.schedule.clear();
+    }
+
+    /// Next handle for a named effect (never 0)
+    fn allocate_effect_id(&mut self) -> u32 {
+        self.next_effect_id = self.next_effect_id.wrapping_add(1).max(1);
+        self.next_effect_id
+    }
+
+    /// Set how many shockwaves run at once (1..=64, default 10) and what happens to a
+    /// wave triggered at capacity (emitter waves included)
+    /// policy: 0 = drop the oldest, 1 = drop the weakest (or the new wave if weaker),
+    /// 2 = reject the new wave. Poll `take_dropped_shockwaves` to count drops
+    /// Shockwaves are the only capped effect: there is a single gravity well and no
+    /// vortex effect, and drops are counted rather than reported as individual events
+    #[wasm_bindgen]
+    pub fn set_shockwave_limit(&mut self, capacity: u32, policy: u32) {
+        self.record(InputEvent::ShockwaveLimit { capacity, policy });
+        self.shockwaves.set_capacity(capacity as usize);
+        self.shockwaves.overflow = OverflowPolicy::from_u32(policy);
+    }
+
+    /// Number of shockwaves dropped or rejected at capacity since the last call
+    #[wasm_bindgen]
+    pub fn take_dropped_shockwaves(&mut self) -> u32 {
+        self.shockwaves.take_dropped()
+    }
+
+    /// Trigger a shockwave `delay_ms` milliseconds of simulated time from now, fired
+    /// inside `tick` (pausing or slowing the simulation delays it too)
+    /// Returns its handle for `cancel_effect`, 0 if the delay is invalid or the schedule is full
+    #[wasm_bindgen]
+    pub fn schedule_shockwave(&mut self, delay_ms: f32, x: f32, y: f32, strength: f32) -> u32 {
+        self.record(InputEvent::ScheduleShockwave {
+            delay_ms,
+            x,
+            y,
+            strength,
+        });
+        let Some(time) = self.schedule_time(delay_ms) else {
+            return 0;
+        };
+        if self.schedule.remaining() < 1 {
+            return 0;
+        }
+        let id = self.allocate_effect_id();
+        self.schedule.push(time, id, ScheduledEffect::Shockwave { x, y, strength });
+        id
+    }
+
+    /// Place a gravity well from `start_ms` to `end_ms` milliseconds of simulated time
+    /// from now; it replaces any current well when it starts and is removed at the end
+    /// Returns its handle for `cancel_effect`, 0 if the times are invalid or the schedule is full
+    #[wasm_bindgen]
+    pub fn schedule_gravity_well(
+        &mut self,
+        start_ms: f32,
+        end_ms: f32,
+        x: f32,
+        y: f32,
+        attract: bool,
+    ) -> u32 {
+        self.record(InputEvent::ScheduleGravityWell {
+            start_ms,
+            end_ms,
+            x,
+            y,
+            attract,
+        });
+        let (Some(start), Some(end)) = (self.schedule_time(start_ms), self.schedule_time(end_ms))
+        else {
+            return 0;
+        };
+        if end <= start || self.schedule.remaining() < 2 {
+            return 0;
+        }
+        let id = self.allocate_effect_id();
+        self.schedule.push(start, id, ScheduledEffect::GravityWellStart { x, y, attract });
+        self.schedule.push(end, id, ScheduledEffect::GravityWellEnd);
+        id
+    }
+
+    /// Drop all scheduled effects that have not fired yet
+    #[wasm_bindgen]
+    pub fn clear_schedule(&mut self) {
+        self.record(InputEvent::ClearSchedule);
+        self.schedule.clear();
+    }
+
+    /// Simulated time (frames) `delay_ms` from now, None if negative or not finite
+    fn schedule_time(&self, delay_ms: f32) -> Option<f32> {
+        (delay_ms >= 0.0 && delay_ms.is_finite())
+            .then(|| self.time + delay_ms / 1000.0 * FRAMES_PER_SECOND)
+    }
+
+    /// Fire a scheduled effect (the scheduling call was recorded, this is not)
+    fn fire_scheduled(&mut self, id: u32, effect: ScheduledEffect) {
+        match effect {
+            ScheduledEffect::Shockwave { x, y, strength } => {
+                let wave = Shockwave::new(x, y, strength);
+                self.shockwaves.add(Shockwave { id, ..wave });
+            }
+            ScheduledEffect::GravityWellStart { x, y, attract } => {
+                self.place_gravity_well(id, x, y, attract);
+            }
+            // Leave a well that has replaced the scheduled one since
+            ScheduledEffect::GravityWellEnd => {
+                if self.gravity_well.is_some_and(|well| well.id == id) {
+                    self.gravity_well = None;
+                }
+            }
+        }
+    }
+
+    /// Flip the triangles in a rectangle like tiles, sweeping along its longer side
+    /// stagger: seconds between the first and last triangle starting
+    /// Emitted through the FLIP vertex attribute
+    #[wasm_bindgen]
+    pub fn trigger_flip_region(&mut self, x: f32, y: f32, width: f32, height: f32, stagger: f32) {
+        self.record(InputEvent::FlipRegion {
+            x,
+            y,
+            width,
+            height,
+            stagger,
+        });
+        let time = self.time / FRAMES_PER_SECOND;
+        self.flips.add(x, y, width, height, stagger, time);
+    }
+
+    /// Continuously emit shockwaves from the cursor position
+    /// rate: waves per second (0 detaches the emitter)
+    #[wasm_bindgen]
+    pub fn attach_shockwave_emitter_to_cursor(&mut self, rate: f32, strength: f32) {
+        self.record(InputEvent::CursorEmitter { rate, strength });
+        self.emitters.set_cursor(rate, strength);
+    }
+
+    /// Let small shockwaves fall at random positions, `rate_per_sec` of them per second
+    /// with strengths uniformly between `min_strength` and `max_strength`
+    /// Drawn from the seeded random streams, so runs with the same seed rain alike
+    #[wasm_bindgen]
+    pub fn set_rain(
+        &mut self,
+        enabled: bool,
+        rate_per_sec: f32,
+        min_strength: f32,
+        max_strength: f32,
+    ) {
+        self.record(InputEvent::Rain {
+            enabled,
+            rate: rate_per_sec,
+            min_strength,
+            max_strength,
+        });
+        let rate = if enabled && rate_per_sec.is_finite() {
+            rate_per_sec
+        } else {
+            0.0
+        };
+        let strength = |s: f32| if s.is_finite() { s.max(0.0) } else { 0.0 };
+        self.emitters.set_rain(rate, strength(min_strength), strength(max_strength));
+    }
+
+    /// Add an emitter moving along a path of [x, y, t_seconds, ...] keyframes
+    /// Emits `rate` waves per second from the first keyframe's time until the last one
+    /// (inactive before); returns its id (0 if invalid)
+    #[wasm_bindgen]
+    pub fn add_moving_emitter(&mut self, path: &[f32], rate: f32, strength: f32) -> u32 {
+        self.record(InputEvent::PathEmitter {
+            path: path.to_vec(),
+            rate,
+            strength,
+        });
+        self.emitters.add_path(path, rate, strength)
+    }
+
+    /// Remove a moving emitter by id
+    #[wasm_bindgen]
+    pub fn remove_emitter(&mut self, id: u32) {
+        self.record(InputEvent::RemoveEmitter { id });
+        self.emitters.remove(id);
+    }
+
+    /// Add a circular obstacle that points are pushed out of
+    /// x, y: canvas pixels; radius: CSS pixels; returns its id (0 if invalid or full)
+    #[wasm_bindgen]
+    pub fn add_obstacle_circle(&mut self, x: f32, y: f32, radius: f32) -> u32 {
+        self.record(InputEvent::AddObstacle { x, y, radius });
+        self.obstacles.add(x, y, radius)
+    }
+
+    /// Remove an obstacle by id
+    #[wasm_bindgen]
+    pub fn remove_obstacle(&mut self, id: u32) {
+        self.record(InputEvent::RemoveObstacle { id });
+        self.obstacles.remove(id);
+    }
+
+    /// Remove all obstacles
+    #[wasm_bindgen]
+    pub fn clear_obstacles(&mut self) {
+        self.record(InputEvent::ClearObstacles);
+        self.obstacles.clear();
+    }
+
+    /// Drop triangles (and their strokes) whose centroid lies inside an obstacle
+    /// Voronoi cells and points are not culled
+    #[wasm_bindgen]
+    pub fn set_obstacle_culling(&mut self, enabled: bool) {
+        self.record(InputEvent::ObstacleCulling { enabled });
+        self.obstacles.cull = enabled;
+    }
+
+    /// Softly pin the nearest point to each of the [x, y, ...] canvas positions (e.g.
+    /// card corners measured by the host): their rest positions stay at the anchors, so
+    /// the mesh holds onto page elements and springs back around them when disturbed.
+    /// Anchors follow resizes and re-pin to the nearest points; an empty array clears them
+    #[wasm_bindgen]
+    pub fn set_anchor_points(&mut self, positions: &[f32]) {
+        self.record(InputEvent::AnchorPoints {
+            positions: positions.to_vec(),
+        });
+        self.anchors.set(positions);
+    }
+
+    /// Register an axis-aligned exclusion rectangle (canvas pixels) for a content area
+    /// mode: 0 = repel points toward the nearest edge, 1 = flag overlapping triangles
+    /// (EXCLUSION vertex attribute), 2 = both; returns its id (0 if invalid or full)
+    #[wasm_bindgen]
+    pub fn add_exclusion_rect(&mut self, x: f32, y: f32, w: f32, h: f32, mode: u32) -> u32 {
+        self.record(InputEvent::AddExclusion {
+            x,
+            y,
+            width: w,
+            height: h,
+            mode,
+        });
+        self.exclusions.add(x, y, w, h, ExclusionMode::from_u32(mode))
+    }
+
+    /// Remove an exclusion rectangle by id
+    #[wasm_bindgen]
+    pub fn remove_exclusion_rect(&mut self, id: u32) {
+        self.record(InputEvent::RemoveExclusion { id });
+        self.exclusions.remove(id);
+    }
+
+    /// Remove all exclusion rectangles
+    #[wasm_bindgen]
+    pub fn clear_exclusion_rects(&mut self) {
+        self.record(InputEvent::ClearExclusions);
+        self.exclusions.clear();
+    }
+
+    /// Remove all emitters, including the cursor emitter
+    #[wasm_bindgen]
+    pub fn clear_emitters(&mut self) {
+        self.record(InputEvent::ClearEmitters);
+        self.emitters.clear();
+    }
+
+    /// Set or clear gravity well
+    /// Returns the new well's handle for `cancel_effect` (0 when clearing)
+    #[wasm_bindgen]
+    pub fn set_gravity_well(&mut self, x: f32, y: f32, active: bool, attract: bool) -> u32 {
+        self.record(InputEvent::GravityWell {
+            x,
+            y,
+            active,
+            attract,
+        });
+        if active {
+            let id = self.allocate_effect_id();
+            self.place_gravity_well(id, x, y, attract);
+            id
+        } else {
+            let throw = self.well_throw;
+            let px = self.pixel_ratio;
+            self.gravity_well = self
+                .gravity_well
+                .take()
+                .and_then(|mut well| well.throw(throw?, px).then_some(well));
+            0
+        }
+    }
+
+    /// Replace the gravity well
+    fn place_gravity_well(&mut self, id: u32, x: f32, y: f32, attract: bool) {
+        let mut well = if attract {
+            GravityWell::attract(x, y)
+        } else {
+            GravityWell::repel(x, y)
+        };
+        well.id = id;
+        self.gravity_well = Some(well);
+    }
+
+    /// Throw gravity wells on release: a well dragged fast enough keeps moving with its
+    /// last velocity, keeping `friction` of it per frame (0..1), bouncing off the canvas
+    /// edges or expiring once it leaves, and disappears when it has slowed down
+    #[wasm_bindgen]
+    pub fn set_gravity_well_throw(&mut self, enabled: bool, friction: f32, bounce: bool) {
+        self.record(InputEvent::GravityWellThrow {
+            enabled,
+            friction,
+            bounce,
+        });
+        self.well_throw = enabled.then(|| WellThrow {
+            friction: if friction.is_finite() {
+                friction.clamp(0.0, 1.0)
+            } else {
+                DEFAULT_WELL_THROW_FRICTION
+            },
+            bounce,
+        });
+    }
+
+    /// Update gravity well position
+    #[wasm_bindgen]
+    pub fn update_gravity_well_position(&mut self, x: f32, y: f32) {
+        self.record(InputEvent::GravityWellPosition { x, y });
+        if let Some(ref mut well) = self.gravity_well {
+            well.set_position(x, y);
+        }
+    }
+
+    // ========== Simulation Control ==========
+
+    /// Freeze the background without tearing down state (e.g. while a heavy modal is
+    /// open): `tick` and `update_points` change nothing and `tick` returns the cached
+    /// triangle count, so the last buffers can be redrawn
+    #[wasm_bindgen]
+    pub fn pause(&mut self) {
+        self.record(InputEvent::Pause);
+        self.paused = true;
+    }
+
+    /// Continue after `pause`
+    #[wasm_bindgen]
+    pub fn resume(&mut self) {
+        self.record(InputEvent::Resume);
+        self.paused = false;
+    }
+
+    #[wasm_bindgen]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Advance by exactly one update of `delta_time` frames and triangulate, even while
+    /// paused (e.g. to debug frame by frame); pointer state and speed stay as the last
+    /// tick left them. With a fixed timestep `delta_time` feeds the step accumulator like
+    /// a tick. Returns the triangle count
+    #[wasm_bindgen]
+    pub fn step(&mut self, delta_time: f32) -> usize {
+        self.record(InputEvent::Step { delta_time });
+        self.advance(delta_time, self.last_speed);
+        self.watched_triangulate(delta_time)
+    }
+
+    /// Silently run `steps` one-frame updates (at most MAX_WARMUP_STEPS) and triangulate
+    /// once, so the first render shows an already settled mesh. Runs while paused too;
+    /// the frame counter is left unchanged. Returns the triangle count
+    #[wasm_bindgen]
+    pub fn warmup(&mut self, steps: u32) -> usize {
+        self.record(InputEvent::Warmup { steps });
+        let frame = self.frame;
+        for _ in 0..steps.min(MAX_WARMUP_STEPS) {
+            self.advance(1.0, self.last_speed);
+        }
+        self.frame = frame;
+        self.watched_triangulate(1.0)
+    }
+
+    /// Resize the simulation
+    #[wasm_bindgen]
+    pub fn resize(&mut self, new_width: f32, new_height: f32) {
+        self.record(InputEvent::Resize {
+            new_width,
+            new_height,
+        });
+        let new_width = Self::validate_dimension(new_width);
+        let new_height = Self::validate_dimension(new_height);
+
+        if self.width > 0.0 && self.height > 0.0 {
+            let scale_x = new_width / self.width;
+            let scale_y = new_height / self.height;
+
+            for point in &mut self.points {
+                point.scale(scale_x, scale_y);
+            }
+            self.anchors.scale(scale_x, scale_y);
+            self.distribution.scale(scale_x, scale_y);
+        }
+
+        self.width = new_width;
+        self.height = new_height;
+        self.apply_point_budget();
+        self.reindex_points();
+    }
+
+    /// Pin a point at its current position so no drift or force moves it
+    #[wasm_bindgen]
+    pub fn pin_point(&mut self, index: usize) {
+        self.record(InputEvent::PinPoint { index });
+        if let Some(point) = self.points.get_mut(index) {
+            point.pin_here();
+        }
+    }
+
+    /// Release a pinned point; it resumes drifting from its pin position
+    #[wasm_bindgen]
+    pub fn unpin_point(&mut self, index: usize) {
+        self.record(InputEvent::UnpinPoint { index });
+        if let Some(point) = self.points.get_mut(index) {
+            point.pin = None;
+        }
+    }
+
+    /// Pin every point within `radius` (CSS pixels) of (x, y); returns how many were newly pinned
+    #[wasm_bindgen]
+    pub fn pin_points_near(&mut self, x: f32, y: f32, radius: f32) -> u32 {
+        self.record(InputEvent::PinPointsNear { x, y, radius });
+        let radius = radius.max(0.0) * self.pixel_ratio;
+        let mut pinned = 0;
+        for point in &mut self.points {
+            let dist_sq = (point.x - x).powi(2) + (point.y - y).powi(2);
+            if point.pin.is_none() && dist_sq <= radius * radius {
+                point.pin_here();
+                pinned += 1;
+            }
+        }
+        pinned
+    }
+
+    /// Put a point on a layer (0..31, clamped) for effect filter layer masks
+    #[wasm_bindgen]
+    pub fn set_point_layer(&mut self, index: usize, layer: u32) {
+        self.record(InputEvent::PointLayer { index, layer });
+        if let Some(point) = self.points.get_mut(index) {
+            point.layer = layer.min(MAX_POINT_LAYER) as u8;
+        }
+    }
+
+    /// Put every point within `radius` (CSS pixels) of (x, y) on a layer; returns how many
+    #[wasm_bindgen]
+    pub fn set_points_layer_near(&mut self, x: f32, y: f32, radius: f32, layer: u32) -> u32 {
+        self.record(InputEvent::PointsLayerNear {
+            x,
+            y,
+            radius,
+            layer,
+        });
+        let radius = radius.max(0.0) * self.pixel_ratio;
+        let layer = layer.min(MAX_POINT_LAYER) as u8;
+        let mut count = 0;
+        for point in &mut self.points {
+            let dist_sq = (point.x - x).powi(2) + (point.y - y).powi(2);
+            if dist_sq <= radius * radius {
+                point.layer = layer;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Set point count (add or remove points)
+    /// With a preset point budget enabled the next resize recomputes the count
+    /// With a structured distribution (see `set_distribution`) the whole set is laid out
+    /// again instead
+    #[wasm_bindgen]
+    pub fn set_point_count(&mut self, count: usize, seed: u32) {
+        self.record(InputEvent::PointCount { count, seed });
+        self.requested_points = Self::validate_point_count(count);
+        self.rng = RngStreams::new(seed);
+        if self.distribution.is_structured() {
+            // Lay the set out again even when the count is unchanged
+            self.points.clear();
+        }
+        self.fill_requested_points();
+    }
+
+    /// Add one point at (x, y) canvas pixels (clamped to the canvas); returns its index,
+    /// or the point count (no valid index) when already at MAX_POINT_COUNT
+    /// The layout of the other points is kept; the requested count follows
+    #[wasm_bindgen]
+    pub fn add_point(&mut self, x: f32, y: f32) -> usize {
+        self.record(InputEvent::AddPoint { x, y });
+        let index = self.points.len();
+        if index >= MAX_POINT_COUNT || !x.is_finite() || !y.is_finite() {
+            return index;
+        }
+        self.spawn_point(x.clamp(0.0, self.width), y.clamp(0.0, self.height));
+        self.points_changed(1, 0);
+        index
+    }
+
+    /// Remove the point at `index`; later points move down by one index
+    /// Ignored for an invalid index or at MIN_POINT_COUNT
+    #[wasm_bindgen]
+    pub fn remove_point(&mut self, index: usize) {
+        self.record(InputEvent::RemovePoint { index });
+        if index < self.points.len() && self.points.len() > MIN_POINT_COUNT {
+            self.points.remove(index);
+            self.points_changed(0, 1);
+        }
+    }
+
+    /// Remove every point within `radius` (CSS pixels) of (x, y), keeping at least
+    /// MIN_POINT_COUNT (the farthest of them survive); returns how many were removed
+    /// The remaining points keep their order
+    #[wasm_bindgen]
+    pub fn remove_points_in_radius(&mut self, x: f32, y: f32, radius: f32) -> u32 {
+        self.record(InputEvent::RemovePointsInRadius { x, y, radius });
+        let radius_sq = (radius.max(0.0) * self.pixel_ratio).powi(2);
+        let dist_sq = |p: &Point| (p.x - x).powi(2) + (p.y - y).powi(2);
+        let inside = self.points.iter().filter(|p| dist_sq(p) <= radius_sq).count();
+        let removable = inside.min(self.points.len().saturating_sub(MIN_POINT_COUNT));
+        if removable == 0 {
+            return 0;
+        }
+
+        // Distance below which a point goes: everything inside the radius, or only the
+        // `removable` nearest when that would leave too few
+        let mut cutoff = radius_sq;
+        if removable < inside {
+            let mut distances: Vec<f32> = self.points.iter().map(dist_sq).collect();
+            distances.sort_unstable_by(f32::total_cmp);
+            cutoff = distances[removable - 1];
+        }
+        let mut budget = removable;
+        self.points.retain(|p| {
+            let remove = budget > 0 && dist_sq(p) <= cutoff;
+            budget -= remove as usize;
+            !remove
+        });
+        self.points_changed(0, removable);
+        removable as u32
+    }
+
+    /// Move every point to new rest positions from [x, y, ...] canvas pixels (one pair per
+    /// point, see `get_positions`), discarding displacement; pinned points move their pin
+    /// Mesh springs take their rest lengths from the new layout and output smoothing
+    /// starts there instead of gliding from the old positions
+    /// Returns false (and changes nothing) if the length does not match or a value is
+    /// not finite
+    #[wasm_bindgen]
+    pub fn set_positions(&mut self, data: &[f32]) -> bool {
+        self.record(InputEvent::Positions {
+            data: data.to_vec(),
+        });
+        if !self.valid_point_pairs(data) {
+            return false;
+        }
+        for (point, pair) in self.points.iter_mut().zip(data.chunks_exact(2)) {
+            point.respawn_at(pair[0], pair[1]);
+            if point.pin.is_some() {
+                point.pin_here();
+            }
+        }
+        self.reindex_points();
+        true
+    }
+
+    /// Set every point's drift velocity from [vx, vy, ...] canvas pixels per frame (see
+    /// `get_velocities`); returns false (and changes nothing) if the length does not
+    /// match or a value is not finite
+    #[wasm_bindgen]
+    pub fn set_velocities(&mut self, data: &[f32]) -> bool {
+        self.record(InputEvent::Velocities {
+            data: data.to_vec(),
+        });
+        if !self.valid_point_pairs(data) {
+            return false;
+        }
+        for (point, pair) in self.points.iter_mut().zip(data.chunks_exact(2)) {
+            (point.vx, point.vy) = (pair[0], pair[1]);
+        }
+        true
+    }
+
+    /// Whether `data` holds one finite pair per point
+    fn valid_point_pairs(&self, data: &[f32]) -> bool {
+        data.len() == self.points.len() * 2 && data.iter().all(|v| v.is_finite())
+    }
+
+    /// Keep the requested count and index-based state in line after points were added
+    /// or removed individually; the request moves by the same amount, so quality tiers
+    /// keep scaling the host's count
+    fn points_changed(&mut self, added: usize, removed: usize) {
+        let requested = (self.requested_points + added).saturating_sub(removed);
+        self.requested_points = Self::validate_point_count(requested);
+        self.reindex_points();
+    }
+
+    /// Refresh state keyed by point index or position after the point set was replaced
+    /// or moved outside an update
+    fn reindex_points(&mut self) {
+        // Mesh spring rest lengths, output smoothing and stroke motion describe the old
+        // geometry (or shifted indices); they are measured again from the new set
+        self.buffers.edges.clear();
+        self.smoothed_points.clear();
+        self.prev_positions.clear();
+        self.anchors.invalidate();
+        if let Some(ref mut watchdog) = self.watchdog {
+            watchdog.invalidate();
+        }
+        self.rebuild_spatial_grid();
+    }
+
+    /// Fill to the requested point count as scaled by the quality tier
+    fn fill_requested_points(&mut self) {
+        let count = QualityTier::get(self.quality_tier).point_count(self.requested_points);
+        self.fill_points(Self::validate_point_count(count));
+    }
+
+    /// Add or remove points until there are `count` (already validated)
+    fn fill_points(&mut self, count: usize) {
+        if count == self.points.len() {
+            return;
+        }
+
+        if self.distribution.is_structured() {
+            // Lattices only stay even when laid out as a whole
+            let mut sites = Vec::with_capacity(count);
+            let (width, height) = (self.width, self.height);
+            self.distribution.place(count, width, height, &mut self.rng.placement, &mut sites);
+            self.points.clear();
+            for (x, y) in sites {
+                self.spawn_point(x, y);
+            }
+        }
+
+        // Add new points if needed
+        while self.points.len() < count {
+            let (x, y) = match self.density_map {
+                Some(ref map) => map.sample(&mut self.rng.placement, self.width, self.height),
+                None => (
+                    self.rng.placement.next_f32() * self.width,
+                    self.rng.placement.next_f32() * self.height,
+                ),
+            };
+            self.spawn_point(x, y);
+        }
+
+        // Remove excess points
+        self.points.truncate(count);
+        self.reindex_points();
+    }
+
+    /// Add a point at (x, y) with fresh drift, terrain height and depth
+    fn spawn_point(&mut self, x: f32, y: f32) {
+        let noise_scale = self.effective_noise_scale();
+        let mut point = Point::new_at(
+            x,
+            y,
+            &mut self.rng.drift,
+            self.width,
+            self.height,
+            noise_scale,
+            self.height_intensity,
+            self.light_shift,
+        );
+        point.depth = self.depth_mode.sample(&point, &mut self.rng.depth);
+        self.points.push(point);
+    }
+
+    /// Set how point sets are laid out by the next `set_point_count`
+    /// mode: 0 = random (default, follows the density map), 1 = jittered grid,
+    /// 2 = hex lattice; jitter: 0-1 of the lattice spacing each point may be moved
+    /// Structured layouts ignore the density map and are laid out again as a whole
+    /// whenever the point count changes
+    #[wasm_bindgen]
+    pub fn set_distribution(&mut self, mode: u32, jitter: f32) {
+        self.record(InputEvent::Distribution { mode, jitter });
+        self.distribution = Distribution::from_u32(mode, jitter);
+    }
+
+    /// Lay point sets out on a golden-angle (sunflower) spiral around (center_x, center_y)
+    /// canvas pixels (clamped onto the canvas), from the next `set_point_count` on; the
+    /// center follows `resize`
+    /// falloff: 0 = even density, 1 = densest at the center and thinning outward
+    #[wasm_bindgen]
+    pub fn set_spiral_distribution(&mut self, center_x: f32, center_y: f32, falloff: f32) {
+        self.record(InputEvent::SpiralDistribution {
+            center_x,
+            center_y,
+            falloff,
+        });
+        let (width, height) = (self.width, self.height);
+        self.distribution = Distribution::spiral(center_x, center_y, falloff, width, height);
+    }
+
+    /// Set a grayscale density map (row-major, cols x rows, 0 = empty, 255 = densest)
+    /// Points added by `set_point_count` are placed according to it; existing points
+    /// stay until rebalanced. Returns false (and clears the map) if the size does not
+    /// match or every cell is empty
+    #[wasm_bindgen]
+    pub fn set_density_map(&mut self, data: &[u8], cols: usize, rows: usize) -> bool {
+        self.record(InputEvent::DensityMap {
+            data: data.to_vec(),
+            cols,
+            rows,
+        });
+        let rate = self.density_map.as_ref().map_or(0.0, DensityMap::rebalance_rate);
+        self.density_map = DensityMap::new(data, cols, rows);
+        if let Some(ref mut map) = self.density_map {
+            map.set_rebalance_rate(rate);
+        }
+        self.density_map.is_some()
+    }
+
+    /// Relocate `rate` random points per second to density-map positions (0 disables)
+    /// Relocated points jump, so keep the rate low for a gradual shift
+    #[wasm_bindgen]
+    pub fn set_density_rebalance(&mut self, rate: f32) {
+        self.record(InputEvent::DensityRebalance { rate });
+        if let Some(ref mut map) = self.density_map {
+            map.set_rebalance_rate(rate);
+        }
+    }
+
+    /// Move due points to positions drawn from the density map
+    fn rebalance_density(&mut self, delta_time: f32) {
+        let noise_scale = self.effective_noise_scale();
+        let Some(ref mut map) = self.density_map else {
+            return;
+        };
+        let due = map.rebalance_due(delta_time / FRAMES_PER_SECOND);
+        if self.points.is_empty() {
+            return;
+        }
+        for _ in 0..due {
+            let index = self.rng.density.next() as usize % self.points.len();
+            let point = &mut self.points[index];
+            if point.pin.is_some() {
+                continue;
+            }
+            let (x, y) = map.sample(&mut self.rng.density, self.width, self.height);
+            point.respawn_at(x, y);
+            point.regenerate_height(
+                self.width,
+                self.height,
+                noise_scale,
+                self.height_intensity,
+                self.light_shift,
+            );
+        }
+    }
+
+    // ========== Watchdog ==========
+
+    /// Snapshot the points every `interval_seconds` of simulated time and roll back
+    /// to the last good snapshot when a tick fails `health_check` (0 disables)
+    /// Poll `take_watchdog_rollbacks` to learn about rollbacks
+    #[wasm_bindgen]
+    pub fn set_watchdog(&mut self, interval_seconds: f32) {
+        self.record(InputEvent::Watchdog { interval_seconds });
+        self.watchdog = if interval_seconds > 0.0 {
+            Some(Watchdog::new(interval_seconds * FRAMES_PER_SECOND))
+        } else {
+            None
+        };
+    }
+
+    /// Run a maintenance pass every `interval_seconds` of simulated time (0 disables):
+    /// rest positions are folded back into the canvas and, for a few seconds, crowded
+    /// cells are relaxed toward uniform density at a tenth of the equalization default.
+    /// Keeps pages running for hours from slowly losing their even distribution; with a
+    /// density map, `set_density_rebalance` covers the map instead
+    #[wasm_bindgen]
+    pub fn set_auto_maintenance(&mut self, interval_seconds: f32) {
+        self.record(InputEvent::AutoMaintenance { interval_seconds });
+        self.maintenance = if interval_seconds > 0.0 && interval_seconds.is_finite() {
+            Some(Maintenance::new(interval_seconds * FRAMES_PER_SECOND))
+        } else {
+            None
+        };
+    }
+
+    /// After `delay_ms` without pointer movement, play gentle ambient effects (a slowly
+    /// wandering swirl and a soft shockwave every few seconds) scaled by `intensity`
+    /// (1 = gentle, up to 3); they ramp in over two seconds and off within half a
+    /// second of input returning
+    #[wasm_bindgen]
+    pub fn set_idle_behavior(&mut self, enabled: bool, delay_ms: f32, intensity: f32) {
+        self.record(InputEvent::IdleBehavior {
+            enabled,
+            delay_ms,
+            intensity,
+        });
+        let valid = delay_ms.is_finite() && intensity.is_finite();
+        self.idle = (enabled && valid).then(|| {
+            let delay = delay_ms.max(0.0) / 1000.0 * FRAMES_PER_SECOND;
+            IdleBehavior::new(delay, intensity.clamp(0.0, MAX_IDLE_INTENSITY))
+        });
+    }
+
+    /// Once mean point motion stays below `threshold` CSS pixels per frame for half a
+    /// second, stop physics and triangulation entirely (ticks return the last triangle
+    /// count) until pointer movement or any input, effect or setter call wakes it.
+    /// Rain, path emitters, scheduled effects and idle behavior keep it awake; look
+    /// changes made while asleep show on waking. Drift counts as motion, so pair with a
+    /// low `speed`
+    #[wasm_bindgen]
+    pub fn set_static_after_settle(&mut self, enabled: bool, threshold: f32) {
+        self.record(InputEvent::StaticAfterSettle { enabled, threshold });
+        let valid = threshold.is_finite() && threshold > 0.0;
+        self.settle = (enabled && valid).then(|| Settle::new(threshold));
+    }
+
+    /// Whether the simulation is asleep after settling (hosts may stop their frame loop
+    /// until the next input)
+    #[wasm_bindgen]
+    pub fn is_settled(&self) -> bool {
+        self.settle.as_ref().is_some_and(Settle::is_asleep)
+    }
+
+    /// Skip triangulation and buffer rebuilds on ticks where no point is more than
+    /// `epsilon` CSS pixels from where it was last drawn (0 disables). Active shockwaves,
+    /// gravity wells, tile flips, audio jitter and any input, effect or setter
+    /// call still rebuild; `buffers_changed` tells whether a tick did
+    #[wasm_bindgen]
+    pub fn set_quiescence_epsilon(&mut self, epsilon: f32) {
+        self.record(InputEvent::QuiescenceEpsilon { epsilon });
+        self.quiescence = (epsilon.is_finite() && epsilon > 0.0).then(|| Quiescence::new(epsilon));
+    }
+
+    /// Whether the last tick rebuilt the output buffers (false when it reused them:
+    /// quiescent, paused, settled or between triangulation cadence ticks)
+    #[wasm_bindgen]
+    pub fn buffers_changed(&self) -> bool {
+        self.buffers_changed
+    }
+
+    /// Whether the last build still matches the points and nothing animates the output
+    fn quiescent(&self) -> bool {
+        let Some(ref quiescence) = self.quiescence else {
+            return false;
+        };
+        let animating = !self.shockwaves.is_empty()
+            || self.gravity_well.is_some()
+            || !self.flips.is_empty()
+            || self.audio.stroke_jitter() != 0.0;
+        let (layout, mode) = (self.buffers.layout, self.render_mode);
+        !animating && quiescence.holds(&self.points, layout, mode, self.pixel_ratio)
+    }
+
+    /// Track motion after an update of `delta_time` frames
+    fn observe_settle(&mut self, delta_time: f32) {
+        let busy = self.emitters.emits_without_input()
+            || !self.schedule.is_empty()
+            || self.idle.is_some();
+        if let Some(ref mut settle) = self.settle {
+            settle.observe(&self.points, self.pixel_ratio, delta_time, busy);
+        }
+    }
+
+    /// Advance the idle clock; while idle, swirl and drop soft shockwaves
+    fn run_idle(&mut self, delta_time: f32) {
+        let Some(ref mut idle) = self.idle else {
+            return;
+        };
+        let threshold = IDLE_INPUT_SPEED * self.pixel_ratio;
+        let input = std::iter::once(&self.mouse)
+            .chain(self.pointers.iter())
+            .any(|m| m.in_canvas && m.speed() > threshold);
+        if idle.update(input, delta_time) {
+            let x = self.rng.idle.next_f32() * self.width;
+            let y = self.rng.idle.next_f32() * self.height;
+            self.shockwaves.add(Shockwave::new(x, y, idle.shockwave_strength()));
+        }
+        let (width, height, px) = (self.width, self.height, self.pixel_ratio);
+        idle.swirl(&mut self.points, width, height, px, self.time, delta_time);
+    }
+
+    /// Fold rest positions into the canvas at the start of a pass, then relax gently
+    fn maintain(&mut self, delta_time: f32) {
+        let Some(ref mut maintenance) = self.maintenance else {
+            return;
+        };
+        if maintenance.update(delta_time) {
+            for point in self.points.iter_mut().filter(|p| p.pin.is_none()) {
+                self.boundary.translate(point, 0.0, 0.0, self.width, self.height);
+            }
+        }
+        let relax = maintenance.relaxing()
+            && self.density_map.is_none()
+            && !self.density_equalization_enabled;
+        if relax {
+            apply_density_equalization(
+                &mut self.points,
+                &self.spatial_grid,
+                &self.boundary,
+                MAINTENANCE_STRENGTH,
+                delta_time,
+            );
+        }
+    }
+
+    /// Check state invariants: point positions finite and near the canvas, and the
+    /// last triangulation non-empty (unless the render mode has no mesh)
+    #[wasm_bindgen]
+    pub fn health_check(&self) -> bool {
+        let triangulated = !self.render_mode.meshes() || self.buffers.triangle_count() > 0;
+        triangulated && points_healthy(&self.points, self.width, self.height)
+    }
+
+    /// Number of watchdog rollbacks since the last call
+    #[wasm_bindgen]
+    pub fn take_watchdog_rollbacks(&mut self) -> u32 {
+        self.watchdog.as_mut().map_or(0, Watchdog::take_rollbacks)
+    }
+
+    /// Triangulate under the watchdog: snapshot when healthy, otherwise roll back (or,
+    /// before the first snapshot, respawn the broken points) and retriangulate. Points
+    /// are checked first since non-finite coordinates can panic the Delaunay sort
+    fn watched_triangulate(&mut self, delta_time: f32) -> usize {
+        let Some(mut watchdog) = self.watchdog.take() else {
+            return self.triangulate();
+        };
+        let points_ok = points_healthy(&self.points, self.width, self.height);
+        let mut triangle_count = if points_ok { self.triangulate() } else { 0 };
+
+        if points_ok && self.health_check() {
+            watchdog.healthy(delta_time, &self.points, self.time);
+        } else if let Some(time) = watchdog.roll_back(&mut self.points) {
+            self.time = time;
+            triangle_count = self.triangulate();
+        } else if !points_ok {
+            // Nothing to roll back to yet
+            self.respawn_unhealthy_points();
+            triangle_count = self.triangulate();
+        }
+        self.watchdog = Some(watchdog);
+        triangle_count
+    }
+
+    /// Replace every point failing `points_healthy` with a fresh one at a random position
+    fn respawn_unhealthy_points(&mut self) {
+        let (width, height) = (self.width, self.height);
+        for i in 0..self.points.len() {
+            if points_healthy(&self.points[i..=i], width, height) {
+                continue;
+            }
+            let x = self.rng.placement.next_f32() * width;
+            let y = self.rng.placement.next_f32() * height;
+            self.spawn_point(x, y);
+            self.points.swap_remove(i);
+        }
+    }
+
+    /// Update point positions
+    #[wasm_bindgen]
+    pub fn update_points(&mut self, delta_time: f32, speed: f32) {
+        self.record(InputEvent::UpdatePoints { delta_time, speed });
+        if self.paused || self.is_settled() {
+            return;
+        }
+        self.last_speed = speed;
+        self.advance(delta_time, speed);
+    }
+
+    /// Advance physics by one update, splitting long gaps into bounded substeps (or into
+    /// fixed steps, see `set_fixed_timestep`)
+    /// Time beyond `max_catchup` carries over as a decaying backlog
+    fn advance(&mut self, delta_time: f32, speed: f32) {
+        self.frame = self.frame.wrapping_add(1);
+
+        let requested = delta_time.max(0.0) + self.catchup_backlog;
+        let simulated = requested.min(self.max_catchup);
+        self.catchup_backlog = (requested - simulated) * CATCHUP_BACKLOG_DECAY;
+
+        let split = self.substeps;
+        if let Some(step) = self.fixed_step {
+            // A fixed step covers `step` frames, so it applies that many frames of impulses
+            self.step_accumulator += simulated;
+            while self.step_accumulator >= step {
+                self.step_accumulator -= step;
+                for _ in 0..split {
+                    self.substep(step / split as f32, step / split as f32, speed);
+                }
+            }
+        } else {
+            let substeps = (simulated / MAX_SUBSTEP_FRAMES).ceil().max(1.0) * split as f32;
+            for _ in 0..substeps as u32 {
+                self.substep(simulated / substeps, 1.0 / split as f32, speed);
+            }
+        }
+        self.observe_settle(simulated);
+        // Effects keep animating on ticks that skip triangulation
+        self.build_effects_buffer();
+    }
+
+    /// Describe the active shockwaves and gravity well (see `get_effects_buffer`)
+    fn build_effects_buffer(&mut self) {
+        build_effects_buffer(
+            &self.shockwaves,
+            self.gravity_well.as_ref(),
+            self.pixel_ratio,
+            &mut self.effects_buffer,
+        );
+    }
+
+    /// Advance physics by `delta_time` real frames (at most MAX_SUBSTEP_FRAMES)
+    /// Impulses advance by `share` of their per-frame change (1 / `substeps` of an update,
+    /// or the step length in frames with a fixed timestep); springs and shockwaves by that
+    /// share of simulated time
+    fn substep(&mut self, delta_time: f32, share: f32, speed: f32) {
+        let speed = speed.clamp(0.0, 10.0);
+        let delta_time = delta_time * self.time_scale;
+        let time_share = share * self.time_scale;
+        let (spring_back, damping) = if time_share == 1.0 {
+            (self.spring_back, self.damping)
+        } else {
+            (1.0 - (1.0 - self.spring_back).powf(time_share), self.damping.powf(time_share))
+        };
+
+        // Fire scheduled effects coming due during this step
+        while let Some((id, effect)) = self.schedule.pop_due(self.time + delta_time) {
+            self.fire_scheduled(id, effect);
+        }
+
+        // Spawn emitter waves, then update shockwaves
+        self.emitters.update(
+            delta_time / FRAMES_PER_SECOND,
+            &self.mouse,
+            &mut self.rng.rain,
+            self.width,
+            self.height,
+            &mut self.shockwaves,
+        );
+        self.shockwaves.update(self.pixel_ratio, time_share);
+
+        self.time += delta_time;
+        self.flips.update(self.time / FRAMES_PER_SECOND);
+        self.apply_scroll(delta_time);
+
+        // Drift velocities are in CSS pixels per frame
+        let speed = speed
+            * self.pixel_ratio
+            * self.audio.speed_scale()
+            * self.environment.value(EnvironmentTarget::DriftSpeed);
+        let noise_scale = self.effective_noise_scale();
+        let effects = QualityTier::get(self.quality_tier).effects;
+        let flow_field = self.flow_field.as_ref().filter(|_| effects);
+
+        // Update point positions and physics
+        match self.boundary.mode {
+            BoundaryMode::Wrap => {
+                // Independent per point, so large sets drift in parallel chunks
+                let (time, pixel_ratio) = (self.time, self.pixel_ratio);
+                let (width, height, parallax) = (self.width, self.height, self.depth_parallax);
+                for_each_chunk(&mut self.points, 1, |_, points| {
+                    for point in points.iter_mut().filter(|p| p.pin.is_none()) {
+                        if let Some(flow) = flow_field {
+                            flow.advect(point, delta_time, time, pixel_ratio);
+                        }
+                        let speed = speed * depth_factor(point.depth, parallax);
+                        point.update_position(delta_time, speed, width, height);
+                    }
+                });
+            }
+            BoundaryMode::WindTunnel => {
+                for point in &mut self.points {
+                    if point.pin.is_some() {
+                        continue;
+                    }
+                    if let Some(flow) = flow_field {
+                        flow.advect(point, delta_time, self.time, self.pixel_ratio);
+                    }
+                    point.drift(delta_time, speed * depth_factor(point.depth, self.depth_parallax));
+                    let respawned = self.boundary.advance_tunnel_point(
+                        point,
+                        &mut self.rng.placement,
+                        delta_time,
+                        self.time,
+                        self.width,
+                        self.height,
+                        self.pixel_ratio,
+                    );
+                    if respawned {
+                        point.regenerate_height(
+                            self.width,
+                            self.height,
+                            noise_scale,
+                            self.height_intensity,
+                            self.light_shift,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Pull displacement back toward rest (pinned points are skipped)
+        apply_springs(&mut self.points, spring_back, damping);
+
+        // Attached points' rest positions follow the cursor
+        if let Some(ref sticky) = self.sticky {
+            sticky.update(&mut self.points, &self.mouse, self.spring_back, self.pixel_ratio);
+        }
+
+        // Rebuild spatial grid
+        self.rebuild_spatial_grid();
+
+        if let Some(ref mut metrics) = self.interaction_metrics {
+            metrics.begin(&self.points);
+        }
+
+        // Apply effects using spatial queries
+        // Queued events sweep the mouse along its sub-frame path
+        let filter = self.force_filter(EffectTarget::Pointer);
+        let mouse: &[MouseState] = if self.mouse_path.is_empty() {
+            std::slice::from_ref(&self.mouse)
+        } else {
+            &self.mouse_path
+        };
+        apply_mouse_influence(
+            &mut self.points,
+            mouse.iter().chain(self.pointers.iter()),
+            self.velocity_influence,
+            self.depth_parallax,
+            self.pixel_ratio,
+            share,
+            &filter,
+            &self.spatial_grid,
+        );
+
+        let (width, height, px) = (self.width, self.height, self.pixel_ratio);
+        if let Some(ref mut well) = self.gravity_well {
+            if !well.update(delta_time, width, height, px) {
+                self.gravity_well = None;
+            }
+        }
+        let filter = self.force_filter(EffectTarget::GravityWell);
+        if let Some(ref well) = self.gravity_well {
+            apply_gravity_well(
+                &mut self.points,
+                well,
+                self.pixel_ratio,
+                share,
+                &filter,
+                &self.spatial_grid,
+            );
+        }
+
+        let filter = self.force_filter(EffectTarget::Shockwave);
+        for wave in self.shockwaves.iter() {
+            apply_shockwave(
+                &mut self.points,
+                wave,
+                self.pixel_ratio,
+                share,
+                &filter,
+                &self.spatial_grid,
+            );
+        }
+
+        if let Some(ref mut metrics) = self.interaction_metrics {
+            metrics.end(&self.points, width, height, px, delta_time);
+        }
+
+        self.audio.apply_pulse(
+            &mut self.points,
+            self.width,
+            self.height,
+            self.pixel_ratio,
+            delta_time,
+        );
+        self.run_idle(delta_time);
+
+        let (tilt_x, tilt_y) = self.tilt;
+        if tilt_x != 0.0 || tilt_y != 0.0 {
+            let scale = TILT_DRIFT_SPEED * self.pixel_ratio * delta_time;
+            let (dx, dy) = (tilt_x * scale, tilt_y * scale);
+            for point in self.points.iter_mut().filter(|p| p.pin.is_none()) {
+                self.boundary.translate(point, dx, dy, self.width, self.height);
+            }
+        }
+
+        let (fx, fy) = self.global_force;
+        if fx != 0.0 || fy != 0.0 {
+            let scale = self.pixel_ratio * delta_time;
+            for point in &mut self.points {
+                point.dx += fx * scale;
+                point.dy += fy * scale;
+            }
+        }
+
+        if let Some(turbulence) = self.turbulence.filter(|_| effects) {
+            turbulence.apply(&mut self.points, self.time, self.pixel_ratio, delta_time);
+        }
+
+        if self.density_equalization_enabled && effects {
+            apply_density_equalization(
+                &mut self.points,
+                &self.spatial_grid,
+                &self.boundary,
+                self.density_equalization_strength,
+                delta_time,
+            );
+        }
+
+        if let Some(stiffness) = self.mesh_springs.filter(|_| effects) {
+            apply_mesh_springs(
+                &mut self.points,
+                &mut self.buffers.edges,
+                stiffness,
+                &self.boundary,
+                self.width,
+                self.height,
+                delta_time,
+            );
+        }
+
+        self.exclusions.repel(&mut self.points, delta_time);
+        self.obstacles.push_out(&mut self.points, self.pixel_ratio, &self.spatial_grid);
+
+        if effects {
+            self.update_heat(delta_time);
+        }
+        self.rebalance_density(delta_time);
+        self.maintain(delta_time);
+
+        self.anchors.hold(&mut self.points);
+
+        // Pinned points ignore every force applied above
+        for point in &mut self.points {
+            point.hold();
+        }
+    }
+
+    /// Ease the scroll offset toward its target and shift every point by the step
+    fn apply_scroll(&mut self, delta_time: f32) {
+        let (x, y) = self.scroll_offset;
+        let (target_x, target_y) = self.scroll_target;
+        if x == target_x && y == target_y {
+            return;
+        }
+
+        let follow = 1.0 - SCROLL_SMOOTHING.powf(delta_time);
+        let mut next = (x + (target_x - x) * follow, y + (target_y - y) * follow);
+        let (rest_x, rest_y) = (target_x - next.0, target_y - next.1);
+        if rest_x * rest_x + rest_y * rest_y < SCROLL_SNAP_DISTANCE * SCROLL_SNAP_DISTANCE {
+            next = self.scroll_target;
+        }
+        self.scroll_offset = next;
+
+        let (dx, dy) = ((next.0 - x) * self.pixel_ratio, (next.1 - y) * self.pixel_ratio);
+        for point in &mut self.points {
+            self.boundary.translate(point, dx, dy, self.width, self.height);
+        }
+    }
+
+    /// Spread the mouse over the queued event positions (ending at the current mouse),
+    /// splitting its strength so a sweep pushes as hard as a single sample
+    fn build_mouse_path(&mut self) {
+        self.mouse_path.clear();
+        let events = self.pointer_events.events();
+        if let Some(last) = events.last() {
+            let share = self.mouse.strength / events.len() as f32;
+            for event in &events[..events.len() - 1] {
+                let mut sample = self.mouse.clone();
+                sample.x = event.x;
+                sample.y = event.y;
+                sample.strength = share * event.strength_factor();
+                self.mouse_path.push(sample);
+            }
+            let mut end = self.mouse.clone();
+            end.strength = share * last.strength_factor();
+            self.mouse_path.push(end);
+        }
+        self.pointer_events.finish();
+    }
+
+    /// Deposit heat from interactions, diffuse it, and jitter points in hot regions
+    fn update_heat(&mut self, delta_time: f32) {
+        let Some(heat) = self.heat.as_mut() else {
+            return;
+        };
+        let px = self.pixel_ratio;
+        heat.ensure_size(self.width, self.height, HEAT_CELL_SIZE * px);
+
+        for mouse in std::iter::once(&self.mouse).chain(self.pointers.iter()) {
+            if mouse.in_canvas {
+                let boost = 1.0 + mouse.speed() / px * self.velocity_influence;
+                heat.deposit(mouse.x, mouse.y, HEAT_MOUSE_DEPOSIT * boost * delta_time);
+            }
+        }
+        for wave in self.shockwaves.iter() {
+            heat.deposit(wave.x, wave.y, HEAT_SHOCKWAVE_DEPOSIT * delta_time);
+        }
+        if let Some(ref well) = self.gravity_well {
+            heat.deposit(well.x, well.y, HEAT_GRAVITY_DEPOSIT * delta_time);
+        }
+        heat.update(delta_time);
+
+        let jitter = HEAT_JITTER * px;
+        for point in &mut self.points {
+            let h = heat.sample(point.x, point.y);
+            if h > 0.001 {
+                point.dx += (self.rng.heat.next_f32() - 0.5) * 2.0 * h * jitter;
+                point.dy += (self.rng.heat.next_f32() - 0.5) * 2.0 * h * jitter;
+            }
+        }
+    }
+
+    /// Rebuild spatial grid with optimal cell size
+    fn rebuild_spatial_grid(&mut self) {
+        // Calculate optimal cell size based on effect radii (in canvas pixels)
+        let px = self.pixel_ratio;
+        let max_shockwave_radius = self.shockwaves.max_radius(SHOCKWAVE_WAVE_WIDTH * px);
+        let gravity_range = if self.gravity_well.is_some() {
+            GRAVITY_WELL_MAX_RANGE * px
+        } else {
+            0.0
+        };
+        let max_radius = (self.mouse.radius.max(self.pointers.max_radius()) * px)
+            .max(max_shockwave_radius)
+            .max(gravity_range);
+        let cell_size = (max_radius / 2.0).max(50.0 * px);
+
+        // Resize if needed
+        if (self.spatial_grid.cell_size - cell_size).abs() > 1.0
+            || self.spatial_grid.width != self.width
+            || self.spatial_grid.height != self.height
+        {
+            self.spatial_grid
+                .resize(self.width, self.height, cell_size);
+        } else {
+            self.spatial_grid.clear();
+        }
+
+        // Insert all points
+        for (i, point) in self.points.iter().enumerate() {
+            self.spatial_grid.insert(i, point.x, point.y);
+        }
+    }
+
+    // ========== Triangulation ==========
+
+    /// Perform triangulation and build vertex buffers
+    /// Returns the triangle count, or the constellation edge count when the render mode
+    /// has no mesh (0 for points only)
+    #[wasm_bindgen]
+    pub fn triangulate(&mut self) -> usize {
+        self.compute_occlusion();
+        self.compute_heat();
+        self.compute_motion();
+        self.smooth_output();
+        if let Some(ref mut quiescence) = self.quiescence {
+            let drawn = if self.output_smoothing > 0.0 {
+                &self.smoothed_points
+            } else {
+                &self.points
+            };
+            quiescence.built(drawn, self.buffers.layout, self.render_mode);
+        }
+        self.build_effects_buffer();
+        self.culled_obstacles.clear();
+        self.flagged_exclusions.clear();
+        if self.buffers.layout.has(vertex_attr::EXCLUSION) {
+            self.flagged_exclusions.extend(self.exclusions.flagging());
+        }
+        if self.obstacles.cull {
+            self.culled_obstacles.extend(self.obstacles.resolved(self.pixel_ratio));
+        }
+        if self.render_mode.lines() {
+            // Neighbors must match the final positions of this frame
+            self.rebuild_spatial_grid();
+        }
+
+        let height_scale = self.environment.value(EnvironmentTarget::HeightScale);
+        let params = AttributeParams {
+            depth_parallax: self.depth_parallax,
+            occlusion: &self.occlusion,
+            flips: &self.flips,
+            time: self.time / FRAMES_PER_SECOND,
+            light_dir: (self.light_angle.cos(), self.light_angle.sin()),
+            heat: &self.heat_values,
+            motion: &self.motion,
+            culled: &self.culled_obstacles,
+            exclusions: &self.flagged_exclusions,
+            height_scale,
+            height_intensity: self.height_intensity * height_scale,
+            pixel_ratio: self.pixel_ratio,
+            light: self.light,
+            stroke_jitter: self.audio.stroke_jitter(),
+            frame: self.frame,
+        };
+
+        // Buffers are built from the smoothed positions when output smoothing is on
+        let points = if self.output_smoothing > 0.0 {
+            &self.smoothed_points
+        } else {
+            &self.points
+        };
+        let line_distance = if self.render_mode.lines() {
+            self.constellation_distance * self.pixel_ratio
+        } else {
+            0.0
+        };
+        if !self.render_mode.meshes() {
+            return build_constellation_output(
+                points,
+                self.width,
+                self.height,
+                &self.spatial_grid,
+                line_distance,
+                &params,
+                &mut self.buffers,
+            );
+        }
+
+        let triangles = triangulate(
+            points,
+            self.width,
+            self.height,
+            &self.boundary,
+            &params,
+            &mut self.buffers,
+        );
+        if self.render_mode.lines() {
+            let space = self.buffers.layout.space;
+            let transform = OutputTransform::new(space, self.width, self.height);
+            build_constellation(
+                points,
+                &self.spatial_grid,
+                line_distance,
+                &transform,
+                &mut self.buffers.constellation_vertices,
+            );
+        }
+        triangles
+    }
+
+    /// Ease output positions toward the physics positions (see `set_output_smoothing`)
+    /// Jumps over half the canvas (edge wrapping, respawns) snap instead of gliding
+    fn smooth_output(&mut self) {
+        if self.output_smoothing <= 0.0 {
+            self.smoothed_points.clear();
+            return;
+        }
+        if self.smoothed_points.len() != self.points.len() {
+            self.smoothed_points.clone_from(&self.points);
+            return;
+        }
+
+        let keep = self.output_smoothing;
+        let half_extent = self.width.min(self.height) * 0.5;
+        for (out, point) in self.smoothed_points.iter_mut().zip(&self.points) {
+            let (x, y) = (out.x, out.y);
+            *out = *point;
+            if (point.x - x).abs() < half_extent && (point.y - y).abs() < half_extent {
+                out.x = point.x + (x - point.x) * keep;
+                out.y = point.y + (y - point.y) * keep;
+            }
+        }
+    }
+
+    /// Movement per point since the last build for the STROKE_MOTION attribute
+    /// Jumps over half the canvas (edge wrapping, respawns) count as no movement
+    fn compute_motion(&mut self) {
+        self.motion.clear();
+        if !self.buffers.layout.has(vertex_attr::STROKE_MOTION) {
+            self.prev_positions.clear();
+            return;
+        }
+
+        let half_extent = self.width.min(self.height) * 0.5;
+        for (i, point) in self.points.iter().enumerate() {
+            let moved = match self.prev_positions.get(i) {
+                Some(&(x, y)) => {
+                    let dist = ((point.x - x).powi(2) + (point.y - y).powi(2)).sqrt();
+                    if dist < half_extent {
+                        dist / self.pixel_ratio
+                    } else {
+                        0.0
+                    }
+                }
+                None => 0.0,
+            };
+            self.motion.push(moved);
+        }
+
+        self.prev_positions.clear();
+        self.prev_positions.extend(self.points.iter().map(|p| (p.x, p.y)));
+    }
+
+    /// Sample heat per point for the HEAT attribute
+    fn compute_heat(&mut self) {
+        self.heat_values.clear();
+        if !self.buffers.layout.has(vertex_attr::HEAT) {
+            return;
+        }
+        let heat = self.heat.as_ref();
+        self.heat_values
+            .extend(self.points.iter().map(|p| heat.map_or(0.0, |h| h.sample(p.x, p.y))));
+    }
+
+    /// Approximate ambient occlusion per point from spatial grid crowding
+    /// 0 at or below average density, approaching 1 in very crowded regions
+    fn compute_occlusion(&mut self) {
+        self.occlusion.clear();
+        if !self.buffers.layout.has(vertex_attr::OCCLUSION) {
+            return;
+        }
+
+        let average = self.points.len() as f32 / self.spatial_grid.cell_count() as f32;
+        for point in &self.points {
+            let density = self.spatial_grid.neighborhood_density(point.x, point.y, average);
+            self.occlusion.push((1.0 - 1.0 / density.max(1.0)).clamp(0.0, 1.0));
+        }
+    }
+
+    // ========== Recording & Seeking ==========
+
+    /// Start recording inputs, snapshotting full state every `snapshot_interval` frames
+    /// (the interval doubles each time MAX_RECORDING_SNAPSHOTS is exceeded)
+    /// Restarts any recording in progress
+    #[wasm_bindgen]
+    pub fn start_recording(&mut self, snapshot_interval: u32) {
+        self.recorder = None;
+        let initial = self.snapshot();
+        self.recorder = Some(Box::new(Recorder::new(
+            snapshot_interval,
+            self.frame,
+            initial,
+        )));
+    }
+
+    /// Stop recording and discard recorded history
+    #[wasm_bindgen]
+    pub fn stop_recording(&mut self) {
+        self.recorder = None;
+    }
+
+    /// Check if inputs are being recorded
+    #[wasm_bindgen]
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Current frame (number of physics updates performed)
+    #[wasm_bindgen]
+    pub fn get_frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Recorded frame range as [start, end]
+    #[wasm_bindgen]
+    pub fn get_recording_range(&self) -> js_sys::Uint32Array {
+        let range = match self.recorder {
+            Some(ref recorder) => [recorder.start_frame(), recorder.end_frame()],
+            None => [self.frame, self.frame],
+        };
+        js_sys::Uint32Array::from(&range[..])
+    }
+
+    /// Jump to a recorded frame: restore the nearest earlier snapshot and replay
+    /// recorded inputs up to `frame`. Returns the frame reached (clamped to the
+    /// recorded range). Ticking afterwards overwrites the history after that frame.
+    /// Parameter changes are replayed too; the vertex layout stays as currently set.
+    #[wasm_bindgen]
+    pub fn seek(&mut self, frame: u32) -> u32 {
+        let Some(mut recorder) = self.recorder.take() else {
+            return self.frame;
+        };
+
+        let target = frame.clamp(recorder.start_frame(), recorder.end_frame());
+        let (_, event_index, state) = recorder.snapshot_before(target);
+
+        // Keep the renderer-facing layout, restore everything else
+        let layout = self.buffers.layout;
+        *self = state.clone();
+        self.buffers.layout = layout;
+
+        let mut applied = event_index;
+        for (_, event) in recorder.events_from(event_index) {
+            if event.advances_frame() && self.frame >= target {
+                break;
+            }
+            self.apply_event(event);
+            applied += 1;
+        }
+        recorder.set_cursor(applied);
+        self.recorder = Some(recorder);
+
+        self.triangulate();
+        self.frame
+    }
+
+    /// Wake a settled simulation and force the next build; `record` calls it for every
+    /// recorded setter, input and effect, the unrecorded layout setters call it directly
+    fn output_changed(&mut self) {
+        if let Some(ref mut settle) = self.settle {
+            settle.wake();
+        }
+        if let Some(ref mut quiescence) = self.quiescence {
+            quiescence.invalidate();
+        }
+    }
+
+    /// Record an input event (no-op unless recording)
+    fn record(&mut self, event: InputEvent) {
+        // Every input and effect call passes here, so it also wakes a settled simulation
+        // and forces the next build
+        if !event.advances_frame() {
+            self.output_changed();
+        }
+        let Some(mut recorder) = self.recorder.take() else {
+            return;
+        };
+        if recorder.wants_snapshot(self.frame, &event) {
+            let state = self.snapshot();
+            recorder.push_snapshot(self.frame, state);
+        }
+        recorder.push_event(self.frame, event);
+        self.recorder = Some(recorder);
+    }
+
+    /// Copy of the current state without output buffers (they are rebuilt on demand)
+    fn snapshot(&mut self) -> Simulation {
+        let kept = self.buffers.without_output();
+        let buffers = std::mem::replace(&mut self.buffers, kept);
+        let state = self.clone();
+        self.buffers = buffers;
+        state
+    }
+
+    /// Run `f` with recording detached, so setters it calls are not logged a second time
+    fn unrecorded<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let recorder = self.recorder.take();
+        let result = f(self);
+        self.recorder = recorder;
+        result
+    }
+
+    /// Re-apply a recorded input event (recorder is detached while replaying)
+    fn apply_event(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::Tick {
+                delta_time,
+                speed,
+                mouse_x,
+                mouse_y,
+                mouse_in_canvas,
+                mouse_radius,
+                mouse_strength,
+                mouse_mode,
+            } => {
+                self.tick(
+                    delta_time,
+                    speed,
+                    mouse_x,
+                    mouse_y,
+                    mouse_in_canvas,
+                    mouse_radius,
+                    mouse_strength,
+                    mouse_mode,
+                );
+            }
+            InputEvent::MouseState {
+                x,
+                y,
+                in_canvas,
+                radius,
+                strength,
+                mode,
+            } => {
+                self.set_mouse_state(x, y, in_canvas, radius, strength, mode)
+            }
+            InputEvent::UpdatePoints { delta_time, speed } => self.update_points(delta_time, speed),
+            InputEvent::Step { delta_time } => {
+                self.step(delta_time);
+            }
+            InputEvent::Warmup { steps } => {
+                self.warmup(steps);
+            }
+            InputEvent::Pause => self.pause(),
+            InputEvent::Resume => self.resume(),
+            InputEvent::Shockwave { x, y, strength } => {
+                self.trigger_shockwave(x, y, strength);
+            }
+            InputEvent::FlipRegion {
+                x,
+                y,
+                width,
+                height,
+                stagger,
+            } => {
+                self.trigger_flip_region(x, y, width, height, stagger)
+            }
+            InputEvent::GravityWell {
+                x,
+                y,
+                active,
+                attract,
+            } => {
+                self.set_gravity_well(x, y, active, attract);
+            }
+            InputEvent::GravityWellPosition { x, y } => self.update_gravity_well_position(x, y),
+            InputEvent::CursorEmitter { rate, strength } => {
+                self.attach_shockwave_emitter_to_cursor(rate, strength)
+            }
+            InputEvent::Rain {
+                enabled,
+                rate,
+                min_strength,
+                max_strength,
+            } => {
+                self.set_rain(enabled, rate, min_strength, max_strength)
+            }
+            InputEvent::PathEmitter {
+                ref path,
+                rate,
+                strength,
+            } => {
+                self.add_moving_emitter(path, rate, strength);
+            }
+            InputEvent::RemoveEmitter { id } => self.remove_emitter(id),
+            InputEvent::Pointer {
+                id,
+                x,
+                y,
+                active,
+                radius,
+                strength,
+                mode,
+            } => {
+                self.set_pointer(id, x, y, active, radius, strength, mode)
+            }
+            InputEvent::RemovePointer { id } => self.remove_pointer(id),
+            InputEvent::Environment { hour, day_of_year } => {
+                self.set_environment(hour, day_of_year);
+            }
+            InputEvent::AudioLevels { bass, mid, treble } => {
+                self.set_audio_levels(bass, mid, treble);
+            }
+            InputEvent::Tilt { gx, gy } => self.set_tilt(gx, gy),
+            InputEvent::ScrollOffset { x, y } => self.set_scroll_offset(x, y),
+            InputEvent::StickyCursor {
+                enabled,
+                break_force,
+            } => {
+                self.set_sticky_cursor(enabled, break_force)
+            }
+            InputEvent::ScheduleShockwave {
+                delay_ms,
+                x,
+                y,
+                strength,
+            } => {
+                self.schedule_shockwave(delay_ms, x, y, strength);
+            }
+            InputEvent::ScheduleGravityWell {
+                start_ms,
+                end_ms,
+                x,
+                y,
+                attract,
+            } => {
+                self.schedule_gravity_well(start_ms, end_ms, x, y, attract);
+            }
+            InputEvent::ClearSchedule => self.clear_schedule(),
+            InputEvent::Implosion {
+                x,
+                y,
+                strength,
+                start_radius,
+            } => {
+                self.trigger_implosion(x, y, strength, start_radius);
+            }
+            InputEvent::CancelEffect { id } => {
+                self.cancel_effect(id);
+            }
+            InputEvent::ClearEffects => self.clear_effects(),
+            InputEvent::PointerEvent {
+                x,
+                y,
+                t,
+                pressure,
+                buttons,
+            } => {
+                self.push_pointer_event(x, y, t, pressure, buttons)
+            }
+            InputEvent::AddObstacle { x, y, radius } => {
+                self.add_obstacle_circle(x, y, radius);
+            }
+            InputEvent::RemoveObstacle { id } => self.remove_obstacle(id),
+            InputEvent::ClearObstacles => self.clear_obstacles(),
+            InputEvent::AnchorPoints { ref positions } => self.set_anchor_points(positions),
+            InputEvent::AddExclusion {
+                x,
+                y,
+                width,
+                height,
+                mode,
+            } => {
+                self.add_exclusion_rect(x, y, width, height, mode);
+            }
+            InputEvent::RemoveExclusion { id } => self.remove_exclusion_rect(id),
+            InputEvent::ClearExclusions => self.clear_exclusion_rects(),
+            InputEvent::PinPoint { index } => self.pin_point(index),
+            InputEvent::UnpinPoint { index } => self.unpin_point(index),
+            InputEvent::PinPointsNear { x, y, radius } => {
+                self.pin_points_near(x, y, radius);
+            }
+            InputEvent::PointLayer { index, layer } => self.set_point_layer(index, layer),
+            InputEvent::PointsLayerNear {
+                x,
+                y,
+                radius,
+                layer,
+            } => {
+                self.set_points_layer_near(x, y, radius, layer);
+            }
+            InputEvent::DensityMap {
+                ref data,
+                cols,
+                rows,
+            } => {
+                self.set_density_map(data, cols, rows);
+            }
+            InputEvent::DensityRebalance { rate } => self.set_density_rebalance(rate),
+            InputEvent::Positions { ref data } => {
+                self.set_positions(data);
+            }
+            InputEvent::Velocities { ref data } => {
+                self.set_velocities(data);
+            }
+            InputEvent::AddPoint { x, y } => {
+                self.add_point(x, y);
+            }
+            InputEvent::RemovePoint { index } => self.remove_point(index),
+            InputEvent::RemovePointsInRadius { x, y, radius } => {
+                self.remove_points_in_radius(x, y, radius);
+            }
+            InputEvent::Params { ref preset } => self.apply_preset_values(preset),
+            InputEvent::Handoff { ref bytes } => {
+                let _ = self.import_handoff(bytes);
+            }
+            InputEvent::NoiseParams {
+                noise_scale,
+                height_intensity,
+            } => {
+                self.set_noise_params(noise_scale, height_intensity)
+            }
+            InputEvent::DevicePixelRatio { dpr } => self.set_device_pixel_ratio(dpr),
+            InputEvent::InputFilter { min_cutoff, beta } => self.set_input_filter(min_cutoff, beta),
+            InputEvent::PhysicsParams {
+                spring_back,
+                damping,
+                velocity_influence,
+            } => {
+                self.set_physics_params(spring_back, damping, velocity_influence)
+            }
+            InputEvent::DensityEqualization { enabled, strength } => {
+                self.set_density_equalization(enabled, strength)
+            }
+            InputEvent::QualityTier { tier } => self.set_quality_tier(tier),
+            InputEvent::AdaptiveQuality {
+                enabled,
+                target_frame_ms,
+            } => {
+                self.set_adaptive_quality(enabled, target_frame_ms)
+            }
+            InputEvent::FrameTime { frame_ms } => {
+                self.report_frame_time(frame_ms);
+            }
+            InputEvent::EnvironmentCurve {
+                target,
+                ref daily,
+                ref seasonal,
+            } => {
+                self.set_environment_curve(target, daily, seasonal);
+            }
+            InputEvent::AudioResponse {
+                pulse,
+                drift_boost,
+                jitter,
+            } => {
+                self.set_audio_response(pulse, drift_boost, jitter)
+            }
+            InputEvent::GlobalForce { fx, fy } => self.set_global_force(fx, fy),
+            InputEvent::MeshSprings { enabled, stiffness } => {
+                self.set_mesh_springs(enabled, stiffness)
+            }
+            InputEvent::ForceHeightMask { min_z, max_z } => {
+                self.set_force_height_mask(min_z, max_z)
+            }
+            InputEvent::BoundaryMode { mode } => self.set_boundary_mode(mode),
+            InputEvent::WindTunnelParams {
+                direction,
+                flow_speed,
+                turbulence,
+            } => {
+                self.set_wind_tunnel_params(direction, flow_speed, turbulence)
+            }
+            InputEvent::PointDepth { mode, parallax } => self.set_point_depth(mode, parallax),
+            InputEvent::FlowField {
+                enabled,
+                scale,
+                strength,
+                time_speed,
+            } => {
+                self.set_flow_field(enabled, scale, strength, time_speed)
+            }
+            InputEvent::Turbulence {
+                amount,
+                scale,
+                speed,
+            } => {
+                self.set_turbulence(amount, scale, speed)
+            }
+            InputEvent::HeatField {
+                enabled,
+                diffusion,
+                retention,
+            } => {
+                self.set_heat_field(enabled, diffusion, retention)
+            }
+            InputEvent::InteractionMetrics { window_minutes } => {
+                self.set_interaction_metrics(window_minutes)
+            }
+            InputEvent::LightDirection { angle } => self.set_light_direction(angle),
+            InputEvent::Light {
+                dir_x,
+                dir_y,
+                dir_z,
+                ambient,
+            } => {
+                self.set_light(dir_x, dir_y, dir_z, ambient)
+            }
+            InputEvent::MaxCatchup { frames } => self.set_max_catchup(frames),
+            InputEvent::FixedTimestep { hz } => self.set_fixed_timestep(hz),
+            InputEvent::Substeps { n } => self.set_substeps(n),
+            InputEvent::TimeScale { scale } => self.set_time_scale(scale),
+            InputEvent::HeightBlur { iterations } => self.set_height_blur(iterations),
+            InputEvent::MinTriangleQuality { min_angle_deg } => {
+                self.set_min_triangle_quality(min_angle_deg)
+            }
+            InputEvent::IncrementalTriangulation { enabled, epsilon } => {
+                self.set_incremental_triangulation(enabled, epsilon)
+            }
+            InputEvent::GhostMargin { px } => self.set_ghost_margin_px(px),
+            InputEvent::BaseTopology { enabled } => self.set_base_topology(enabled),
+            InputEvent::TopologyHysteresis { margin_deg } => {
+                self.set_topology_hysteresis(margin_deg)
+            }
+            InputEvent::TriangleMetadata { enabled } => self.set_triangle_metadata(enabled),
+            InputEvent::OutputSmoothing { alpha } => self.set_output_smoothing(alpha),
+            InputEvent::ConstellationMode { max_distance } => {
+                self.set_constellation_mode(max_distance)
+            }
+            InputEvent::RenderMode { mode } => self.set_render_mode(mode),
+            InputEvent::BuiltinPreset { ref name } => {
+                self.apply_preset(name);
+            }
+            InputEvent::ShockwaveLimit { capacity, policy } => {
+                self.set_shockwave_limit(capacity, policy)
+            }
+            InputEvent::ObstacleCulling { enabled } => self.set_obstacle_culling(enabled),
+            InputEvent::ClearEmitters => self.clear_emitters(),
+            InputEvent::GravityWellThrow {
+                enabled,
+                friction,
+                bounce,
+            } => {
+                self.set_gravity_well_throw(enabled, friction, bounce)
+            }
+            InputEvent::Resize {
+                new_width,
+                new_height,
+            } => {
+                self.resize(new_width, new_height)
+            }
+            InputEvent::PointCount { count, seed } => self.set_point_count(count, seed),
+            InputEvent::Distribution { mode, jitter } => self.set_distribution(mode, jitter),
+            InputEvent::SpiralDistribution {
+                center_x,
+                center_y,
+                falloff,
+            } => {
+                self.set_spiral_distribution(center_x, center_y, falloff)
+            }
+            InputEvent::Watchdog { interval_seconds } => self.set_watchdog(interval_seconds),
+            InputEvent::AutoMaintenance { interval_seconds } => {
+                self.set_auto_maintenance(interval_seconds)
+            }
+            InputEvent::IdleBehavior {
+                enabled,
+                delay_ms,
+                intensity,
+            } => {
+                self.set_idle_behavior(enabled, delay_ms, intensity)
+            }
+            InputEvent::StaticAfterSettle { enabled, threshold } => {
+                self.set_static_after_settle(enabled, threshold)
+            }
+            InputEvent::QuiescenceEpsilon { epsilon } => self.set_quiescence_epsilon(epsilon),
+        }
+    }
+
+    // ========== Data Access ==========
+
+    /// Get triangle vertices as Float32Array
+    #[wasm_bindgen]
+    pub fn get_triangle_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.triangle_vertices) }
+    }
+
+    /// Get the Float64 triangle vertices as Float64Array (empty unless enabled)
+    #[wasm_bindgen]
+    pub fn get_triangle_vertices_f64(&self) -> js_sys::Float64Array {
+        unsafe { js_sys::Float64Array::view(&self.buffers.triangle_vertices_f64) }
+    }
+
+    /// Normal map of the triangulated height field as RGB bytes (cols x rows, rows top
+    /// to bottom; R = x right, G = y up, B = out of the screen, 128 = 0)
+    /// Rebuilt from the last triangulation when the size changes or every 10 frames,
+    /// sides are clamped to 1..=256; flat (128, 128, 255) in render modes without a mesh
+    #[wasm_bindgen]
+    pub fn get_normal_map(&mut self, cols: usize, rows: usize) -> js_sys::Uint8Array {
+        let cols = cols.clamp(1, MAX_NORMAL_MAP_SIZE);
+        let rows = rows.clamp(1, MAX_NORMAL_MAP_SIZE);
+        if self.normal_map.stale(cols, rows, self.frame, NORMAL_MAP_INTERVAL_FRAMES) {
+            let (vertices, triangles) = self.buffers.mesh();
+            self.normal_map.rebuild(
+                vertices,
+                triangles,
+                cols,
+                rows,
+                self.width,
+                self.height,
+                self.height_intensity,
+                self.frame,
+            );
+        }
+        js_sys::Uint8Array::from(&self.normal_map.pixels[..])
+    }
+
+    /// Get shared vertices (indexed mode) as Float32Array
+    #[wasm_bindgen]
+    pub fn get_indexed_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.indexed_vertices) }
+    }
+
+    /// Get triangle indices (indexed mode) as Uint32Array
+    #[wasm_bindgen]
+    pub fn get_triangle_indices(&self) -> js_sys::Uint32Array {
+        unsafe { js_sys::Uint32Array::view(&self.buffers.indices) }
+    }
+
+    /// Get stroke line indices into the shared vertices (indexed mode) as Uint32Array
+    /// Two per unique edge, for `drawElements(LINES)`; empty with bundled edges, which
+    /// keep using `get_stroke_vertices`. Per-edge stroke attributes are not emitted
+    #[wasm_bindgen]
+    pub fn get_stroke_indices(&self) -> js_sys::Uint32Array {
+        unsafe { js_sys::Uint32Array::view(&self.buffers.stroke_indices) }
+    }
+
+    /// Get Voronoi cell triangle vertices as Float32Array (stride 6)
+    #[wasm_bindgen]
+    pub fn get_voronoi_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.voronoi.cells) }
+    }
+
+    /// Get Voronoi edge vertices as Float32Array: [x1, y1, x2, y2] per edge
+    #[wasm_bindgen]
+    pub fn get_voronoi_edge_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.voronoi.edges) }
+    }
+
+    /// Get constellation edges as Float32Array: [x1, y1, x2, y2, alpha] per edge
+    #[wasm_bindgen]
+    pub fn get_constellation_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.constellation_vertices) }
+    }
+
+    /// Get number of constellation edges
+    #[wasm_bindgen]
+    pub fn get_constellation_edge_count(&self) -> usize {
+        self.buffers.constellation_edge_count()
+    }
+
+    /// Active effects as of the last physics update or triangulation, so also on ticks
+    /// that skip triangulation, for drawing matching overlay rings and glows:
+    /// [kind, id, x, y, radius, ring width, strength] per effect in canvas
+    /// pixels; kind 0 = shockwave (strength fades 1 -> 0), 1 = attracting well,
+    /// 2 = repelling well (radius = range, strength 1); id as from `cancel_effect` handles
+    #[wasm_bindgen]
+    pub fn get_effects_buffer(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.effects_buffer) }
+    }
+
+    /// Number of entries in the effects buffer
+    #[wasm_bindgen]
+    pub fn get_effect_count(&self) -> usize {
+        self.effects_buffer.len() / EFFECT_STRIDE
+    }
+
+    /// Get stroke vertices as Float32Array
+    #[wasm_bindgen]
+    pub fn get_stroke_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.stroke_vertices) }
+    }
+
+    /// Get point vertices as Float32Array
+    #[wasm_bindgen]
+    pub fn get_point_vertices(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.point_vertices) }
+    }
+
+    /// Get per-triangle metadata as Float32Array (see `set_triangle_metadata`):
+    /// [centroid x, centroid y, area, avg height] per drawn triangle, in the order of
+    /// the triangle buffers; centroids in the output space, area in canvas pixels²
+    #[wasm_bindgen]
+    pub fn get_triangle_metadata(&self) -> Float32Array {
+        unsafe { Float32Array::view(&self.buffers.triangle_metadata) }
+    }
+
+    /// Get every vertex and index buffer in one Float32Array, for a single upload
+    /// Starts with an 18-float header of [offset, length, stride] for the triangle,
+    /// stroke, point and indexed vertex sections and the triangle and stroke index
+    /// sections (offsets in floats from the start); data follows back to back. Indices
+    /// keep their u32 bits, read them through a Uint32Array over the same range
+    /// Rebuilt only when a triangulation changed the buffers since the last call
+    #[wasm_bindgen]
+    pub fn get_combined_buffer(&mut self) -> Float32Array {
+        self.buffers.build_combined();
+        unsafe { Float32Array::view(&self.buffers.combined) }
+    }
+
+    // The views above alias WASM memory and go stale (detached) when it grows; the
+    // copies and pointers below let JS own the data or rebuild views on demand
+
+    /// Counter that changes whenever previously returned buffer views may be invalid:
+    /// a view-backed buffer reallocated or WASM memory grew since the last call
+    /// Check once per frame and re-acquire views when it differs from the last value
+    #[wasm_bindgen]
+    pub fn memory_generation(&mut self) -> u32 {
+        let b = &self.buffers;
+        let addresses = [
+            b.triangle_vertices.as_ptr() as usize,
+            b.triangle_vertices_f64.as_ptr() as usize,
+            b.stroke_vertices.as_ptr() as usize,
+            b.point_vertices.as_ptr() as usize,
+            b.indexed_vertices.as_ptr() as usize,
+            b.indices.as_ptr() as usize,
+            b.stroke_indices.as_ptr() as usize,
+            b.voronoi.cells.as_ptr() as usize,
+            b.voronoi.edges.as_ptr() as usize,
+            b.constellation_vertices.as_ptr() as usize,
+            b.combined.as_ptr() as usize,
+            b.triangle_metadata.as_ptr() as usize,
+            self.effects_buffer.as_ptr() as usize,
+        ];
+        self.memory.observe(&addresses)
+    }
+
+    /// Copy triangle vertices into `dst`; returns the number of floats copied
+    /// (at most `get_triangle_vertices_len`, fewer when `dst` is shorter)
+    #[wasm_bindgen]
+    pub fn copy_triangle_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.triangle_vertices, dst)
+    }
+
+    /// Copy stroke vertices into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_stroke_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.stroke_vertices, dst)
+    }
+
+    /// Copy point vertices into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_point_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.point_vertices, dst)
+    }
+
+    /// Byte offset of the triangle vertices in WASM memory, for
+    /// `new Float32Array(memory.buffer, ptr, len)`; valid until the next triangulate
+    #[wasm_bindgen]
+    pub fn get_triangle_vertices_ptr(&self) -> usize {
+        self.buffers.triangle_vertices.as_ptr() as usize
+    }
+
+    /// Number of floats in the triangle vertex buffer
+    #[wasm_bindgen]
+    pub fn get_triangle_vertices_len(&self) -> usize {
+        self.buffers.triangle_vertices.len()
+    }
+
+    /// Byte offset of the stroke vertices in WASM memory
+    #[wasm_bindgen]
+    pub fn get_stroke_vertices_ptr(&self) -> usize {
+        self.buffers.stroke_vertices.as_ptr() as usize
+    }
+
+    /// Number of floats in the stroke vertex buffer
+    #[wasm_bindgen]
+    pub fn get_stroke_vertices_len(&self) -> usize {
+        self.buffers.stroke_vertices.len()
+    }
+
+    /// Byte offset of the point vertices in WASM memory
+    #[wasm_bindgen]
+    pub fn get_point_vertices_ptr(&self) -> usize {
+        self.buffers.point_vertices.as_ptr() as usize
+    }
+
+    /// Number of floats in the point vertex buffer
+    #[wasm_bindgen]
+    pub fn get_point_vertices_len(&self) -> usize {
+        self.buffers.point_vertices.len()
+    }
+
+    /// Copy the Float64 triangle vertices into `dst`; returns the number of values copied
+    #[wasm_bindgen]
+    pub fn copy_triangle_vertices_f64_into(&self, dst: &mut [f64]) -> usize {
+        copy_into(&self.buffers.triangle_vertices_f64, dst)
+    }
+
+    /// Byte offset of the Float64 triangle vertices in WASM memory
+    #[wasm_bindgen]
+    pub fn get_triangle_vertices_f64_ptr(&self) -> usize {
+        self.buffers.triangle_vertices_f64.as_ptr() as usize
+    }
+
+    /// Number of values in the Float64 triangle vertices
+    #[wasm_bindgen]
+    pub fn get_triangle_vertices_f64_len(&self) -> usize {
+        self.buffers.triangle_vertices_f64.len()
+    }
+
+    /// Copy the shared vertices (indexed mode) into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_indexed_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.indexed_vertices, dst)
+    }
+
+    /// Byte offset of the shared vertices (indexed mode) in WASM memory
+    #[wasm_bindgen]
+    pub fn get_indexed_vertices_ptr(&self) -> usize {
+        self.buffers.indexed_vertices.as_ptr() as usize
+    }
+
+    /// Number of floats in the shared vertices (indexed mode)
+    #[wasm_bindgen]
+    pub fn get_indexed_vertices_len(&self) -> usize {
+        self.buffers.indexed_vertices.len()
+    }
+
+    /// Copy the triangle indices (indexed mode) into `dst`; returns the number of indices copied
+    #[wasm_bindgen]
+    pub fn copy_triangle_indices_into(&self, dst: &mut [u32]) -> usize {
+        copy_into(&self.buffers.indices, dst)
+    }
+
+    /// Byte offset of the triangle indices (indexed mode) in WASM memory
+    #[wasm_bindgen]
+    pub fn get_triangle_indices_ptr(&self) -> usize {
+        self.buffers.indices.as_ptr() as usize
+    }
+
+    /// Number of indices in the triangle indices (indexed mode)
+    #[wasm_bindgen]
+    pub fn get_triangle_indices_len(&self) -> usize {
+        self.buffers.indices.len()
+    }
+
+    /// Copy the stroke line indices (indexed mode) into `dst`; returns the number of indices copied
+    #[wasm_bindgen]
+    pub fn copy_stroke_indices_into(&self, dst: &mut [u32]) -> usize {
+        copy_into(&self.buffers.stroke_indices, dst)
+    }
+
+    /// Byte offset of the stroke line indices (indexed mode) in WASM memory
+    #[wasm_bindgen]
+    pub fn get_stroke_indices_ptr(&self) -> usize {
+        self.buffers.stroke_indices.as_ptr() as usize
+    }
+
+    /// Number of indices in the stroke line indices (indexed mode)
+    #[wasm_bindgen]
+    pub fn get_stroke_indices_len(&self) -> usize {
+        self.buffers.stroke_indices.len()
+    }
+
+    /// Copy the Voronoi cell vertices into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_voronoi_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.voronoi.cells, dst)
+    }
+
+    /// Byte offset of the Voronoi cell vertices in WASM memory
+    #[wasm_bindgen]
+    pub fn get_voronoi_vertices_ptr(&self) -> usize {
+        self.buffers.voronoi.cells.as_ptr() as usize
+    }
+
+    /// Number of floats in the Voronoi cell vertices
+    #[wasm_bindgen]
+    pub fn get_voronoi_vertices_len(&self) -> usize {
+        self.buffers.voronoi.cells.len()
+    }
+
+    /// Copy the Voronoi edge vertices into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_voronoi_edge_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.voronoi.edges, dst)
+    }
+
+    /// Byte offset of the Voronoi edge vertices in WASM memory
+    #[wasm_bindgen]
+    pub fn get_voronoi_edge_vertices_ptr(&self) -> usize {
+        self.buffers.voronoi.edges.as_ptr() as usize
+    }
+
+    /// Number of floats in the Voronoi edge vertices
+    #[wasm_bindgen]
+    pub fn get_voronoi_edge_vertices_len(&self) -> usize {
+        self.buffers.voronoi.edges.len()
+    }
+
+    /// Copy the constellation edges into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_constellation_vertices_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.constellation_vertices, dst)
+    }
+
+    /// Byte offset of the constellation edges in WASM memory
+    #[wasm_bindgen]
+    pub fn get_constellation_vertices_ptr(&self) -> usize {
+        self.buffers.constellation_vertices.as_ptr() as usize
+    }
+
+    /// Number of floats in the constellation edges
+    #[wasm_bindgen]
+    pub fn get_constellation_vertices_len(&self) -> usize {
+        self.buffers.constellation_vertices.len()
+    }
+
+    /// Copy the effects buffer into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_effects_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.effects_buffer, dst)
+    }
+
+    /// Byte offset of the effects buffer in WASM memory
+    #[wasm_bindgen]
+    pub fn get_effects_ptr(&self) -> usize {
+        self.effects_buffer.as_ptr() as usize
+    }
+
+    /// Number of floats in the effects buffer
+    #[wasm_bindgen]
+    pub fn get_effects_len(&self) -> usize {
+        self.effects_buffer.len()
+    }
+
+    /// Copy the per-triangle metadata into `dst`; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_triangle_metadata_into(&self, dst: &mut [f32]) -> usize {
+        copy_into(&self.buffers.triangle_metadata, dst)
+    }
+
+    /// Byte offset of the per-triangle metadata in WASM memory
+    #[wasm_bindgen]
+    pub fn get_triangle_metadata_ptr(&self) -> usize {
+        self.buffers.triangle_metadata.as_ptr() as usize
+    }
+
+    /// Number of floats in the per-triangle metadata
+    #[wasm_bindgen]
+    pub fn get_triangle_metadata_len(&self) -> usize {
+        self.buffers.triangle_metadata.len()
+    }
+
+    /// Copy the combined buffer (see `get_combined_buffer`) into `dst`, building it first
+    /// if needed; returns the number of floats copied
+    #[wasm_bindgen]
+    pub fn copy_combined_into(&mut self, dst: &mut [f32]) -> usize {
+        self.buffers.build_combined();
+        copy_into(&self.buffers.combined, dst)
+    }
+
+    /// Byte offset of the combined buffer in WASM memory, building it first if needed
+    #[wasm_bindgen]
+    pub fn get_combined_ptr(&mut self) -> usize {
+        self.buffers.build_combined();
+        self.buffers.combined.as_ptr() as usize
+    }
+
+    /// Number of floats in the combined buffer, building it first if needed
+    #[wasm_bindgen]
+    pub fn get_combined_len(&mut self) -> usize {
+        self.buffers.build_combined();
+        self.buffers.combined.len()
+    }
+
+    /// Get number of triangles
+    #[wasm_bindgen]
+    pub fn get_triangle_count(&self) -> usize {
+        self.buffers.triangle_count()
+    }
+
+    /// Get number of stroke line segments
+    #[wasm_bindgen]
+    pub fn get_stroke_vertex_count(&self) -> usize {
+        self.buffers.stroke_vertex_count()
+    }
+
+    /// Get number of points
+    #[wasm_bindgen]
+    pub fn get_point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Index of the point nearest to (x, y) canvas pixels, e.g. for hover tooltips or
+    /// dragging; u32::MAX when there are no points. Rebuilds the spatial grid first, since
+    /// points keep moving after each update builds it
+    #[wasm_bindgen]
+    pub fn find_nearest_point(&mut self, x: f32, y: f32) -> u32 {
+        self.rebuild_spatial_grid();
+        let points = &self.points;
+        let position = |i: usize| (points[i].x, points[i].y);
+        self.spatial_grid.nearest(x, y, position).map_or(u32::MAX, |i| i as u32)
+    }
+
+    /// Indices of the points within `radius` (CSS pixels) of (x, y) canvas pixels,
+    /// nearest first; rebuilds the spatial grid like `find_nearest_point`
+    #[wasm_bindgen]
+    pub fn find_points_in_radius(&mut self, x: f32, y: f32, radius: f32) -> js_sys::Uint32Array {
+        self.rebuild_spatial_grid();
+        let radius = radius.max(0.0) * self.pixel_ratio;
+        let mut found: Vec<(f32, u32)> = self
+            .spatial_grid
+            .query_radius(x, y, radius)
+            .filter_map(|i| {
+                let p = &self.points[i];
+                let dist_sq = (p.x - x).powi(2) + (p.y - y).powi(2);
+                (dist_sq <= radius * radius).then_some((dist_sq, i as u32))
+            })
+            .collect();
+        found.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        let indices: Vec<u32> = found.into_iter().map(|(_, i)| i).collect();
+        js_sys::Uint32Array::from(&indices[..])
+    }
+
+    /// Get current point positions as a Float32Array copy: [x, y, ...] canvas pixels
+    /// in point order
+    #[wasm_bindgen]
+    pub fn get_positions(&self) -> Float32Array {
+        let positions: Vec<f32> = self.points.iter().flat_map(|p| [p.x, p.y]).collect();
+        Float32Array::from(&positions[..])
+    }
+
+    /// Get point drift velocities as a Float32Array copy: [vx, vy, ...] canvas pixels
+    /// per frame at speed 1, in point order
+    #[wasm_bindgen]
+    pub fn get_velocities(&self) -> Float32Array {
+        let velocities: Vec<f32> = self.points.iter().flat_map(|p| [p.vx, p.vy]).collect();
+        Float32Array::from(&velocities[..])
     }
 
     // ========== Combined Operations ==========
@@ -330,6 +4042,7 @@ impl Simulation {
     /// Combined tick method - reduces JS-WASM boundary crossings
     /// Performs update_points + triangulate in a single call
     #[wasm_bindgen]
+    #[allow(clippy::too_many_arguments)]
     pub fn tick(
         &mut self,
         delta_time: f32,
@@ -341,31 +4054,383 @@ impl Simulation {
         mouse_strength: f32,
         mouse_mode: u32,
     ) -> usize {
-        // Update mouse state
-        self.set_mouse_state(
+        #[cfg(feature = "alloc-audit")]
+        let allocations_before = crate::alloc_audit::allocation_count();
+
+        self.record(InputEvent::Tick {
+            delta_time,
+            speed,
             mouse_x,
             mouse_y,
             mouse_in_canvas,
             mouse_radius,
             mouse_strength,
             mouse_mode,
-        );
+        });
+        if self.paused {
+            self.buffers_changed = false;
+            return self.buffers.triangle_count();
+        }
+        self.last_speed = speed;
+        let was_in_canvas = self.mouse.in_canvas;
+
+        // Update mouse state (filtered with the real frame delta)
+        let (mouse_x, mouse_y) =
+            self.pointer_events.events().last().map_or((mouse_x, mouse_y), |e| (e.x, e.y));
+        let (mouse_x, mouse_y) = self.filter_pointer(mouse_x, mouse_y, mouse_in_canvas, delta_time);
+        match self.pointer_events.velocity() {
+            Some((vx, vy)) => self.mouse.update_measured(
+                mouse_x,
+                mouse_y,
+                mouse_in_canvas,
+                mouse_radius,
+                mouse_strength,
+                mouse_mode,
+                (vx * delta_time, vy * delta_time),
+            ),
+            None => self.mouse.update(
+                mouse_x,
+                mouse_y,
+                mouse_in_canvas,
+                mouse_radius,
+                mouse_strength,
+                mouse_mode,
+            ),
+        }
+        self.build_mouse_path();
+
+        // Asleep after settling until the pointer moves, enters or leaves
+        if let Some(ref mut settle) = self.settle {
+            let moved = self.mouse.in_canvas
+                && self.mouse.speed() > SETTLE_WAKE_SPEED * self.pixel_ratio;
+            if moved || self.mouse.in_canvas != was_in_canvas {
+                settle.wake();
+            } else if settle.is_asleep() {
+                self.mouse_path.clear();
+                self.buffers_changed = false;
+                return self.buffers.triangle_count();
+            }
+        }
 
         // Update physics
-        self.update_points(delta_time, speed);
+        self.advance(delta_time, speed);
+        self.mouse_path.clear();
+
+        // Triangulate at the tier's cadence unless quiescent (skipped ticks keep the
+        // last buffers)
+        let interval = QualityTier::get(self.quality_tier).triangulation_interval;
+        self.buffers_changed = self.frame.is_multiple_of(interval) && !self.quiescent();
+        let triangle_count = if self.buffers_changed {
+            self.watched_triangulate(delta_time)
+        } else {
+            self.buffers.triangle_count()
+        };
+
+        #[cfg(feature = "alloc-audit")]
+        {
+            self.tick_allocations =
+                crate::alloc_audit::allocation_count().wrapping_sub(allocations_before);
+        }
 
-        // Triangulate and return triangle count
-        self.triangulate()
+        triangle_count
+    }
+
+    /// Allocator calls during the last tick (alloc-audit builds only)
+    /// Steady state is zero apart from delaunator's own buffers; recording adds snapshots
+    #[cfg(feature = "alloc-audit")]
+    #[wasm_bindgen]
+    pub fn get_tick_allocations(&self) -> u32 {
+        self.tick_allocations
     }
 
     /// Get all vertex data sizes for buffer pre-allocation
+    /// [triangle, stroke, point, indexed vertex, index, stroke index] element counts
     #[wasm_bindgen]
     pub fn get_buffer_sizes(&self) -> js_sys::Uint32Array {
-        let sizes = [
+        js_sys::Uint32Array::from(&self.buffer_sizes()[..])
+    }
+
+    fn buffer_sizes(&self) -> [u32; 6] {
+        [
             self.buffers.triangle_vertices.len() as u32,
             self.buffers.stroke_vertices.len() as u32,
             self.buffers.point_vertices.len() as u32,
-        ];
-        js_sys::Uint32Array::from(&sizes[..])
+            self.buffers.indexed_vertices.len() as u32,
+            self.buffers.indices.len() as u32,
+            self.buffers.stroke_indices.len() as u32,
+        ]
+    }
+
+    // ========== Worker Protocol ==========
+
+    /// Apply every command queued by the main thread (worker side), in order
+    /// Returns the number of commands applied
+    #[wasm_bindgen]
+    pub fn drain_commands(&mut self, queue: &CommandQueue) -> u32 {
+        let mut applied = 0;
+        while let Some(command) = queue.pop() {
+            self.apply_command(command);
+            applied += 1;
+        }
+        applied
+    }
+
+    /// Frame metadata to post with the vertex arrays (worker side)
+    /// [frame, triangle count, stroke vertex count, point count, triangle stride,
+    /// stroke stride, point stride, ...`get_buffer_sizes`]
+    #[wasm_bindgen]
+    pub fn get_frame_info(&self) -> js_sys::Uint32Array {
+        let info = FrameInfo {
+            frame: self.frame,
+            triangle_count: self.get_triangle_count() as u32,
+            stroke_vertex_count: self.get_stroke_vertex_count() as u32,
+            point_count: self.points.len() as u32,
+            triangle_stride: self.get_triangle_stride() as u32,
+            stroke_stride: self.get_stroke_stride() as u32,
+            point_stride: self.get_point_stride() as u32,
+            buffer_sizes: self.buffer_sizes(),
+        };
+        js_sys::Uint32Array::from(&info.words()[..])
+    }
+
+    fn apply_command(&mut self, command: Command) {
+        match command {
+            Command::Tick {
+                delta_time,
+                speed,
+                mouse_x,
+                mouse_y,
+                mouse_in_canvas,
+                mouse_radius,
+                mouse_strength,
+                mouse_mode,
+            } => {
+                self.tick(
+                    delta_time,
+                    speed,
+                    mouse_x,
+                    mouse_y,
+                    mouse_in_canvas,
+                    mouse_radius,
+                    mouse_strength,
+                    mouse_mode,
+                );
+            }
+            Command::MouseState {
+                x,
+                y,
+                in_canvas,
+                radius,
+                strength,
+                mode,
+            } => self.set_mouse_state(x, y, in_canvas, radius, strength, mode),
+            Command::Pointer {
+                id,
+                x,
+                y,
+                active,
+                radius,
+                strength,
+                mode,
+            } => self.set_pointer(id, x, y, active, radius, strength, mode),
+            Command::RemovePointer { id } => self.remove_pointer(id),
+            Command::Shockwave { x, y, strength } => {
+                self.trigger_shockwave(x, y, strength);
+            }
+            Command::GravityWell {
+                x,
+                y,
+                active,
+                attract,
+            } => {
+                self.set_gravity_well(x, y, active, attract);
+            }
+            Command::GravityWellPosition { x, y } => self.update_gravity_well_position(x, y),
+            Command::Resize { width, height } => self.resize(width, height),
+            Command::DevicePixelRatio { dpr } => self.set_device_pixel_ratio(dpr),
+            Command::ScrollOffset { x, y } => self.set_scroll_offset(x, y),
+            Command::Tilt { gx, gy } => self.set_tilt(gx, gy),
+            Command::AudioLevels { bass, mid, treble } => self.set_audio_levels(bass, mid, treble),
+        }
+    }
+}
+
+/// Native access for the headless CLI (no JS typed arrays)
+#[cfg(feature = "cli")]
+impl Simulation {
+    /// Import a preset parsed from a JSON file (same format as `export_preset`)
+    pub fn import_preset_json(&mut self, preset: &serde_json::Value) -> Result<(), String> {
+        let preset = Preset::from_json(preset, &self.current_preset(""))?;
+        self.record_params(&preset);
+        Ok(())
+    }
+
+    /// Canvas size in pixels
+    pub fn size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    /// Triangle vertices from the last `triangulate` (see `get_triangle_stride`)
+    pub fn triangle_vertex_data(&self) -> &[f32] {
+        &self.buffers.triangle_vertices
+    }
+}
+
+/// `v` scaled to unit length (callers ensure it is non-zero)
+fn normalized([x, y, z]: [f32; 3]) -> [f32; 3] {
+    let length = (x * x + y * y + z * z).sqrt();
+    [x / length, y / length, z / length]
+}
+
+/// Copy as much of `src` as fits into `dst`, returning the count copied
+fn copy_into<T: Copy>(src: &[T], dst: &mut [T]) -> usize {
+    let n = src.len().min(dst.len());
+    dst[..n].copy_from_slice(&src[..n]);
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Update one 60 Hz frame at a time, with a fixed timestep of `hz`, until `done`;
+    /// returns the frames taken
+    fn frames_until(sim: &mut Simulation, hz: f32, done: impl Fn(&Simulation) -> bool) -> u32 {
+        sim.set_fixed_timestep(hz);
+        let mut frames = 0;
+        while !done(sim) {
+            sim.update_points(1.0, 0.0);
+            frames += 1;
+            assert!(frames < 2000, "not done at {hz} Hz");
+        }
+        frames
+    }
+
+    fn shockwave_lifetime(hz: f32) -> u32 {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        sim.trigger_shockwave(400.0, 300.0, 100.0);
+        frames_until(&mut sim, hz, |sim| sim.shockwaves.is_empty())
+    }
+
+    fn spring_settle_time(hz: f32) -> u32 {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        for point in &mut sim.points {
+            point.dx = 40.0;
+            point.x = point.base_x + point.dx;
+        }
+        frames_until(&mut sim, hz, |sim| {
+            sim.points.iter().all(|p| (p.x - p.base_x).abs() < 0.5)
+        })
+    }
+
+    #[test]
+    fn fixed_timestep_rate_keeps_effect_timing() {
+        let (wave_60, settle_60) = (shockwave_lifetime(60.0), spring_settle_time(60.0));
+        for hz in [120.0, 240.0] {
+            let (wave, settle) = (shockwave_lifetime(hz), spring_settle_time(hz));
+            assert!(wave.abs_diff(wave_60) <= 1, "wave {wave} vs {wave_60} frames at {hz} Hz");
+            let tolerance = settle_60 / 10 + 1;
+            assert!(settle.abs_diff(settle_60) <= tolerance, "settle {settle} vs {settle_60}");
+        }
+    }
+
+    fn positions(sim: &Simulation) -> Vec<(f32, f32)> {
+        sim.points.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    #[test]
+    fn seek_replays_parameter_changes() {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        sim.start_recording(1000);
+        for frame in 0..30 {
+            match frame {
+                5 => sim.set_global_force(0.0, 3.0),
+                10 => sim.set_point_count(150, 3),
+                15 => sim.set_turbulence(4.0, 0.01, 1.0),
+                20 => sim.set_physics_params(0.01, 0.9, 0.0),
+                _ => {}
+            }
+            sim.update_points(1.0, 1.0);
+        }
+        let live = positions(&sim);
+
+        sim.seek(0);
+        assert_eq!(sim.points.len(), 200);
+        assert_eq!(sim.seek(u32::MAX), 30);
+        assert_eq!(positions(&sim), live);
+    }
+
+    fn tick(sim: &mut Simulation) -> usize {
+        sim.tick(1.0, 0.0, 0.0, 0.0, false, 100.0, 1.0, 0)
+    }
+
+    fn settled() -> Simulation {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        sim.set_static_after_settle(true, 0.05);
+        for _ in 0..120 {
+            tick(&mut sim);
+        }
+        assert!(sim.is_settled());
+        sim
+    }
+
+    #[test]
+    fn setters_wake_a_settled_simulation() {
+        let mut sim = settled();
+        let triangles = tick(&mut sim);
+        sim.set_point_count(300, 7);
+        assert!(!sim.is_settled());
+        assert_ne!(tick(&mut sim), triangles);
+        assert!(sim.buffers_changed());
+
+        let mut sim = settled();
+        let before = positions(&sim);
+        sim.set_global_force(0.0, 3.0);
+        for _ in 0..2 {
+            tick(&mut sim);
+        }
+        assert!(sim.buffers_changed());
+        assert_ne!(positions(&sim), before);
+    }
+
+    #[test]
+    fn setters_invalidate_quiescence() {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        sim.set_quiescence_epsilon(0.5);
+        let quiet = |sim: &mut Simulation| {
+            for _ in 0..120 {
+                tick(sim);
+            }
+            assert!(!sim.buffers_changed());
+        };
+        quiet(&mut sim);
+        sim.set_height_blur(2);
+        tick(&mut sim);
+        assert!(sim.buffers_changed());
+
+        quiet(&mut sim);
+        sim.set_density_equalization(true, 0.5);
+        tick(&mut sim);
+        assert!(sim.buffers_changed());
+    }
+
+    #[test]
+    fn nearest_point_sees_points_moved_since_the_update() {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        sim.update_points(1.0, 1.0);
+        (sim.points[0].x, sim.points[0].y) = (1.0, 1.0);
+        assert_eq!(sim.find_nearest_point(1.0, 1.0), 0);
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn json_presets_read_integers_like_js() {
+        let mut sim = Simulation::new(800.0, 600.0, 200, 7);
+        let preset = serde_json::json!({ "version": 1, "boundary": { "mode": 1.0 } });
+        assert!(sim.import_preset_json(&preset).is_ok());
+        assert!(sim.boundary.mode == BoundaryMode::WindTunnel);
+
+        let preset = serde_json::json!({ "version": 1, "depth": { "mode": 1.5 } });
+        assert!(sim.import_preset_json(&preset).is_err());
     }
 }
@@ -1,6 +1,7 @@
-/// Uniform grid for spatial partitioning
-/// Enables O(k) spatial queries instead of O(n)
+//! Uniform grid for spatial partitioning
+//! Enables O(k) spatial queries instead of O(n)
 
+#[derive(Clone)]
 pub struct SpatialGrid {
     /// Cell index -> list of point indices
     cells: Vec<Vec<usize>>,
@@ -61,6 +62,49 @@ impl SpatialGrid {
         row * self.cols + col
     }
 
+    /// Get (column, row) of the cell containing a position
+    #[inline]
+    pub fn cell_coords(&self, x: f32, y: f32) -> (usize, usize) {
+        let col = ((x.max(0.0) / self.cell_size) as usize).min(self.cols - 1);
+        let row = ((y.max(0.0) / self.cell_size) as usize).min(self.rows - 1);
+        (col, row)
+    }
+
+    /// Number of points in a cell
+    #[inline]
+    pub fn cell_len(&self, col: usize, row: usize) -> usize {
+        self.cells[row * self.cols + col].len()
+    }
+
+    /// Number of points in a cell, with column/row wrapping around the grid edges
+    #[inline]
+    pub fn cell_len_wrapped(&self, col: isize, row: isize) -> usize {
+        let col = col.rem_euclid(self.cols as isize) as usize;
+        let row = row.rem_euclid(self.rows as isize) as usize;
+        self.cells[row * self.cols + col].len()
+    }
+
+    /// Point count in the 3x3 cell neighborhood around a position, relative to the average
+    /// 1.0 means average density, 2.0 twice as crowded
+    pub fn neighborhood_density(&self, x: f32, y: f32, average_per_cell: f32) -> f32 {
+        if average_per_cell <= 0.0 {
+            return 1.0;
+        }
+        let (col, row) = self.cell_coords(x, y);
+        let mut count = 0;
+        for dr in -1isize..=1 {
+            for dc in -1isize..=1 {
+                count += self.cell_len_wrapped(col as isize + dc, row as isize + dr);
+            }
+        }
+        count as f32 / (9.0 * average_per_cell)
+    }
+
+    /// Total number of cells
+    pub fn cell_count(&self) -> usize {
+        self.cols * self.rows
+    }
+
     /// Insert a point into the grid
     pub fn insert(&mut self, point_index: usize, x: f32, y: f32) {
         let idx = self.cell_index(x, y);
@@ -81,4 +125,37 @@ impl SpatialGrid {
             })
         })
     }
+
+    /// Index of the point nearest to (x, y), searching rings of cells outward until no
+    /// closer point can remain; `position` maps an index to its current position
+    pub fn nearest(&self, x: f32, y: f32, position: impl Fn(usize) -> (f32, f32)) -> Option<usize> {
+        let (col, row) = self.cell_coords(x, y);
+        let mut best: Option<(usize, f32)> = None;
+        for ring in 0..self.cols.max(self.rows) {
+            // Points in this ring are at least (ring - 1) cells away
+            let reach = ring.saturating_sub(1) as f32 * self.cell_size;
+            if best.is_some_and(|(_, dist_sq)| dist_sq <= reach * reach) {
+                break;
+            }
+            let (ring, col, row) = (ring as isize, col as isize, row as isize);
+            for r in row - ring..=row + ring {
+                for c in col - ring..=col + ring {
+                    let on_ring = (r - row).abs() == ring || (c - col).abs() == ring;
+                    let inside = (0..self.rows as isize).contains(&r)
+                        && (0..self.cols as isize).contains(&c);
+                    if !on_ring || !inside {
+                        continue;
+                    }
+                    for &i in &self.cells[r as usize * self.cols + c as usize] {
+                        let (px, py) = position(i);
+                        let dist_sq = (px - x).powi(2) + (py - y).powi(2);
+                        if best.is_none_or(|(_, best_sq)| dist_sq < best_sq) {
+                            best = Some((i, dist_sq));
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(i, _)| i)
+    }
 }
@@ -0,0 +1,83 @@
+//! Sticky cursor: points under the mouse attach to it and are dragged along until
+//! their spring tension exceeds the break force, then snap back home
+
+use crate::constants::STICKY_CAPTURE_FRACTION;
+use crate::effects::MouseState;
+use crate::point::Point;
+
+/// Per-point attachment to the cursor
+#[derive(Clone, Copy)]
+pub struct Attachment {
+    /// Base position relative to the cursor while attached
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Base position to return to on release
+    pub home_x: f32,
+    pub home_y: f32,
+}
+
+impl Attachment {
+    pub fn scale(&mut self, scale_x: f32, scale_y: f32) {
+        self.offset_x *= scale_x;
+        self.offset_y *= scale_y;
+        self.home_x *= scale_x;
+        self.home_y *= scale_y;
+    }
+}
+
+/// Sticky cursor settings
+#[derive(Clone, Copy)]
+pub struct StickyCursor {
+    /// Tension (spring back × stretch in CSS pixels) at which a point lets go
+    pub break_force: f32,
+}
+
+impl StickyCursor {
+    /// Attach points whose rest position is under the cursor, drag attached ones and
+    /// release those stretched too far (or all of them once the mouse leaves)
+    pub fn update(
+        &self,
+        points: &mut [Point],
+        mouse: &MouseState,
+        spring_back: f32,
+        pixel_ratio: f32,
+    ) {
+        let capture = mouse.radius * STICKY_CAPTURE_FRACTION * pixel_ratio;
+        let capture_sq = capture * capture;
+
+        for point in points.iter_mut() {
+            if point.pin.is_some() {
+                point.stuck = None;
+                continue;
+            }
+
+            match point.stuck {
+                Some(a) if mouse.in_canvas => {
+                    let x = mouse.x + a.offset_x;
+                    let y = mouse.y + a.offset_y;
+                    let (sx, sy) = (x - a.home_x, y - a.home_y);
+                    let stretch = (sx * sx + sy * sy).sqrt() / pixel_ratio;
+                    if stretch * spring_back > self.break_force {
+                        point.release();
+                    } else {
+                        point.base_x = x;
+                        point.base_y = y;
+                    }
+                }
+                Some(_) => point.release(),
+                None if mouse.in_canvas => {
+                    let (ox, oy) = (point.base_x - mouse.x, point.base_y - mouse.y);
+                    if ox * ox + oy * oy < capture_sq {
+                        point.stuck = Some(Attachment {
+                            offset_x: ox,
+                            offset_y: oy,
+                            home_x: point.base_x,
+                            home_y: point.base_y,
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+}
@@ -1,63 +1,502 @@
-/// Delaunay triangulation and vertex buffer generation
+//! Delaunay triangulation and vertex buffer generation
 
-use delaunator::{triangulate as delaunay_triangulate, Point as DelaunayPoint};
+use delaunator::{
+    next_halfedge, triangulate as delaunay_triangulate, Point as DelaunayPoint, Triangulation,
+    EMPTY,
+};
 
-use crate::constants::GHOST_THRESHOLD;
+use crate::boundary::Boundary;
+use crate::constellation::{build_constellation, CONSTELLATION_EDGE_STRIDE};
+use crate::constants::{
+    EDGE_BUNDLE_CURVATURE, EDGE_BUNDLE_SEGMENTS, FACE_NORMAL_RELIEF, GHOST_MARGIN_SPACINGS,
+    NEUTRAL_DEPTH, SHADOW_STRENGTH,
+};
+use crate::depth::depth_factor;
+use crate::exclusion::ExclusionRect;
+use crate::flip::FlipManager;
+use crate::incremental::IncrementalMesh;
+use crate::obstacle::Obstacle;
+use crate::parallel::for_each_chunk;
 use crate::point::Point;
+use crate::rng::Rng;
+use crate::spatial_grid::SpatialGrid;
+use crate::voronoi::{build_voronoi, VoronoiBuffers};
 
-/// Generate ghost points for edge wrapping continuity
-pub fn generate_ghost_points(points: &[Point], width: f32, height: f32) -> Vec<(f32, f32, f32)> {
-    let threshold_x = width * GHOST_THRESHOLD;
-    let threshold_y = height * GHOST_THRESHOLD;
+/// Triangulation input vertex: a real point, a ghost copy of one, or a corner
+#[derive(Clone, Copy)]
+pub struct Vertex {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Index of the real point this vertex represents (None for corners)
+    pub source: Option<usize>,
+}
+
+impl Vertex {
+    /// Real point at its seam-wrapped position
+    fn from_point(
+        index: usize,
+        point: &Point,
+        width: f32,
+        height: f32,
+        boundary: &Boundary,
+    ) -> Self {
+        let (x, y) = boundary.seam_position(point.x, point.y, width, height);
+        Self {
+            x,
+            y,
+            z: point.z,
+            source: Some(index),
+        }
+    }
+
+    /// Copy of a real vertex shifted across the seam
+    fn ghost(&self, offset_x: f32, offset_y: f32) -> Self {
+        Self {
+            x: self.x + offset_x,
+            y: self.y + offset_y,
+            ..*self
+        }
+    }
 
-    let mut ghosts = Vec::new();
+    fn corner(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            z: 0.0,
+            source: None,
+        }
+    }
+
+    /// Depth of the source point (neutral for corners)
+    #[inline]
+    fn depth(&self, points: &[Point]) -> f32 {
+        self.source.map_or(NEUTRAL_DEPTH, |i| points[i].depth)
+    }
+}
+
+/// Generate ghost points for edge wrapping continuity, appending them to `ghosts`
+/// Points within `margin` (canvas pixels) of a wrapping edge are copied across it;
+/// open (non-wrapping) boundary axes get no ghosts
+pub fn generate_ghost_points(
+    points: &[Point],
+    width: f32,
+    height: f32,
+    boundary: &Boundary,
+    margin: f32,
+    ghosts: &mut Vec<Vertex>,
+) {
+    let threshold_x = margin.min(width);
+    let threshold_y = margin.min(height);
+    let wrap_x = boundary.wraps_x();
+    let wrap_y = boundary.wraps_y();
 
-    for point in points {
-        let near_left = point.x < threshold_x;
-        let near_right = point.x > width - threshold_x;
-        let near_top = point.y < threshold_y;
-        let near_bottom = point.y > height - threshold_y;
+    for (i, point) in points.iter().enumerate() {
+        let v = Vertex::from_point(i, point, width, height, boundary);
+        let near_left = wrap_x && v.x < threshold_x;
+        let near_right = wrap_x && v.x > width - threshold_x;
+        let near_top = wrap_y && v.y < threshold_y;
+        let near_bottom = wrap_y && v.y > height - threshold_y;
 
         // Edge ghosts
         if near_left {
-            ghosts.push((point.x + width, point.y, point.z));
+            ghosts.push(v.ghost(width, 0.0));
         }
         if near_right {
-            ghosts.push((point.x - width, point.y, point.z));
+            ghosts.push(v.ghost(-width, 0.0));
         }
         if near_top {
-            ghosts.push((point.x, point.y + height, point.z));
+            ghosts.push(v.ghost(0.0, height));
         }
         if near_bottom {
-            ghosts.push((point.x, point.y - height, point.z));
+            ghosts.push(v.ghost(0.0, -height));
         }
 
         // Corner ghosts
         if near_left && near_top {
-            ghosts.push((point.x + width, point.y + height, point.z));
+            ghosts.push(v.ghost(width, height));
         }
         if near_left && near_bottom {
-            ghosts.push((point.x + width, point.y - height, point.z));
+            ghosts.push(v.ghost(width, -height));
         }
         if near_right && near_top {
-            ghosts.push((point.x - width, point.y + height, point.z));
+            ghosts.push(v.ghost(-width, height));
         }
         if near_right && near_bottom {
-            ghosts.push((point.x - width, point.y - height, point.z));
+            ghosts.push(v.ghost(-width, -height));
+        }
+    }
+}
+
+/// Optional per-vertex attributes appended after the base layouts
+/// Bit flags, combined with `|` and set via `Simulation::set_vertex_layout`
+pub mod vertex_attr {
+    /// Per-vertex point depth (triangles) / size scale + depth (points)
+    pub const DEPTH: u32 = 1 << 0;
+    /// Per-vertex ambient occlusion from local point density (triangles only)
+    pub const OCCLUSION: u32 = 1 << 1;
+    /// Per-triangle tile flip [phase, axisX, axisY] (triangles only)
+    pub const FLIP: u32 = 1 << 2;
+    /// Signed distance (pixels) of the vertex from its triangle centroid along
+    /// the light direction, positive toward the light (triangles only)
+    pub const LIGHT_GRADIENT: u32 = 1 << 3;
+    /// Per-vertex heat 0..1 from the heat field (triangles and indexed vertices)
+    pub const HEAT: u32 = 1 << 4;
+    /// Per stroke vertex: larger endpoint movement (CSS pixels) since the last build,
+    /// shared by both vertices of the edge (strokes only)
+    pub const STROKE_MOTION: u32 = 1 << 5;
+    /// Per-triangle 1 if the triangle's bounding box overlaps a flagging exclusion
+    /// rectangle, else 0 (triangles only)
+    pub const EXCLUSION: u32 = 1 << 6;
+    /// Per-triangle light attenuation 0..1 from higher neighbors lying toward the light,
+    /// 1 = unshadowed (triangles only)
+    pub const SHADOW: u32 = 1 << 7;
+    /// Per stroke vertex signed jitter offset (CSS pixels) from the audio treble level,
+    /// re-rolled every frame (strokes only)
+    pub const AUDIO_JITTER: u32 = 1 << 8;
+    /// Per-triangle depth-weighted centroid [x, y] (output space) and the mean weight;
+    /// the base centroid stays the arithmetic mean (triangles only)
+    pub const WEIGHTED_CENTROID: u32 = 1 << 9;
+    /// Per-triangle pseudo-random value in [0, 1) hashed from the triangle's source point
+    /// indices, stable while the facet keeps its points (triangles only)
+    pub const FACET_SEED: u32 = 1 << 10;
+    /// Per-triangle unit face normal [nx, ny, nz] from the vertex heights, canvas axes
+    /// (x right, y down, z toward the viewer) (triangles only)
+    pub const FACE_NORMAL: u32 = 1 << 11;
+    /// Per-triangle Lambert intensity in [ambient, 1] from the face normal and the 3D
+    /// light (see `Simulation::set_light`) (triangles only)
+    pub const DIFFUSE: u32 = 1 << 12;
+}
+
+/// Coordinate space of emitted positions
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum OutputSpace {
+    /// Canvas pixels, origin top-left
+    #[default]
+    Pixels,
+    /// [0, 1] across the canvas, origin top-left
+    Normalized,
+    /// WebGL clip space [-1, 1], Y up
+    Clip,
+}
+
+impl OutputSpace {
+    /// Convert from numeric value (for JS interop)
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => OutputSpace::Normalized,
+            2 => OutputSpace::Clip,
+            _ => OutputSpace::Pixels,
+        }
+    }
+}
+
+/// Which outputs `tick` builds; the cheaper modes skip Delaunay entirely
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum RenderMode {
+    /// Point buffer only
+    PointsOnly,
+    /// Points plus distance-based constellation lines
+    Lines,
+    /// Triangulated mesh (triangle, stroke and point buffers)
+    #[default]
+    Mesh,
+    /// Mesh plus constellation lines
+    MeshAndLines,
+}
+
+impl RenderMode {
+    /// Convert from numeric value (for JS interop)
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => RenderMode::PointsOnly,
+            1 => RenderMode::Lines,
+            3 => RenderMode::MeshAndLines,
+            _ => RenderMode::Mesh,
         }
     }
 
-    ghosts
+    /// Whether the points are triangulated
+    pub fn meshes(self) -> bool {
+        matches!(self, RenderMode::Mesh | RenderMode::MeshAndLines)
+    }
+
+    /// Whether constellation lines are built
+    pub fn lines(self) -> bool {
+        matches!(self, RenderMode::Lines | RenderMode::MeshAndLines)
+    }
+}
+
+/// How stroke edges are emitted
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum EdgeMode {
+    /// One straight segment per edge
+    #[default]
+    Straight,
+    /// Polyline bowed toward the local drift direction for a softer, hand-drawn look
+    Bundled,
+}
+
+impl EdgeMode {
+    /// Convert from numeric value (for JS interop)
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => EdgeMode::Bundled,
+            _ => EdgeMode::Straight,
+        }
+    }
+
+    /// Line segments emitted per edge
+    pub fn segments(self) -> usize {
+        match self {
+            EdgeMode::Straight => 1,
+            EdgeMode::Bundled => EDGE_BUNDLE_SEGMENTS,
+        }
+    }
+}
+
+/// Affine viewport transform applied to emitted positions: p' = p * scale + offset
+#[derive(Clone, Copy)]
+pub struct OutputTransform {
+    scale_x: f32,
+    scale_y: f32,
+    offset_x: f32,
+    offset_y: f32,
+}
+
+impl OutputTransform {
+    pub fn new(space: OutputSpace, width: f32, height: f32) -> Self {
+        match space {
+            OutputSpace::Pixels => Self {
+                scale_x: 1.0,
+                scale_y: 1.0,
+                offset_x: 0.0,
+                offset_y: 0.0,
+            },
+            OutputSpace::Normalized => Self {
+                scale_x: 1.0 / width,
+                scale_y: 1.0 / height,
+                offset_x: 0.0,
+                offset_y: 0.0,
+            },
+            OutputSpace::Clip => Self {
+                scale_x: 2.0 / width,
+                scale_y: -2.0 / height,
+                offset_x: -1.0,
+                offset_y: 1.0,
+            },
+        }
+    }
+
+    #[inline]
+    pub fn x(&self, x: f32) -> f32 {
+        x * self.scale_x + self.offset_x
+    }
+
+    #[inline]
+    pub fn y(&self, y: f32) -> f32 {
+        y * self.scale_y + self.offset_y
+    }
+
+    /// `x` evaluated in double precision
+    #[inline]
+    pub fn x64(&self, x: f64) -> f64 {
+        x * self.scale_x as f64 + self.offset_x as f64
+    }
+
+    /// `y` evaluated in double precision
+    #[inline]
+    pub fn y64(&self, y: f64) -> f64 {
+        y * self.scale_y as f64 + self.offset_y as f64
+    }
 }
 
+/// Active vertex layout (base attributes plus enabled optional ones)
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct VertexLayout {
+    pub flags: u32,
+    /// Coordinate space for positions and centroids
+    pub space: OutputSpace,
+    /// Emit a shared vertex pool + index buffer instead of expanded triangles
+    pub indexed: bool,
+    /// Also build Voronoi cell and edge buffers
+    pub voronoi: bool,
+    /// Also build a Float64 copy of the base triangle vertices
+    pub f64_output: bool,
+    /// Straight or bundled stroke edges
+    pub edge_mode: EdgeMode,
+    /// Leave the outer hull edges out of the stroke buffer
+    pub skip_hull_edges: bool,
+}
+
+impl VertexLayout {
+    /// Base triangle vertex: [x, y, z, centroidY, centroidX, centroidY]
+    const TRIANGLE_BASE: usize = 6;
+    /// Base stroke vertex: [x, y]
+    const STROKE_BASE: usize = 2;
+    /// Base point vertex: [x, y]
+    const POINT_BASE: usize = 2;
+    /// Base shared vertex (indexed mode): [x, y, z]
+    const INDEXED_BASE: usize = 3;
+
+    #[inline]
+    pub fn has(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Floats per triangle vertex
+    pub fn triangle_stride(&self) -> usize {
+        let mut stride = Self::TRIANGLE_BASE;
+        if self.has(vertex_attr::DEPTH) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::OCCLUSION) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::FLIP) {
+            stride += 3;
+        }
+        if self.has(vertex_attr::LIGHT_GRADIENT) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::HEAT) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::EXCLUSION) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::SHADOW) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::WEIGHTED_CENTROID) {
+            stride += 3;
+        }
+        if self.has(vertex_attr::FACET_SEED) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::FACE_NORMAL) {
+            stride += 3;
+        }
+        if self.has(vertex_attr::DIFFUSE) {
+            stride += 1;
+        }
+        stride
+    }
+
+    /// Floats per shared vertex in indexed mode
+    /// Only per-vertex attributes apply; per-triangle ones (FLIP, LIGHT_GRADIENT, EXCLUSION,
+    /// SHADOW, WEIGHTED_CENTROID, FACET_SEED, FACE_NORMAL, DIFFUSE) are skipped
+    pub fn indexed_stride(&self) -> usize {
+        let mut stride = Self::INDEXED_BASE;
+        if self.has(vertex_attr::DEPTH) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::OCCLUSION) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::HEAT) {
+            stride += 1;
+        }
+        stride
+    }
+
+    /// Floats per stroke vertex
+    pub fn stroke_stride(&self) -> usize {
+        let mut stride = Self::STROKE_BASE;
+        if self.has(vertex_attr::STROKE_MOTION) {
+            stride += 1;
+        }
+        if self.has(vertex_attr::AUDIO_JITTER) {
+            stride += 1;
+        }
+        stride
+    }
+
+    /// Floats per point vertex
+    pub fn point_stride(&self) -> usize {
+        let mut stride = Self::POINT_BASE;
+        if self.has(vertex_attr::DEPTH) {
+            stride += 2;
+        }
+        stride
+    }
+}
+
+/// Floats per triangle in the metadata buffer: [centroid x, centroid y, area, avg height]
+const TRIANGLE_METADATA_STRIDE: usize = 4;
+
+/// Floats before the data in the combined buffer: [offset, length, stride] for the
+/// triangle, stroke, point, indexed vertex, triangle index and stroke index sections
+const COMBINED_HEADER_FLOATS: usize = 18;
+
 /// Output buffers for triangulation results
+#[derive(Clone)]
 pub struct TriangulationBuffers {
-    /// Triangle vertices: [x, y, z, centroidY, centroidX, centroidY] per vertex
+    /// Triangle vertices: [x, y, z, centroidY, centroidX, centroidY, ...optional] per vertex
     pub triangle_vertices: Vec<f32>,
-    /// Stroke vertices: [x1, y1, x2, y2] per edge
+    /// Stroke vertices: [x1, y1, x2, y2] per segment, optional attributes after each [x, y]
+    /// (one segment per edge, or a polyline of several in bundled edge mode)
     pub stroke_vertices: Vec<f32>,
-    /// Point vertices: [x, y] per point
+    /// Point vertices: [x, y, ...optional] per point
     pub point_vertices: Vec<f32>,
+    /// Float64 triangle vertices: [x, y, z, centroidY, centroidX, centroidY] per vertex,
+    /// base layout only (when `layout.f64_output` is set); positions widened from f32
+    pub triangle_vertices_f64: Vec<f64>,
+    /// Shared vertices (indexed mode): [x, y, z, ...optional] per triangulation vertex
+    pub indexed_vertices: Vec<f32>,
+    /// Triangle indices into `indexed_vertices` (indexed mode)
+    pub indices: Vec<u32>,
+    /// Line-list stroke indices into `indexed_vertices`, two per edge (indexed mode with
+    /// straight edges; `stroke_vertices` is then empty)
+    pub stroke_indices: Vec<u32>,
+    /// Voronoi cells and edges (when enabled)
+    pub voronoi: VoronoiBuffers,
+    /// Constellation edges: [x1, y1, x2, y2, alpha] per edge (constellation mode)
+    pub constellation_vertices: Vec<f32>,
+    /// Header plus every vertex and index section back to back (see `build_combined`)
+    pub combined: Vec<f32>,
+    /// The buffers were rebuilt since `combined` was last filled
+    combined_stale: bool,
+    /// Per drawn triangle: [centroid x, centroid y (output space), area (canvas px²),
+    /// avg height], in triangle buffer order (when `collect_metadata` is set)
+    pub triangle_metadata: Vec<f32>,
+    pub collect_metadata: bool,
+    /// Triangulation input, reused across frames to avoid per-frame allocation
+    scratch_vertices: Vec<Vertex>,
+    scratch_delaunay: Vec<DelaunayPoint>,
+    scratch_triangles: Vec<usize>,
+    scratch_kept: Vec<bool>,
+    scratch_blur: Vec<(f32, f32)>,
+    scratch_shadows: Vec<f32>,
+    scratch_edges: Vec<(usize, usize, f32)>,
+    /// Full triangulation from the last build, indices into `scratch_vertices`; kept so
+    /// incremental mode repairs it in place
+    last_mesh: Triangulation,
+    /// Neighbor-averaging passes over heights before emission (0 = off)
+    pub height_blur: u32,
+    /// Cosine of the smallest angle a triangle touching a ghost or corner may have
+    /// before it is dropped as a sliver (None = keep all)
+    pub sliver_cos: Option<f32>,
+    /// Unique Delaunay edges between real points with their spring rest length (when
+    /// `collect_edges` is set); NaN until the physics pass measures a new edge
+    pub edges: Vec<(usize, usize, f32)>,
+    pub collect_edges: bool,
+    /// Previous mesh repaired instead of re-triangulated (None = full pass every build)
+    pub incremental: Option<IncrementalMesh>,
+    /// Margin (radians) past co-circular before a repair flips an edge (0 = exact)
+    pub hysteresis: f64,
+    /// Connect points by their rest positions while drawing them displaced
+    pub base_topology: bool,
+    /// Ghost margin from wrapping edges (CSS pixels, 0 = adaptive to point spacing)
+    pub ghost_margin: f32,
+    /// Skip optional work (Voronoi, bundled edges, height blur, shadows) for cheap frames;
+    /// strides are unchanged, shadow attributes read as unshadowed
+    pub lean: bool,
+    /// Layout used for triangle and point vertices
+    pub layout: VertexLayout,
+    /// Layout the current buffers were built with; counts and combined strides follow it
+    /// until the next build picks up a changed `layout`
+    built_layout: VertexLayout,
 }
 
 impl TriangulationBuffers {
@@ -66,175 +505,998 @@ impl TriangulationBuffers {
             triangle_vertices: Vec::new(),
             stroke_vertices: Vec::new(),
             point_vertices: Vec::new(),
+            triangle_vertices_f64: Vec::new(),
+            indexed_vertices: Vec::new(),
+            indices: Vec::new(),
+            stroke_indices: Vec::new(),
+            voronoi: VoronoiBuffers::new(),
+            constellation_vertices: Vec::new(),
+            combined: Vec::new(),
+            combined_stale: true,
+            triangle_metadata: Vec::new(),
+            collect_metadata: false,
+            scratch_vertices: Vec::new(),
+            scratch_delaunay: Vec::new(),
+            scratch_triangles: Vec::new(),
+            scratch_kept: Vec::new(),
+            scratch_blur: Vec::new(),
+            scratch_shadows: Vec::new(),
+            scratch_edges: Vec::new(),
+            last_mesh: empty_mesh(),
+            height_blur: 0,
+            sliver_cos: None,
+            edges: Vec::new(),
+            collect_edges: false,
+            incremental: None,
+            hysteresis: 0.0,
+            base_topology: false,
+            ghost_margin: 0.0,
+            lean: false,
+            layout: VertexLayout::default(),
+            built_layout: VertexLayout::default(),
+        }
+    }
+
+    /// Copy of the settings and the topology later builds depend on (spring edges,
+    /// incremental mesh), with empty output and scratch buffers
+    pub fn without_output(&self) -> Self {
+        Self {
+            collect_metadata: self.collect_metadata,
+            last_mesh: self.last_mesh.clone(),
+            height_blur: self.height_blur,
+            sliver_cos: self.sliver_cos,
+            edges: self.edges.clone(),
+            collect_edges: self.collect_edges,
+            incremental: self.incremental.clone(),
+            hysteresis: self.hysteresis,
+            base_topology: self.base_topology,
+            ghost_margin: self.ghost_margin,
+            lean: self.lean,
+            layout: self.layout,
+            ..Self::new()
         }
     }
 
     /// Get number of triangles
     pub fn triangle_count(&self) -> usize {
-        self.triangle_vertices.len() / 18
+        if self.built_layout.indexed {
+            self.indices.len() / 3
+        } else {
+            self.triangle_vertices.len() / (3 * self.built_layout.triangle_stride())
+        }
+    }
+
+    /// Get number of constellation edges
+    pub fn constellation_edge_count(&self) -> usize {
+        self.constellation_vertices.len() / CONSTELLATION_EDGE_STRIDE
     }
 
     /// Get number of stroke line segments (vertices / 2)
     pub fn stroke_vertex_count(&self) -> usize {
-        self.stroke_vertices.len() / 2
+        self.stroke_vertices.len() / self.built_layout.stroke_stride()
+    }
+
+    /// Fill `combined` if the buffers changed since it was last filled: a
+    /// COMBINED_HEADER_FLOATS header of [offset, length, stride] per section (in floats
+    /// from the start of the buffer), then the triangle, stroke, point and indexed vertices
+    /// and the triangle and stroke indices; indices are stored as their u32 bit patterns,
+    /// to be read through a Uint32Array over the same memory
+    pub fn build_combined(&mut self) {
+        if !self.combined_stale {
+            return;
+        }
+        self.combined_stale = false;
+        let sections = [
+            (self.triangle_vertices.len(), self.built_layout.triangle_stride()),
+            (self.stroke_vertices.len(), self.built_layout.stroke_stride()),
+            (self.point_vertices.len(), self.built_layout.point_stride()),
+            (self.indexed_vertices.len(), self.built_layout.indexed_stride()),
+            (self.indices.len(), 3),
+            (self.stroke_indices.len(), 2),
+        ];
+        self.combined.clear();
+        let mut offset = COMBINED_HEADER_FLOATS;
+        for (len, stride) in sections {
+            self.combined.extend([offset as f32, len as f32, stride as f32]);
+            offset += len;
+        }
+        let vertices = [
+            &self.triangle_vertices,
+            &self.stroke_vertices,
+            &self.point_vertices,
+            &self.indexed_vertices,
+        ];
+        for data in vertices {
+            self.combined.extend_from_slice(data);
+        }
+        for data in [&self.indices, &self.stroke_indices] {
+            self.combined.extend(data.iter().map(|&index| f32::from_bits(index)));
+        }
+    }
+
+    /// Vertices (canvas pixels) and full triangle indices of the last triangulation
+    /// Empty after a constellation build
+    pub fn mesh(&self) -> (&[Vertex], &[usize]) {
+        (&self.scratch_vertices, &self.last_mesh.triangles)
+    }
+}
+
+/// Triangulation with no triangles (delaunator has no public empty constructor)
+fn empty_mesh() -> Triangulation {
+    Triangulation {
+        triangles: Vec::new(),
+        halfedges: Vec::new(),
+        hull: Vec::new(),
     }
 }
 
+/// Per-point attribute settings needed while building buffers
+#[derive(Clone, Copy)]
+pub struct AttributeParams<'a> {
+    /// Depth parallax amount used for point size scaling
+    pub depth_parallax: f32,
+    /// Per-point occlusion (empty unless the OCCLUSION attribute is enabled)
+    pub occlusion: &'a [f32],
+    /// Active tile flips, sampled at triangle centroids
+    pub flips: &'a FlipManager,
+    /// Simulation time in seconds (for flip phases)
+    pub time: f32,
+    /// Unit vector toward the light in canvas pixels (Y down)
+    pub light_dir: (f32, f32),
+    /// Per-point heat (empty unless the HEAT attribute is enabled)
+    pub heat: &'a [f32],
+    /// Per-point movement since the last build (empty unless STROKE_MOTION is enabled)
+    pub motion: &'a [f32],
+    /// Obstacles (canvas pixels) whose covered triangles are dropped (empty = no culling)
+    pub culled: &'a [Obstacle],
+    /// Exclusion rectangles (canvas pixels) flagging overlapping triangles
+    pub exclusions: &'a [ExclusionRect],
+    /// Multiplier on emitted heights (environment relief)
+    pub height_scale: f32,
+    /// Height intensity, the scale shadows compare height differences against
+    pub height_intensity: f32,
+    /// Canvas pixels per CSS pixel (face normal relief)
+    pub pixel_ratio: f32,
+    /// Unit direction toward the 3D light and the ambient floor (DIFFUSE)
+    pub light: ([f32; 3], f32),
+    /// Stroke jitter amplitude (CSS pixels) and the frame it is rolled for
+    pub stroke_jitter: f32,
+    pub frame: u32,
+}
+
 /// Perform Delaunay triangulation and build vertex buffers
 pub fn triangulate(
     points: &[Point],
     width: f32,
     height: f32,
+    boundary: &Boundary,
+    params: &AttributeParams<'_>,
     buffers: &mut TriangulationBuffers,
 ) -> usize {
-    // Generate ghost points for edge continuity
-    let ghosts = generate_ghost_points(points, width, height);
+    buffers.combined_stale = true;
+    buffers.built_layout = buffers.layout;
+
+    // Build combined points array (real, ghosts, corners) in reused scratch
+    let mut all_points = std::mem::take(&mut buffers.scratch_vertices);
+    all_points.clear();
+    for (i, p) in points.iter().enumerate() {
+        all_points.push(Vertex::from_point(i, p, width, height, boundary));
+    }
 
-    // Add corner points for full coverage
+    // Generate ghost points for edge continuity, a few point spacings deep unless fixed
+    let ghost_margin = if buffers.ghost_margin > 0.0 {
+        buffers.ghost_margin * params.pixel_ratio
+    } else {
+        (width * height / points.len().max(1) as f32).sqrt() * GHOST_MARGIN_SPACINGS
+    };
+    generate_ghost_points(points, width, height, boundary, ghost_margin, &mut all_points);
+
+    // Add corner points for full coverage (outside any open-edge overhang)
     let margin = 1.0;
+    let (over_x, over_y) = boundary.overhang(width, height);
+    let margin_x = margin + over_x;
+    let margin_y = margin + over_y;
     let corners = [
-        (-margin, -margin, 0.0f32),
-        (width + margin, -margin, 0.0),
-        (width + margin, height + margin, 0.0),
-        (-margin, height + margin, 0.0),
+        Vertex::corner(-margin_x, -margin_y),
+        Vertex::corner(width + margin_x, -margin_y),
+        Vertex::corner(width + margin_x, height + margin_y),
+        Vertex::corner(-margin_x, height + margin_y),
     ];
 
-    // Build combined points array
-    let total_points = points.len() + ghosts.len() + corners.len();
-    let mut all_points: Vec<(f32, f32, f32)> = Vec::with_capacity(total_points);
-
-    for p in points {
-        all_points.push((p.x, p.y, p.z));
+    all_points.extend_from_slice(&corners);
+    if params.height_scale != 1.0 {
+        for v in all_points.iter_mut() {
+            v.z *= params.height_scale;
+        }
     }
-    for g in &ghosts {
-        all_points.push(*g);
+
+    // Convert to delaunator format; with base topology, real points and their ghosts
+    // are connected at their rest positions (displacement removed) but drawn displaced
+    let mut delaunay_points = std::mem::take(&mut buffers.scratch_delaunay);
+    delaunay_points.clear();
+    delaunay_points.extend(all_points.iter().map(|v| {
+        let (x, y) = match v.source {
+            Some(i) if buffers.base_topology => {
+                let p = &points[i];
+                (v.x - (p.x - p.base_x), v.y - (p.y - p.base_y))
+            }
+            _ => (v.x, v.y),
+        };
+        DelaunayPoint {
+            x: x as f64,
+            y: y as f64,
+        }
+    }));
+
+    // Triangulate (or repair the previous mesh in incremental mode)
+    let previous = std::mem::replace(&mut buffers.last_mesh, empty_mesh());
+    let result = match buffers.incremental {
+        Some(ref mut mesh) => {
+            mesh.triangulate(previous, &delaunay_points, &all_points, buffers.hysteresis)
+        }
+        None => delaunay_triangulate(&delaunay_points),
+    };
+
+    // Smooth heights across triangulated neighbors (optional)
+    if buffers.height_blur > 0 && !buffers.lean {
+        blur_heights(
+            &mut all_points,
+            points.len(),
+            &result.triangles,
+            &result.halfedges,
+            buffers.height_blur,
+            &mut buffers.scratch_blur,
+        );
     }
-    for c in &corners {
-        all_points.push(*c);
+
+    // Neighbor pairs for mesh springs (optional)
+    if buffers.collect_edges {
+        collect_edges(
+            &all_points,
+            &result.triangles,
+            &result.halfedges,
+            &mut buffers.edges,
+            &mut buffers.scratch_edges,
+        );
+    } else {
+        buffers.edges.clear();
     }
 
-    // Convert to delaunator format
-    let delaunay_points: Vec<DelaunayPoint> = all_points
-        .iter()
-        .map(|(x, y, _)| DelaunayPoint {
-            x: *x as f64,
-            y: *y as f64,
-        })
-        .collect();
+    // Shadows need the full adjacency, so they are computed before culling
+    let mut layout = buffers.layout;
+    if buffers.lean {
+        layout.voronoi = false;
+        layout.edge_mode = EdgeMode::Straight;
+    }
+    let mut shadows = std::mem::take(&mut buffers.scratch_shadows);
+    shadows.clear();
+    if layout.has(vertex_attr::SHADOW) && !layout.indexed {
+        if buffers.lean {
+            shadows.resize(result.triangles.len() / 3, 1.0);
+        } else {
+            let (triangles, halfedges) = (&result.triangles, &result.halfedges);
+            compute_shadows(&all_points, triangles, halfedges, params, &mut shadows);
+        }
+    }
 
-    // Triangulate
-    let result = delaunay_triangulate(&delaunay_points);
-    let triangles = &result.triangles;
+    // Drop triangles whose centroid is covered by an obstacle, and border slivers
+    // Voronoi cells still come from the full triangulation
+    let mut kept = std::mem::take(&mut buffers.scratch_triangles);
+    kept.clear();
+    // Per full triangle: survived culling (empty = all kept)
+    let mut kept_mask = std::mem::take(&mut buffers.scratch_kept);
+    kept_mask.clear();
+    let triangles: &[usize] = if params.culled.is_empty() && buffers.sliver_cos.is_none() {
+        &result.triangles
+    } else {
+        let mut kept_shadows = 0;
+        for (t, tri) in result.triangles.chunks_exact(3).enumerate() {
+            let (a, b, c) = (&all_points[tri[0]], &all_points[tri[1]], &all_points[tri[2]]);
+            let cx = (a.x + b.x + c.x) / 3.0;
+            let cy = (a.y + b.y + c.y) / 3.0;
+            // Interior triangles are never dropped as slivers, so the canvas keeps no holes
+            let sliver = buffers.sliver_cos.is_some_and(|max_cos| {
+                tri.iter().any(|&v| v >= points.len()) && max_corner_cos([*a, *b, *c]) > max_cos
+            });
+            let dropped = sliver || params.culled.iter().any(|o| o.contains(cx, cy));
+            kept_mask.push(!dropped);
+            if dropped {
+                continue;
+            }
+            kept.extend_from_slice(tri);
+            if !shadows.is_empty() {
+                shadows[kept_shadows] = shadows[t];
+                kept_shadows += 1;
+            }
+        }
+        shadows.truncate(kept_shadows);
+        &kept
+    };
     let num_triangles = triangles.len() / 3;
+    let transform = OutputTransform::new(layout.space, width, height);
+
+    // Build triangle buffers (expanded, or shared pool + indices)
+    if layout.indexed {
+        build_vertex_pool(
+            &all_points,
+            points,
+            &layout,
+            params,
+            &transform,
+            &mut buffers.indexed_vertices,
+        );
+        buffers.indices.clear();
+        buffers.indices.extend(triangles.iter().map(|&i| i as u32));
+        buffers.triangle_vertices.clear();
+    } else {
+        build_triangle_buffer(
+            &all_points,
+            points,
+            triangles,
+            &shadows,
+            &layout,
+            params,
+            &transform,
+            &mut buffers.triangle_vertices,
+        );
+        buffers.indexed_vertices.clear();
+        buffers.indices.clear();
+    }
+
+    // Per-triangle centroid, area and height for JS-side effects (optional)
+    buffers.triangle_metadata.clear();
+    if buffers.collect_metadata {
+        build_triangle_metadata(&all_points, triangles, &transform, &mut buffers.triangle_metadata);
+    }
+
+    // Build the Float64 copy (optional)
+    if layout.f64_output {
+        build_triangle_buffer_f64(
+            &all_points,
+            triangles,
+            &transform,
+            &mut buffers.triangle_vertices_f64,
+        );
+    } else {
+        buffers.triangle_vertices_f64.clear();
+    }
 
-    // Build triangle vertex buffer
-    build_triangle_buffer(&all_points, triangles, &mut buffers.triangle_vertices);
+    // Build stroke buffers (each edge once, from the full mesh's halfedges); bundled
+    // polylines have points outside the shared pool, so they stay expanded
+    buffers.stroke_indices.clear();
+    if layout.indexed && layout.edge_mode == EdgeMode::Straight {
+        let t = &result.triangles;
+        buffers.stroke_indices.extend(
+            stroke_edges(&result, &kept_mask, &layout)
+                .flat_map(|e| [t[e] as u32, t[next_halfedge(e)] as u32]),
+        );
+        buffers.stroke_vertices.clear();
+    } else {
+        build_stroke_buffer(
+            points,
+            &all_points,
+            &result,
+            &kept_mask,
+            &layout,
+            params,
+            &transform,
+            &mut buffers.stroke_vertices,
+        );
+    }
 
-    // Build stroke vertex buffer
-    build_stroke_buffer(&all_points, triangles, &mut buffers.stroke_vertices);
+    // Build Voronoi cells from the dual (optional)
+    if layout.voronoi {
+        build_voronoi(
+            &all_points,
+            points.len(),
+            &result.triangles,
+            &result.halfedges,
+            &transform,
+            &mut buffers.voronoi,
+        );
+    } else {
+        buffers.voronoi.clear();
+    }
 
     // Build point vertex buffer (only real points)
-    build_point_buffer(points, &mut buffers.point_vertices);
+    build_point_buffer(points, &layout, params, &transform, &mut buffers.point_vertices);
+
+    buffers.scratch_vertices = all_points;
+    buffers.scratch_delaunay = delaunay_points;
+    buffers.scratch_triangles = kept;
+    buffers.scratch_kept = kept_mask;
+    buffers.scratch_shadows = shadows;
+    buffers.last_mesh = result;
+    buffers.constellation_vertices.clear();
 
     num_triangles
 }
 
+/// Build outputs without triangulating: distance-based edges (none for a zero
+/// `max_distance`) plus the point buffer
+/// Triangle, stroke, index and Voronoi buffers stay empty; returns the edge count
+pub fn build_constellation_output(
+    points: &[Point],
+    width: f32,
+    height: f32,
+    grid: &SpatialGrid,
+    max_distance: f32,
+    params: &AttributeParams<'_>,
+    buffers: &mut TriangulationBuffers,
+) -> usize {
+    buffers.combined_stale = true;
+    buffers.built_layout = buffers.layout;
+    let layout = buffers.layout;
+    let transform = OutputTransform::new(layout.space, width, height);
+
+    buffers.triangle_vertices.clear();
+    buffers.triangle_vertices_f64.clear();
+    buffers.stroke_vertices.clear();
+    buffers.stroke_indices.clear();
+    buffers.triangle_metadata.clear();
+    buffers.indexed_vertices.clear();
+    buffers.indices.clear();
+    buffers.voronoi.clear();
+    buffers.last_mesh.triangles.clear();
+
+    build_constellation(
+        points,
+        grid,
+        max_distance,
+        &transform,
+        &mut buffers.constellation_vertices,
+    );
+    build_point_buffer(points, &layout, params, &transform, &mut buffers.point_vertices);
+
+    buffers.constellation_edge_count()
+}
+
+/// Sequential writes into a pre-sized slice
+struct SliceWriter<'a> {
+    slice: &'a mut [f32],
+    at: usize,
+}
+
+impl SliceWriter<'_> {
+    fn push(&mut self, value: f32) {
+        self.slice[self.at] = value;
+        self.at += 1;
+    }
+
+    fn extend_from_slice(&mut self, values: &[f32]) {
+        self.slice[self.at..self.at + values.len()].copy_from_slice(values);
+        self.at += values.len();
+    }
+}
+
 /// Build triangle vertex buffer from triangulation result
+#[allow(clippy::too_many_arguments)]
 fn build_triangle_buffer(
-    all_points: &[(f32, f32, f32)],
+    all_points: &[Vertex],
+    points: &[Point],
     triangles: &[usize],
+    shadows: &[f32],
+    layout: &VertexLayout,
+    params: &AttributeParams<'_>,
+    transform: &OutputTransform,
     buffer: &mut Vec<f32>,
 ) {
-    let num_triangles = triangles.len() / 3;
-    let tri_size = num_triangles * 3 * 6;
+    let triangle_floats = 3 * layout.triangle_stride();
+    buffer.clear();
+    buffer.resize(triangles.len() / 3 * triangle_floats, 0.0);
+
+    // Triangles are independent, so large meshes are written in parallel chunks
+    for_each_chunk(buffer, triangle_floats, |first, chunk| {
+        for (k, out) in chunk.chunks_exact_mut(triangle_floats).enumerate() {
+            let i = (first + k) * 3;
+            write_triangle(i, all_points, points, triangles, shadows, layout, params, transform, out);
+        }
+    });
+}
+
+/// Write the three vertices of the triangle starting at `triangles[i]` into `out`
+#[allow(clippy::too_many_arguments)]
+fn write_triangle(
+    i: usize,
+    all_points: &[Vertex],
+    points: &[Point],
+    triangles: &[usize],
+    shadows: &[f32],
+    layout: &VertexLayout,
+    params: &AttributeParams<'_>,
+    transform: &OutputTransform,
+    out: &mut [f32],
+) {
+    let mut out = SliceWriter { slice: out, at: 0 };
+    let p0 = all_points[triangles[i]];
+    let p1 = all_points[triangles[i + 1]];
+    let p2 = all_points[triangles[i + 2]];
+
+    // Calculate centroid
+    let pixel_cx = (p0.x + p1.x + p2.x) / 3.0;
+    let pixel_cy = (p0.y + p1.y + p2.y) / 3.0;
+    let centroid_x = transform.x(pixel_cx);
+    let centroid_y = transform.y(pixel_cy);
+    let avg_height = (p0.z + p1.z + p2.z) / 3.0;
+
+    let flip = if layout.has(vertex_attr::FLIP) {
+        params.flips.sample(pixel_cx, pixel_cy, params.time)
+    } else {
+        (0.0, 0.0, 0.0)
+    };
+
+    let excluded = if layout.has(vertex_attr::EXCLUSION) {
+        let (min_x, max_x) = (p0.x.min(p1.x).min(p2.x), p0.x.max(p1.x).max(p2.x));
+        let (min_y, max_y) = (p0.y.min(p1.y).min(p2.y), p0.y.max(p1.y).max(p2.y));
+        let hit = params.exclusions.iter().any(|r| r.overlaps(min_x, min_y, max_x, max_y));
+        if hit {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        0.0
+    };
+
+    // Corners (no source) weigh as neutral depth; all-zero weights fall back to the mean
+    let weighted = if layout.has(vertex_attr::WEIGHTED_CENTROID) {
+        let [w0, w1, w2] = [p0, p1, p2].map(|v| v.depth(points));
+        let total = w0 + w1 + w2;
+        if total > 1e-6 {
+            let x = (p0.x * w0 + p1.x * w1 + p2.x * w2) / total;
+            let y = (p0.y * w0 + p1.y * w1 + p2.y * w2) / total;
+            (transform.x(x), transform.y(y), total / 3.0)
+        } else {
+            (centroid_x, centroid_y, 0.0)
+        }
+    } else {
+        (0.0, 0.0, 0.0)
+    };
+
+    let seed = if layout.has(vertex_attr::FACET_SEED) {
+        facet_seed([p0, p1, p2])
+    } else {
+        0.0
+    };
+
+    let normal = if layout.has(vertex_attr::FACE_NORMAL | vertex_attr::DIFFUSE) {
+        face_normal([p0, p1, p2], params)
+    } else {
+        [0.0; 3]
+    };
+    let diffuse = if layout.has(vertex_attr::DIFFUSE) {
+        let ([lx, ly, lz], ambient) = params.light;
+        let lambert = (normal[0] * lx + normal[1] * ly + normal[2] * lz).max(0.0);
+        ambient + (1.0 - ambient) * lambert
+    } else {
+        0.0
+    };
+
+    for v in [p0, p1, p2] {
+        // Base: [x, y, height, centroidY, centroidX, centroidY]
+        out.push(transform.x(v.x));
+        out.push(transform.y(v.y));
+        out.push(avg_height);
+        out.push(centroid_y);
+        out.push(centroid_x);
+        out.push(centroid_y);
+
+        // Optional attributes, in flag bit order
+        if layout.has(vertex_attr::DEPTH) {
+            out.push(v.depth(points));
+        }
+        if layout.has(vertex_attr::OCCLUSION) {
+            out.push(v.source.map_or(0.0, |i| params.occlusion[i]));
+        }
+        if layout.has(vertex_attr::FLIP) {
+            out.push(flip.0);
+            out.push(flip.1);
+            out.push(flip.2);
+        }
+        if layout.has(vertex_attr::LIGHT_GRADIENT) {
+            let (lx, ly) = params.light_dir;
+            out.push((v.x - pixel_cx) * lx + (v.y - pixel_cy) * ly);
+        }
+        if layout.has(vertex_attr::HEAT) {
+            out.push(v.source.map_or(0.0, |i| params.heat[i]));
+        }
+        if layout.has(vertex_attr::EXCLUSION) {
+            out.push(excluded);
+        }
+        if layout.has(vertex_attr::SHADOW) {
+            out.push(shadows[i / 3]);
+        }
+        if layout.has(vertex_attr::WEIGHTED_CENTROID) {
+            out.push(weighted.0);
+            out.push(weighted.1);
+            out.push(weighted.2);
+        }
+        if layout.has(vertex_attr::FACET_SEED) {
+            out.push(seed);
+        }
+        if layout.has(vertex_attr::FACE_NORMAL) {
+            out.extend_from_slice(&normal);
+        }
+        if layout.has(vertex_attr::DIFFUSE) {
+            out.push(diffuse);
+        }
+    }
+}
+
+/// Unit normal of the triangle lifted to its heights (FACE_NORMAL_RELIEF CSS pixels per
+/// unit of height intensity), facing the viewer (+z) whatever the winding
+fn face_normal(vertices: [Vertex; 3], params: &AttributeParams<'_>) -> [f32; 3] {
+    let relief = FACE_NORMAL_RELIEF * params.pixel_ratio / params.height_intensity.max(1e-3);
+    let [a, b, c] = vertices.map(|v| (v.x, v.y, v.z * relief));
+    let (ux, uy, uz) = (b.0 - a.0, b.1 - a.1, b.2 - a.2);
+    let (vx, vy, vz) = (c.0 - a.0, c.1 - a.1, c.2 - a.2);
+    let (nx, ny, nz) = (uy * vz - uz * vy, uz * vx - ux * vz, ux * vy - uy * vx);
+    let length = (nx * nx + ny * ny + nz * nz).sqrt() * nz.signum();
+    if length.abs() < 1e-6 {
+        return [0.0, 0.0, 1.0];
+    }
+    [nx / length, ny / length, nz / length]
+}
+
+/// Hash of a triangle's sorted source indices in [0, 1); ghosts share their source's
+/// index, so a facet split by a wrapped seam gets the same value on both sides
+fn facet_seed(vertices: [Vertex; 3]) -> f32 {
+    let mut ids = vertices.map(|v| v.source.map_or(u32::MAX, |i| i as u32));
+    ids.sort_unstable();
+    let hash = Rng::derive(Rng::derive(ids[0], ids[1]).next(), ids[2]).next();
+    // Top 24 bits, exact in f32 and never reaching 1
+    (hash >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// Largest corner cosine of a triangle (from its smallest angle); 1 when degenerate
+fn max_corner_cos([a, b, c]: [Vertex; 3]) -> f32 {
+    let corner = |p: Vertex, q: Vertex, r: Vertex| {
+        let (ux, uy, vx, vy) = (q.x - p.x, q.y - p.y, r.x - p.x, r.y - p.y);
+        let lengths = ((ux * ux + uy * uy) * (vx * vx + vy * vy)).sqrt();
+        if lengths > 0.0 {
+            (ux * vx + uy * vy) / lengths
+        } else {
+            1.0
+        }
+    };
+    corner(a, b, c).max(corner(b, c, a)).max(corner(c, a, b))
+}
+
+/// Append [centroid x, centroid y, area, avg height] per triangle
+fn build_triangle_metadata(
+    all_points: &[Vertex],
+    triangles: &[usize],
+    transform: &OutputTransform,
+    buffer: &mut Vec<f32>,
+) {
+    buffer.reserve(triangles.len() / 3 * TRIANGLE_METADATA_STRIDE);
+    for tri in triangles.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| all_points[tri[k]]);
+        let area = ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() * 0.5;
+        buffer.extend([
+            transform.x((a.x + b.x + c.x) / 3.0),
+            transform.y((a.y + b.y + c.y) / 3.0),
+            area,
+            (a.z + b.z + c.z) / 3.0,
+        ]);
+    }
+}
 
+/// Build the base triangle layout in f64 from the f32 vertex positions
+/// Centroids and the output transform are evaluated in f64, adding no rounding
+fn build_triangle_buffer_f64(
+    all_points: &[Vertex],
+    triangles: &[usize],
+    transform: &OutputTransform,
+    buffer: &mut Vec<f64>,
+) {
     buffer.clear();
-    if buffer.capacity() < tri_size {
-        buffer.reserve(tri_size - buffer.capacity());
-    }
-
-    for i in (0..triangles.len()).step_by(3) {
-        let i0 = triangles[i];
-        let i1 = triangles[i + 1];
-        let i2 = triangles[i + 2];
-
-        let p0 = all_points[i0];
-        let p1 = all_points[i1];
-        let p2 = all_points[i2];
-
-        // Calculate centroid
-        let centroid_x = (p0.0 + p1.0 + p2.0) / 3.0;
-        let centroid_y = (p0.1 + p1.1 + p2.1) / 3.0;
-        let avg_height = (p0.2 + p1.2 + p2.2) / 3.0;
-
-        // Vertex 0: [x, y, height, centroidY, centroidX, centroidY]
-        buffer.push(p0.0);
-        buffer.push(p0.1);
-        buffer.push(avg_height);
-        buffer.push(centroid_y);
-        buffer.push(centroid_x);
-        buffer.push(centroid_y);
-
-        // Vertex 1
-        buffer.push(p1.0);
-        buffer.push(p1.1);
-        buffer.push(avg_height);
-        buffer.push(centroid_y);
-        buffer.push(centroid_x);
-        buffer.push(centroid_y);
-
-        // Vertex 2
-        buffer.push(p2.0);
-        buffer.push(p2.1);
-        buffer.push(avg_height);
-        buffer.push(centroid_y);
-        buffer.push(centroid_x);
-        buffer.push(centroid_y);
-    }
-}
-
-/// Build stroke (edge) vertex buffer from triangulation result
-fn build_stroke_buffer(all_points: &[(f32, f32, f32)], triangles: &[usize], buffer: &mut Vec<f32>) {
-    let num_triangles = triangles.len() / 3;
-    let stroke_size = num_triangles * 3 * 2 * 2;
+    buffer.reserve(triangles.len() * VertexLayout::TRIANGLE_BASE);
+
+    for t in triangles.chunks_exact(3) {
+        let [p0, p1, p2] = [0, 1, 2].map(|k| all_points[t[k]]).map(|v| (v.x as f64, v.y as f64));
+        let centroid_x = transform.x64((p0.0 + p1.0 + p2.0) / 3.0);
+        let centroid_y = transform.y64((p0.1 + p1.1 + p2.1) / 3.0);
+        let avg_height = t.iter().map(|&i| all_points[i].z as f64).sum::<f64>() / 3.0;
+
+        for (x, y) in [p0, p1, p2] {
+            buffer.extend_from_slice(&[
+                transform.x64(x),
+                transform.y64(y),
+                avg_height,
+                centroid_y,
+                centroid_x,
+                centroid_y,
+            ]);
+        }
+    }
+}
+
+/// Build the shared vertex buffer for indexed output (one entry per triangulation vertex)
+fn build_vertex_pool(
+    all_points: &[Vertex],
+    points: &[Point],
+    layout: &VertexLayout,
+    params: &AttributeParams<'_>,
+    transform: &OutputTransform,
+    buffer: &mut Vec<f32>,
+) {
+    buffer.clear();
+    buffer.reserve(all_points.len() * layout.indexed_stride());
+
+    for v in all_points {
+        buffer.push(transform.x(v.x));
+        buffer.push(transform.y(v.y));
+        buffer.push(v.z);
+
+        if layout.has(vertex_attr::DEPTH) {
+            buffer.push(v.depth(points));
+        }
+        if layout.has(vertex_attr::OCCLUSION) {
+            buffer.push(v.source.map_or(0.0, |i| params.occlusion[i]));
+        }
+        if layout.has(vertex_attr::HEAT) {
+            buffer.push(v.source.map_or(0.0, |i| params.heat[i]));
+        }
+    }
+}
+
+/// Average each point's height with its triangulation neighbors, `iterations` times
+/// Ghosts follow their source point so wrapped seams stay continuous; corners keep theirs
+fn blur_heights(
+    all_points: &mut [Vertex],
+    real_count: usize,
+    triangles: &[usize],
+    halfedges: &[usize],
+    iterations: u32,
+    sums: &mut Vec<(f32, f32)>,
+) {
+    for _ in 0..iterations {
+        sums.clear();
+        sums.resize(real_count, (0.0, 0.0));
+
+        // Each undirected edge once; edges between two ghosts are skipped
+        for e in 0..triangles.len() {
+            let opposite = halfedges[e];
+            if opposite != EMPTY && opposite < e {
+                continue;
+            }
+            let (a, b) = (triangles[e], triangles[next_halfedge(e)]);
+            if a >= real_count && b >= real_count {
+                continue;
+            }
+            let (va, vb) = (all_points[a], all_points[b]);
+            if let (Some(sa), Some(sb)) = (va.source, vb.source) {
+                if sa != sb {
+                    sums[sa].0 += vb.z;
+                    sums[sa].1 += 1.0;
+                    sums[sb].0 += va.z;
+                    sums[sb].1 += 1.0;
+                }
+            }
+        }
+
+        for (i, (sum, count)) in sums.iter_mut().enumerate() {
+            *sum = (all_points[i].z + *sum) / (1.0 + *count);
+        }
+        for v in all_points.iter_mut() {
+            if let Some(source) = v.source {
+                v.z = sums[source].0;
+            }
+        }
+    }
+}
+
+/// Per-triangle light attenuation: each edge-adjacent triangle lying toward the light
+/// and higher than this one blocks light in proportion to the height difference
+fn compute_shadows(
+    all_points: &[Vertex],
+    triangles: &[usize],
+    halfedges: &[usize],
+    params: &AttributeParams<'_>,
+    shadows: &mut Vec<f32>,
+) {
+    let centroid = |t: usize| {
+        let [a, b, c] = [0, 1, 2].map(|k| all_points[triangles[t * 3 + k]]);
+        let avg = |f: fn(&Vertex) -> f32| (f(&a) + f(&b) + f(&c)) / 3.0;
+        (avg(|v| v.x), avg(|v| v.y), avg(|v| v.z))
+    };
+
+    let (lx, ly) = params.light_dir;
+    let scale = SHADOW_STRENGTH / params.height_intensity.max(1e-3);
+    for t in 0..triangles.len() / 3 {
+        let (cx, cy, cz) = centroid(t);
+        let mut blocked = 0.0;
+        for &opposite in &halfedges[t * 3..t * 3 + 3] {
+            if opposite == EMPTY {
+                continue;
+            }
+            let (nx, ny, nz) = centroid(opposite / 3);
+            let (dx, dy) = (nx - cx, ny - cy);
+            let distance = (dx * dx + dy * dy).sqrt();
+            let toward = if distance > 0.0 {
+                (dx * lx + dy * ly) / distance
+            } else {
+                0.0
+            };
+            if toward > 0.0 && nz > cz {
+                blocked += (nz - cz) * toward;
+            }
+        }
+        shadows.push(1.0 - (blocked * scale).min(1.0));
+    }
+}
+
+/// Collect each neighbor pair of real points once, ghosts mapped to their source
+/// An edge that was already in the previous list keeps its rest length
+fn collect_edges(
+    all_points: &[Vertex],
+    triangles: &[usize],
+    halfedges: &[usize],
+    edges: &mut Vec<(usize, usize, f32)>,
+    previous: &mut Vec<(usize, usize, f32)>,
+) {
+    std::mem::swap(edges, previous);
+    edges.clear();
+    for e in 0..triangles.len() {
+        let opposite = halfedges[e];
+        if opposite != EMPTY && opposite < e {
+            continue;
+        }
+        let a = all_points[triangles[e]].source;
+        let b = all_points[triangles[next_halfedge(e)]].source;
+        if let (Some(a), Some(b)) = (a, b) {
+            if a != b {
+                edges.push((a.min(b), a.max(b), f32::NAN));
+            }
+        }
+    }
+
+    // Seam edges appear once per side
+    edges.sort_unstable_by_key(|&(a, b, _)| (a, b));
+    edges.dedup_by_key(|&mut (a, b, _)| (a, b));
+
+    // Both lists are sorted, so one merge pass carries the rest lengths over
+    let mut old = previous.iter().peekable();
+    for edge in edges.iter_mut() {
+        let key = (edge.0, edge.1);
+        while old.next_if(|o| (o.0, o.1) < key).is_some() {}
+        if let Some(o) = old.next_if(|o| (o.0, o.1) == key) {
+            edge.2 = o.2;
+        }
+    }
+}
+
+/// Halfedges to stroke: each edge of a drawn triangle (`kept` per triangle, empty = all
+/// drawn) once, an interior edge by the higher of its two halfedges unless the twin's
+/// triangle was culled
+fn stroke_edges<'a>(
+    mesh: &'a Triangulation,
+    kept: &'a [bool],
+    layout: &VertexLayout,
+) -> impl Iterator<Item = usize> + 'a {
+    let drawn = move |e: usize| kept.is_empty() || kept[e / 3];
+    let skip_hull = layout.skip_hull_edges;
+    (0..mesh.triangles.len()).filter(move |&e| {
+        let twin = mesh.halfedges[e];
+        if twin == EMPTY {
+            drawn(e) && !skip_hull
+        } else {
+            drawn(e) && (e > twin || !drawn(twin))
+        }
+    })
+}
+
+/// Build stroke (edge) vertex buffer from triangulation result, one line per `stroke_edges`
+#[allow(clippy::too_many_arguments)]
+fn build_stroke_buffer(
+    points: &[Point],
+    all_points: &[Vertex],
+    mesh: &Triangulation,
+    kept: &[bool],
+    layout: &VertexLayout,
+    params: &AttributeParams<'_>,
+    transform: &OutputTransform,
+    buffer: &mut Vec<f32>,
+) {
+    let triangles = &mesh.triangles;
+    let segments = layout.edge_mode.segments();
+    // Upper bound: every halfedge drawn
+    let stroke_size = triangles.len() * segments * 2 * layout.stroke_stride();
+    let motion = layout.has(vertex_attr::STROKE_MOTION);
+    let jitter = layout.has(vertex_attr::AUDIO_JITTER);
 
     buffer.clear();
     if buffer.capacity() < stroke_size {
         buffer.reserve(stroke_size - buffer.capacity());
     }
 
-    for i in (0..triangles.len()).step_by(3) {
-        let i0 = triangles[i];
-        let i1 = triangles[i + 1];
-        let i2 = triangles[i + 2];
+    let mut push = |x: f32, y: f32, edge_motion: f32, offset: f32| {
+        buffer.push(transform.x(x));
+        buffer.push(transform.y(y));
+        if motion {
+            buffer.push(edge_motion);
+        }
+        if jitter {
+            buffer.push(offset);
+        }
+    };
+
+    // Signed offset per source point, stable within a frame so shared endpoints agree
+    let offset = |v: Vertex| {
+        if !jitter || params.stroke_jitter <= 0.0 {
+            return 0.0;
+        }
+        v.source.map_or(0.0, |i| {
+            let roll = Rng::derive(params.frame, i as u32).next_f32();
+            (roll * 2.0 - 1.0) * params.stroke_jitter
+        })
+    };
 
-        let p0 = all_points[i0];
-        let p1 = all_points[i1];
-        let p2 = all_points[i2];
+    for e in stroke_edges(mesh, kept, layout) {
+        let (a, b) = (all_points[triangles[e]], all_points[triangles[next_halfedge(e)]]);
+        let edge_motion = if motion {
+            let moved = |v: Vertex| v.source.map_or(0.0, |i| params.motion[i]);
+            moved(a).max(moved(b))
+        } else {
+            0.0
+        };
 
-        // Edge 0-1
-        buffer.push(p0.0);
-        buffer.push(p0.1);
-        buffer.push(p1.0);
-        buffer.push(p1.1);
+        let (offset_a, offset_b) = (offset(a), offset(b));
+        if segments == 1 {
+            push(a.x, a.y, edge_motion, offset_a);
+            push(b.x, b.y, edge_motion, offset_b);
+            continue;
+        }
 
-        // Edge 1-2
-        buffer.push(p1.0);
-        buffer.push(p1.1);
-        buffer.push(p2.0);
-        buffer.push(p2.1);
+        // Quadratic curve through a control point pushed off the edge by the
+        // sideways part of the endpoints' drift (corners do not drift)
+        let drift = |v: Vertex| {
+            v.source.map_or((0.0, 0.0), |i| (points[i].vx, points[i].vy))
+        };
+        let ((ax, ay), (bx, by)) = (drift(a), drift(b));
+        let (fx, fy) = (ax + bx, ay + by);
+        // Edge normal scaled by the edge length
+        let (nx, ny) = (a.y - b.y, b.x - a.x);
+        let scale = (fx * fx + fy * fy).sqrt() * (nx * nx + ny * ny).sqrt();
+        let bow = if scale > 0.0 {
+            (fx * nx + fy * ny) / scale * EDGE_BUNDLE_CURVATURE
+        } else {
+            0.0
+        };
+        let cx = (a.x + b.x) * 0.5 + nx * bow;
+        let cy = (a.y + b.y) * 0.5 + ny * bow;
 
-        // Edge 2-0
-        buffer.push(p2.0);
-        buffer.push(p2.1);
-        buffer.push(p0.0);
-        buffer.push(p0.1);
+        let curve = |t: f32| {
+            let (u, tt) = ((1.0 - t) * (1.0 - t), t * t);
+            let m = 2.0 * t * (1.0 - t);
+            (u * a.x + m * cx + tt * b.x, u * a.y + m * cy + tt * b.y)
+        };
+        let mut prev = (a.x, a.y, offset_a);
+        for s in 1..=segments {
+            let t = s as f32 / segments as f32;
+            let next = if s == segments {
+                (b.x, b.y, offset_b)
+            } else {
+                let (x, y) = curve(t);
+                (x, y, offset_a + (offset_b - offset_a) * t)
+            };
+            push(prev.0, prev.1, edge_motion, prev.2);
+            push(next.0, next.1, edge_motion, next.2);
+            prev = next;
+        }
     }
 }
 
 /// Build point vertex buffer (only real points, not ghosts)
-fn build_point_buffer(points: &[Point], buffer: &mut Vec<f32>) {
-    let point_size = points.len() * 2;
+fn build_point_buffer(
+    points: &[Point],
+    layout: &VertexLayout,
+    params: &AttributeParams<'_>,
+    transform: &OutputTransform,
+    buffer: &mut Vec<f32>,
+) {
+    let point_size = points.len() * layout.point_stride();
 
     buffer.clear();
     if buffer.capacity() < point_size {
@@ -242,7 +1504,12 @@ fn build_point_buffer(points: &[Point], buffer: &mut Vec<f32>) {
     }
 
     for p in points {
-        buffer.push(p.x);
-        buffer.push(p.y);
+        buffer.push(transform.x(p.x));
+        buffer.push(transform.y(p.y));
+
+        if layout.has(vertex_attr::DEPTH) {
+            buffer.push(depth_factor(p.depth, params.depth_parallax));
+            buffer.push(p.depth);
+        }
     }
 }
@@ -0,0 +1,30 @@
+//! Ambient turbulence: time-varying simplex noise nudging point displacement, a
+//! constant subtle shimmer that the springs keep from accumulating
+
+use crate::constants::TURBULENCE_AXIS_OFFSET;
+use crate::noise::simplex3d;
+use crate::point::Point;
+
+/// Turbulence parameters (amount in CSS pixels per frame, scale in 1/CSS pixels)
+#[derive(Clone, Copy)]
+pub struct Turbulence {
+    pub amount: f32,
+    pub scale: f32,
+    /// Noise evolution per frame
+    pub speed: f32,
+}
+
+impl Turbulence {
+    /// Push each point's displacement by the noise at its rest position
+    /// x and y read the same field at distant offsets, so the axes are uncorrelated
+    pub fn apply(&self, points: &mut [Point], time: f32, pixel_ratio: f32, delta_time: f32) {
+        let push = self.amount * pixel_ratio * delta_time;
+        let nz = time * self.speed;
+        for point in points.iter_mut() {
+            let nx = point.base_x / pixel_ratio * self.scale;
+            let ny = point.base_y / pixel_ratio * self.scale;
+            point.dx += simplex3d(nx, ny, nz) * push;
+            point.dy += simplex3d(nx + TURBULENCE_AXIS_OFFSET, ny, nz) * push;
+        }
+    }
+}
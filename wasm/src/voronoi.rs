@@ -0,0 +1,147 @@
+//! Voronoi cells from the Delaunay dual (circumcenters around each point)
+//! Real points are surrounded by ghosts/corners, so their cells are always closed
+
+use delaunator::EMPTY;
+
+use crate::triangulation::{OutputTransform, Vertex};
+
+/// Next halfedge within the same triangle
+#[inline]
+fn next_halfedge(e: usize) -> usize {
+    if e % 3 == 2 {
+        e - 2
+    } else {
+        e + 1
+    }
+}
+
+/// Circumcenter of a triangle (falls back to the centroid for degenerate triangles)
+fn circumcenter(a: &Vertex, b: &Vertex, c: &Vertex) -> (f32, f32) {
+    let (ax, ay) = (a.x as f64, a.y as f64);
+    let (bx, by) = ((b.x - a.x) as f64, (b.y - a.y) as f64);
+    let (cx, cy) = ((c.x - a.x) as f64, (c.y - a.y) as f64);
+    let d = 2.0 * (bx * cy - by * cx);
+    if d.abs() < 1e-9 {
+        return ((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+    }
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let ux = (cy * b2 - by * c2) / d;
+    let uy = (bx * c2 - cx * b2) / d;
+    ((ax + ux) as f32, (ay + uy) as f32)
+}
+
+/// Voronoi output buffers plus scratch reused across frames
+#[derive(Clone)]
+pub struct VoronoiBuffers {
+    /// Fan-triangulated cells: [x, y, z, siteY, siteX, siteY] per vertex
+    pub cells: Vec<f32>,
+    /// Cell edges: [x1, y1, x2, y2] per edge
+    pub edges: Vec<f32>,
+    centers: Vec<(f32, f32)>,
+    inedges: Vec<usize>,
+    polygon: Vec<(f32, f32)>,
+}
+
+impl VoronoiBuffers {
+    pub fn new() -> Self {
+        Self {
+            cells: Vec::new(),
+            edges: Vec::new(),
+            centers: Vec::new(),
+            inedges: Vec::new(),
+            polygon: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.edges.clear();
+    }
+}
+
+/// Build fan-triangulated cells for the first `real_count` vertices plus their edges
+/// Each shared edge is emitted once
+pub fn build_voronoi(
+    all_points: &[Vertex],
+    real_count: usize,
+    triangles: &[usize],
+    halfedges: &[usize],
+    transform: &OutputTransform,
+    out: &mut VoronoiBuffers,
+) {
+    let VoronoiBuffers {
+        cells,
+        edges,
+        centers,
+        inedges,
+        polygon,
+    } = out;
+    cells.clear();
+    edges.clear();
+
+    centers.clear();
+    centers.extend(
+        triangles
+            .chunks_exact(3)
+            .map(|t| circumcenter(&all_points[t[0]], &all_points[t[1]], &all_points[t[2]])),
+    );
+
+    // One incoming halfedge per point
+    inedges.clear();
+    inedges.resize(real_count, EMPTY);
+    for e in 0..triangles.len() {
+        let p = triangles[next_halfedge(e)];
+        if p < real_count && (inedges[p] == EMPTY || halfedges[e] == EMPTY) {
+            inedges[p] = e;
+        }
+    }
+
+    for (site, &e0) in inedges.iter().enumerate() {
+        if e0 == EMPTY {
+            continue;
+        }
+
+        // Walk triangles around the site, collecting circumcenters and edges
+        polygon.clear();
+        let mut closed = false;
+        let mut e = e0;
+        loop {
+            polygon.push(centers[e / 3]);
+            let out = next_halfedge(e);
+            let neighbor = triangles[next_halfedge(out)];
+            let opposite = halfedges[out];
+            if opposite == EMPTY {
+                break;
+            }
+            if neighbor >= real_count || site < neighbor {
+                let (x1, y1) = centers[out / 3];
+                let (x2, y2) = centers[opposite / 3];
+                edges.extend_from_slice(&[
+                    transform.x(x1),
+                    transform.y(y1),
+                    transform.x(x2),
+                    transform.y(y2),
+                ]);
+            }
+            e = opposite;
+            if e == e0 {
+                closed = true;
+                break;
+            }
+        }
+        if !closed || polygon.len() < 3 {
+            continue;
+        }
+
+        // Fan-triangulate the (convex) cell
+        let s = &all_points[site];
+        let (sx, sy) = (transform.x(s.x), transform.y(s.y));
+        let (x0, y0) = polygon[0];
+        for pair in polygon[1..].windows(2) {
+            for (x, y) in [(x0, y0), pair[0], pair[1]] {
+                cells.extend_from_slice(&[transform.x(x), transform.y(y), s.z, sy, sx, sy]);
+            }
+        }
+    }
+}
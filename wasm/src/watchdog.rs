@@ -0,0 +1,77 @@
+//! Self-healing watchdog: keeps a compact last-good copy of the points and rolls
+//! back to it when a tick leaves the simulation in an invalid state
+
+use crate::point::Point;
+
+/// Whether every point is finite and within a canvas-sized margin of the canvas
+pub fn points_healthy(points: &[Point], width: f32, height: f32) -> bool {
+    points.iter().all(|p| {
+        let finite = [p.x, p.y, p.z, p.base_x, p.base_y, p.dx, p.dy, p.depth]
+            .iter()
+            .all(|v| v.is_finite());
+        finite
+            && p.x > -width
+            && p.x < width * 2.0
+            && p.y > -height
+            && p.y < height * 2.0
+    })
+}
+
+/// Periodic last-good snapshot and rollback bookkeeping
+#[derive(Clone)]
+pub struct Watchdog {
+    /// Frames between snapshots
+    interval: f32,
+    /// Frames since the last snapshot
+    elapsed: f32,
+    /// Last healthy points and simulation time (frames); empty until the first snapshot
+    good_points: Vec<Point>,
+    good_time: f32,
+    /// Rollbacks not yet reported to the host
+    pending_rollbacks: u32,
+}
+
+impl Watchdog {
+    pub fn new(interval_frames: f32) -> Self {
+        Self {
+            interval: interval_frames.max(1.0),
+            elapsed: 0.0,
+            good_points: Vec::new(),
+            good_time: 0.0,
+            pending_rollbacks: 0,
+        }
+    }
+
+    /// Record a healthy frame, snapshotting when the interval has passed (or none exists)
+    pub fn healthy(&mut self, delta_time: f32, points: &[Point], time: f32) {
+        self.elapsed += delta_time;
+        if self.elapsed >= self.interval || self.good_points.is_empty() {
+            self.elapsed = 0.0;
+            self.good_points.clear();
+            self.good_points.extend_from_slice(points);
+            self.good_time = time;
+        }
+    }
+
+    /// Restore the last good state into `points`; returns its time, None without a snapshot
+    pub fn roll_back(&mut self, points: &mut Vec<Point>) -> Option<f32> {
+        if self.good_points.is_empty() {
+            return None;
+        }
+        points.clear();
+        points.extend_from_slice(&self.good_points);
+        self.elapsed = 0.0;
+        self.pending_rollbacks += 1;
+        Some(self.good_time)
+    }
+
+    /// Drop the snapshot after changes it cannot survive (resize, point count)
+    pub fn invalidate(&mut self) {
+        self.good_points.clear();
+    }
+
+    /// Rollbacks since the last call
+    pub fn take_rollbacks(&mut self) -> u32 {
+        std::mem::take(&mut self.pending_rollbacks)
+    }
+}